@@ -1,13 +1,17 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 //! Criterion benchmarks for pq-mayo keygen, sign, and verify.
+//!
+//! With `--features internals`, the stages of signing are also timed
+//! individually, and with `--features gf16-internals` the scalar GF(16)
+//! multiply is compared against a multiply-free formulation.
 
 use criterion::{Criterion, criterion_group, criterion_main};
 use pq_mayo::{
     ExpandedSigningKey, ExpandedVerifyingKey, KeyPair, Mayo1, Mayo2, Mayo3, Mayo5, MayoParameter,
     VerificationContext,
 };
-use signature::{Signer, Verifier};
+use signature::Signer;
 
 fn bench_keygen<P: MayoParameter>(c: &mut Criterion) {
     c.bench_function(&format!("{}/keygen", P::NAME), |b| {
@@ -37,6 +41,24 @@ fn bench_sign_expanded<P: MayoParameter>(c: &mut Criterion) {
     });
 }
 
+/// Expanding both halves of one keypair, separately and with the shared
+/// P1/P2 expansion of `KeyPair::expand`.
+fn bench_expand_keypair<P: MayoParameter>(c: &mut Criterion) {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen");
+
+    let mut group = c.benchmark_group(format!("{}/expand-keypair", P::NAME));
+    group.bench_function("separate", |b| {
+        b.iter(|| {
+            (
+                keypair.signing_key().expand(),
+                keypair.verifying_key().expand(),
+            )
+        });
+    });
+    group.bench_function("shared", |b| b.iter(|| keypair.expand()));
+    group.finish();
+}
+
 fn bench_verify<P: MayoParameter>(c: &mut Criterion) {
     let mut rng = rand::rng();
     let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen");
@@ -73,42 +95,122 @@ fn bench_verify_context<P: MayoParameter>(c: &mut Criterion) {
     });
 }
 
+/// Time each stage of one signing attempt on its own.
+///
+/// Every stage reruns from the output of the previous one, so the stages are
+/// run once in order before timing starts.
+#[cfg(feature = "internals")]
+fn bench_sign_stages<P: MayoParameter>(c: &mut Criterion) {
+    use pq_mayo::internals::SignStages;
+
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen");
+    let mut stages = SignStages::<P>::new(keypair.signing_key()).expect("stages");
+    stages.compute_m_and_vpv();
+    stages.compute_a();
+
+    let mut group = c.benchmark_group(format!("{}/sign-stages", P::NAME));
+    group.bench_function("expand_sk", |b| {
+        b.iter(|| stages.expand_secret_key().expect("expand"));
+    });
+    group.bench_function("compute_m_and_vpv", |b| {
+        b.iter(|| stages.compute_m_and_vpv());
+    });
+    group.bench_function("compute_a", |b| {
+        b.iter(|| stages.compute_a());
+    });
+    group.bench_function("ef", |b| {
+        b.iter(|| stages.echelon_form());
+    });
+    group.bench_function("sample_solution", |b| {
+        b.iter(|| stages.sample_solution());
+    });
+    group.finish();
+}
+
+/// `gf16::mul` against selecting the shifted operand with masks, the
+/// alternative considered for it. Both are branch-free and table-free.
+#[cfg(feature = "gf16-internals")]
+fn bench_gf16_mul(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    fn masked_mul(a: u8, b: u8) -> u8 {
+        let (a, b) = (a & 0x0f, b & 0x0f);
+        let mut p = 0u8;
+        for i in 0..4 {
+            p ^= (b << i) & 0u8.wrapping_sub((a >> i) & 1);
+        }
+        let top_p = p & 0xf0;
+        (p ^ (top_p >> 4) ^ (top_p >> 3)) & 0x0f
+    }
+
+    fn all_products(mul: fn(u8, u8) -> u8) -> u8 {
+        let mut acc = 0u8;
+        for a in 0..16u8 {
+            for b in 0..16u8 {
+                acc ^= mul(black_box(a), black_box(b));
+            }
+        }
+        acc
+    }
+
+    let mut group = c.benchmark_group("gf16/mul");
+    group.bench_function("mul", |b| b.iter(|| all_products(pq_mayo::gf16::mul)));
+    group.bench_function("masked", |b| b.iter(|| all_products(masked_mul)));
+    group.finish();
+}
+
 fn mayo1_benches(c: &mut Criterion) {
     bench_keygen::<Mayo1>(c);
     bench_sign::<Mayo1>(c);
     bench_sign_expanded::<Mayo1>(c);
+    bench_expand_keypair::<Mayo1>(c);
     bench_verify::<Mayo1>(c);
     bench_verify_expanded::<Mayo1>(c);
     bench_verify_context::<Mayo1>(c);
+    #[cfg(feature = "internals")]
+    bench_sign_stages::<Mayo1>(c);
 }
 
 fn mayo2_benches(c: &mut Criterion) {
     bench_keygen::<Mayo2>(c);
     bench_sign::<Mayo2>(c);
     bench_sign_expanded::<Mayo2>(c);
+    bench_expand_keypair::<Mayo2>(c);
     bench_verify::<Mayo2>(c);
     bench_verify_expanded::<Mayo2>(c);
     bench_verify_context::<Mayo2>(c);
+    #[cfg(feature = "internals")]
+    bench_sign_stages::<Mayo2>(c);
 }
 
 fn mayo3_benches(c: &mut Criterion) {
     bench_keygen::<Mayo3>(c);
     bench_sign::<Mayo3>(c);
     bench_sign_expanded::<Mayo3>(c);
+    bench_expand_keypair::<Mayo3>(c);
     bench_verify::<Mayo3>(c);
     bench_verify_expanded::<Mayo3>(c);
     bench_verify_context::<Mayo3>(c);
+    #[cfg(feature = "internals")]
+    bench_sign_stages::<Mayo3>(c);
 }
 
 fn mayo5_benches(c: &mut Criterion) {
     bench_keygen::<Mayo5>(c);
     bench_sign::<Mayo5>(c);
     bench_sign_expanded::<Mayo5>(c);
+    bench_expand_keypair::<Mayo5>(c);
     bench_verify::<Mayo5>(c);
     bench_verify_expanded::<Mayo5>(c);
     bench_verify_context::<Mayo5>(c);
+    #[cfg(feature = "internals")]
+    bench_sign_stages::<Mayo5>(c);
 }
 
+#[cfg(feature = "gf16-internals")]
+criterion_group!(gf16_benches, bench_gf16_mul);
+
 criterion_group!(
     benches,
     mayo1_benches,
@@ -116,4 +218,7 @@ criterion_group!(
     mayo3_benches,
     mayo5_benches
 );
+#[cfg(feature = "gf16-internals")]
+criterion_main!(benches, gf16_benches);
+#[cfg(not(feature = "gf16-internals"))]
 criterion_main!(benches);
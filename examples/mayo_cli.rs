@@ -0,0 +1,122 @@
+//! A small command-line front end to the library.
+//!
+//! ```text
+//! mayo_cli keygen <params> <sk-file> <pk-file> [seed-hex]
+//! mayo_cli sign   <params> <sk-file>              < message > signature
+//! mayo_cli verify <params> <pk-file> <signature>  < message
+//! ```
+//!
+//! `<params>` is a parameter set name such as `MAYO_1` (case and the
+//! underscore are optional, so `mayo1` works too). The signing key is
+//! written as its raw compact bytes, the verifying key and signatures as
+//! hex. `sign` and `verify` read the message from stdin; `verify` exits
+//! with status 1 if the signature does not verify.
+//!
+//! ```text
+//! cargo run --example mayo_cli -- keygen mayo2 sk.bin pk.hex
+//! echo hello | cargo run --example mayo_cli -- sign mayo2 sk.bin > sig.hex
+//! echo hello | cargo run --example mayo_cli -- verify mayo2 pk.hex "$(cat sig.hex)"
+//! ```
+
+use pq_mayo::{
+    KeyPair, Mayo1, Mayo2, Mayo3, Mayo5, MayoParameter, Signature, SigningKey, VerifyingKey,
+};
+use std::io::Read;
+use std::process::ExitCode;
+
+type CliResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+const USAGE: &str = "usage:
+  mayo_cli keygen <params> <sk-file> <pk-file> [seed-hex]
+  mayo_cli sign   <params> <sk-file>              < message
+  mayo_cli verify <params> <pk-file> <signature>  < message
+params: MAYO_1, MAYO_2, MAYO_3 or MAYO_5";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [command, params, rest @ ..] = args.as_slice() else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    let result = match normalize(params).as_str() {
+        "MAYO1" => run::<Mayo1>(command, rest),
+        "MAYO2" => run::<Mayo2>(command, rest),
+        "MAYO3" => run::<Mayo3>(command, rest),
+        "MAYO5" => run::<Mayo5>(command, rest),
+        _ => Err(format!("unknown parameter set {params:?}").into()),
+    };
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// `MAYO_1`, `mayo1` and `Mayo-1` all name the same parameter set.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+fn run<P: MayoParameter>(command: &str, args: &[String]) -> CliResult<ExitCode> {
+    match (command, args) {
+        ("keygen", [sk_file, pk_file]) => keygen::<P>(sk_file, pk_file, None),
+        ("keygen", [sk_file, pk_file, seed]) => keygen::<P>(sk_file, pk_file, Some(seed)),
+        ("sign", [sk_file]) => sign::<P>(sk_file),
+        ("verify", [pk_file, signature]) => verify::<P>(pk_file, signature),
+        _ => Err(format!("bad arguments for {command:?}").into()),
+    }
+}
+
+fn keygen<P: MayoParameter>(
+    sk_file: &str,
+    pk_file: &str,
+    seed: Option<&str>,
+) -> CliResult<ExitCode> {
+    let keypair = match seed {
+        Some(seed) => KeyPair::<P>::from_seed(&hex::decode(seed)?)?,
+        None => KeyPair::<P>::generate(&mut rand::rng())?,
+    };
+    std::fs::write(sk_file, keypair.signing_key())?;
+    std::fs::write(pk_file, format!("{}\n", keypair.verifying_key()))?;
+    eprintln!(
+        "{}: wrote a {}-byte signing key to {sk_file} and a {}-byte verifying key to {pk_file}",
+        P::NAME,
+        P::CSK_BYTES,
+        P::CPK_BYTES
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+fn sign<P: MayoParameter>(sk_file: &str) -> CliResult<ExitCode> {
+    let signing_key = SigningKey::<P>::try_from(std::fs::read(sk_file)?.as_slice())?;
+    let signature = signing_key.sign_with_rng(&mut rand::rng(), &read_stdin()?)?;
+    println!("{signature}");
+    Ok(ExitCode::SUCCESS)
+}
+
+fn verify<P: MayoParameter>(pk_file: &str, signature: &str) -> CliResult<ExitCode> {
+    let verifying_key: VerifyingKey<P> = std::fs::read_to_string(pk_file)?.trim().parse()?;
+    let signature: Signature<P> = signature.trim().parse()?;
+    match verifying_key.verify(&read_stdin()?, &signature) {
+        Ok(()) => {
+            println!("OK");
+            Ok(ExitCode::SUCCESS)
+        }
+        Err(e) if e.is_verification_failure() => {
+            println!("FAILED");
+            Ok(ExitCode::FAILURE)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn read_stdin() -> CliResult<Vec<u8>> {
+    let mut msg = Vec::new();
+    std::io::stdin().read_to_end(&mut msg)?;
+    Ok(msg)
+}
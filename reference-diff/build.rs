@@ -0,0 +1,28 @@
+//! Links the MAYO reference implementation for `tests/reference_diff.rs`.
+//!
+//! Does nothing unless `MAYO_REF_LIB_DIR` is set, so `cargo test
+//! --workspace` never needs the reference library.
+
+use std::env;
+
+fn main() {
+    println!("cargo::rustc-check-cfg=cfg(mayo_reference)");
+    println!("cargo::rerun-if-env-changed=MAYO_REF_LIB_DIR");
+    println!("cargo::rerun-if-env-changed=MAYO_REF_LIBS");
+    println!("cargo::rerun-if-env-changed=MAYO_REF_VARIANT");
+
+    let Some(dir) = env::var_os("MAYO_REF_LIB_DIR") else {
+        return;
+    };
+
+    let variant = env::var("MAYO_REF_VARIANT").unwrap_or_else(|_| "1".into());
+    let libs = env::var("MAYO_REF_LIBS")
+        .unwrap_or_else(|_| format!("static=mayo_{variant}_nistapi,static=mayo_{variant}"));
+
+    println!("cargo::rustc-link-search=native={}", dir.to_string_lossy());
+    for lib in libs.split(',').map(str::trim).filter(|l| !l.is_empty()) {
+        println!("cargo::rustc-link-lib={lib}");
+    }
+    println!("cargo::rustc-env=MAYO_REF_VARIANT={variant}");
+    println!("cargo::rustc-cfg=mayo_reference");
+}
@@ -0,0 +1,3 @@
+//! Differential tests of `pq-mayo` against the MAYO reference
+//! implementation. Everything lives in `tests/reference_diff.rs`; see the
+//! crate README for how to run it.
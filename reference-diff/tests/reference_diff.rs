@@ -0,0 +1,135 @@
+//! Differential tests against the MAYO reference implementation.
+//!
+//! Compiled only with `MAYO_REF_LIB_DIR` pointing at a build of the
+//! reference library; see the README for the other variables. Both implementations draw keys and salts from the NIST
+//! AES-256 CTR-DRBG, so seeding them identically must give byte-identical
+//! keys and signatures, and each must accept the other's signatures.
+
+#![cfg(mayo_reference)]
+
+use core::ffi::{c_int, c_ulonglong};
+use pq_mayo::kat::{NistDrbg, SEED_BYTES};
+use pq_mayo::{KeyPair, Mayo1, Mayo2, Mayo3, Mayo5, MayoParameter, Signature};
+use rand::{Rng, RngExt};
+
+unsafe extern "C" {
+    fn randombytes_init(
+        entropy_input: *const u8,
+        personalization_string: *const u8,
+        security_strength: c_int,
+    );
+    fn crypto_sign_keypair(pk: *mut u8, sk: *mut u8) -> c_int;
+    fn crypto_sign(
+        sm: *mut u8,
+        smlen: *mut c_ulonglong,
+        m: *const u8,
+        mlen: c_ulonglong,
+        sk: *const u8,
+    ) -> c_int;
+    fn crypto_sign_open(
+        m: *mut u8,
+        mlen: *mut c_ulonglong,
+        sm: *const u8,
+        smlen: c_ulonglong,
+        pk: *const u8,
+    ) -> c_int;
+}
+
+/// Keys and signed message produced by the reference implementation.
+struct Reference {
+    pk: Vec<u8>,
+    sk: Vec<u8>,
+    sm: Vec<u8>,
+}
+
+fn reference_keygen_sign<P: MayoParameter>(seed: &[u8; SEED_BYTES], msg: &[u8]) -> Reference {
+    let mut pk = vec![0u8; P::CPK_BYTES];
+    let mut sk = vec![0u8; P::CSK_BYTES];
+    let mut sm = vec![0u8; P::SIG_BYTES + msg.len()];
+    let mut smlen: c_ulonglong = 0;
+    let mlen = c_ulonglong::try_from(msg.len()).expect("message length fits");
+    // SAFETY: every buffer has the length the NIST API requires for this
+    // parameter set, and the seed is the 48 bytes `randombytes_init` reads.
+    unsafe {
+        randombytes_init(seed.as_ptr(), core::ptr::null(), 256);
+        assert_eq!(crypto_sign_keypair(pk.as_mut_ptr(), sk.as_mut_ptr()), 0);
+        assert_eq!(
+            crypto_sign(sm.as_mut_ptr(), &mut smlen, msg.as_ptr(), mlen, sk.as_ptr()),
+            0
+        );
+    }
+    assert_eq!(usize::try_from(smlen).expect("fits"), sm.len());
+    Reference { pk, sk, sm }
+}
+
+fn reference_open(sm: &[u8], pk: &[u8]) -> bool {
+    let mut m = vec![0u8; sm.len()];
+    let mut mlen: c_ulonglong = 0;
+    let smlen = c_ulonglong::try_from(sm.len()).expect("length fits");
+    // SAFETY: `m` is at least as long as `sm`, and `pk` is a full public key.
+    unsafe { crypto_sign_open(m.as_mut_ptr(), &mut mlen, sm.as_ptr(), smlen, pk.as_ptr()) == 0 }
+}
+
+fn compare<P: MayoParameter>(seed: &[u8; SEED_BYTES], msg: &[u8]) {
+    let reference = reference_keygen_sign::<P>(seed, msg);
+
+    let mut drbg = NistDrbg::new(seed);
+    let keypair = KeyPair::<P>::generate(&mut drbg).expect("keygen failed");
+    let sig = keypair
+        .signing_key()
+        .sign_with_rng(&mut drbg, msg)
+        .expect("signing failed");
+    let mut sm = sig.to_vec();
+    sm.extend_from_slice(msg);
+
+    let seed_hex = hex::encode(seed);
+    assert_eq!(
+        keypair.verifying_key().as_ref(),
+        reference.pk,
+        "pk, seed {seed_hex}"
+    );
+    assert_eq!(
+        keypair.signing_key().as_ref(),
+        reference.sk,
+        "sk, seed {seed_hex}"
+    );
+    assert_eq!(sm, reference.sm, "signature, seed {seed_hex}");
+
+    // Each side accepts the other's signature, and rejects a tampered one.
+    assert!(reference_open(&sm, keypair.verifying_key().as_ref()));
+    let theirs = Signature::<P>::try_from(&reference.sm[..P::SIG_BYTES]).expect("length");
+    assert!(keypair.verifying_key().verify(msg, &theirs).is_ok());
+    sm[0] ^= 1;
+    assert!(!reference_open(&sm, keypair.verifying_key().as_ref()));
+}
+
+fn run<P: MayoParameter>() {
+    let iterations = std::env::var("MAYO_REF_ITERATIONS")
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(1000usize);
+    let mut rng = rand::rng();
+    for _ in 0..iterations {
+        let mut seed = [0u8; SEED_BYTES];
+        rng.fill_bytes(&mut seed);
+        let mut msg = vec![0u8; rng.random_range(0..=256)];
+        rng.fill_bytes(&mut msg);
+        compare::<P>(&seed, &msg);
+    }
+}
+
+#[test]
+fn matches_reference_implementation() -> Result<(), String> {
+    match env!("MAYO_REF_VARIANT") {
+        "1" => run::<Mayo1>(),
+        "2" => run::<Mayo2>(),
+        "3" => run::<Mayo3>(),
+        "5" => run::<Mayo5>(),
+        other => {
+            return Err(format!(
+                "MAYO_REF_VARIANT must be 1, 2, 3 or 5, not {other:?}"
+            ));
+        }
+    }
+    Ok(())
+}
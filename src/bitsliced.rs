@@ -452,6 +452,12 @@ fn dispatch_mul_add(src: &[u64], a: u8, acc: &mut [u64], legs: usize) {
 }
 
 /// Multiply-accumulate: `acc += src * a` where `a` is a GF(16) scalar.
+///
+/// The `mul_table(a)` build is deliberately kept per call rather than offered
+/// as a caller-supplied table: the matrix products in `matrix_ops` accumulate
+/// through the multiply-free bin ladder, and the only remaining caller (`ef`'s
+/// row elimination) uses a different scalar for every row, so there is no
+/// repeated scalar to hoist a table out for.
 #[inline]
 pub(crate) fn m_vec_mul_add(src: &[u64], a: u8, acc: &mut [u64], m_vec_limbs: usize) {
     // Re-slice to the exact length so a too-short slice panics here (safe) rather
@@ -1,12 +1,44 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 //! Encoding and decoding of nibble-packed GF(16) elements.
+//!
+//! # Constant time
+//!
+//! These functions also handle secret data (the oil space `O` in key
+//! expansion, the vinegar values during signing), so none of them may branch
+//! on or index by element values. Every loop bound and every index is a
+//! function of the length arguments alone, and elements are moved with
+//! shifts and masks only. The memory access pattern is therefore the same for
+//! all inputs of a given length. Keep it that way when editing this module.
+//!
+//! # Byte order
+//!
+//! Every limb <-> byte conversion here is little-endian on every host: limbs
+//! are built with `u64::from_le_bytes` and taken apart with shifts of
+//! `8 * byte_index`. Never replace these with a native-endian reinterpretation
+//! (a pointer cast, `bytemuck`, `to_ne_bytes`) even though it would be
+//! equivalent on x86 and aarch64; on big-endian targets such as s390x it would
+//! silently change every key and signature. `tests/endianness.rs` pins the
+//! encoded output to catch that.
+
+/// Whether the padding nibble after `len` packed nibbles is zero.
+///
+/// With an odd `len` the high half of byte `len / 2` is padding that
+/// [`decode`] ignores; a nonzero value there would be a second encoding of
+/// the same nibbles. An even `len` has no padding.
+pub(crate) fn has_zero_padding(input: &[u8], len: usize) -> bool {
+    len.is_multiple_of(2) || input.get(len / 2).is_some_and(|b| b >> 4 == 0)
+}
 
 /// Decode packed nibbles into individual bytes.
 ///
 /// Each byte in `input` contains two GF(16) elements (low nibble first).
 /// Produces `len` output bytes, each containing a single GF(16) element.
+/// Reads the first `len.div_ceil(2)` bytes of `input`.
+/// Constant time in the contents of `input`.
 pub(crate) fn decode(input: &[u8], output: &mut [u8], len: usize) {
+    debug_assert!(input.len() >= len.div_ceil(2), "decode reads past input");
+    debug_assert!(output.len() >= len, "decode writes past output");
     let mut out_idx = 0;
     let mut i = 0;
     while i < len / 2 {
@@ -23,6 +55,7 @@ pub(crate) fn decode(input: &[u8], output: &mut [u8], len: usize) {
 /// Encode individual GF(16) bytes into packed nibbles.
 ///
 /// Each pair of input bytes is packed into one output byte (low nibble first).
+/// Constant time in the contents of `input`.
 pub(crate) fn encode(input: &[u8], output: &mut [u8], len: usize) {
     let mut in_idx = 0;
     let mut i = 0;
@@ -39,7 +72,7 @@ pub(crate) fn encode(input: &[u8], output: &mut [u8], len: usize) {
 /// Unpack packed byte vectors into bitsliced m-vectors.
 ///
 /// Each vector occupies `m/2` bytes in packed form and `m_vec_limbs * 8` bytes
-/// in unpacked (bitsliced u64) form.
+/// in unpacked (bitsliced u64) form. Constant time in the contents of `input`.
 pub(crate) fn unpack_m_vecs(input: &[u8], output: &mut [u64], vecs: usize, m: usize) {
     let m_vec_limbs = m.div_ceil(16);
     let packed_size = m / 2;
@@ -59,7 +92,7 @@ pub(crate) fn unpack_m_vecs(input: &[u8], output: &mut [u64], vecs: usize, m: us
 /// Pack bitsliced m-vectors into packed byte vectors.
 ///
 /// Each vector occupies `m_vec_limbs * 8` bytes in bitsliced form and `m/2` bytes
-/// in packed form.
+/// in packed form. Constant time in the contents of `input`.
 pub(crate) fn pack_m_vecs(input: &[u64], output: &mut [u8], vecs: usize, m: usize) {
     let m_vec_limbs = m.div_ceil(16);
     let packed_size = m / 2;
@@ -74,3 +107,192 @@ pub(crate) fn pack_m_vecs(input: &[u64], output: &mut [u8], vecs: usize, m: usiz
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::{MAX_M, MayoParameter};
+    use proptest::prelude::*;
+
+    #[test]
+    fn encode_decode_roundtrip() {
+        for len in [0usize, 1, 2, 7, 8, 860, 1298] {
+            let nibbles: Vec<u8> = (0..len).map(|i| [3u8, 10, 1, 8][i % 4]).collect();
+            let mut packed = vec![0u8; len.div_ceil(2)];
+            encode(&nibbles, &mut packed, len);
+            let mut decoded = vec![0u8; len];
+            decode(&packed, &mut decoded, len);
+            assert_eq!(nibbles, decoded, "len={len}");
+        }
+    }
+
+    #[test]
+    fn zero_padding() {
+        let nibbles = [7u8, 1, 12];
+        let mut packed = [0u8; 2];
+        encode(&nibbles, &mut packed, 3);
+        assert!(has_zero_padding(&packed, 3));
+        assert!(has_zero_padding(&packed, 2));
+
+        packed[1] |= 0x50;
+        assert!(!has_zero_padding(&packed, 3));
+        // The even prefix has no padding to check.
+        assert!(has_zero_padding(&packed, 2));
+        // Too short to hold the padding byte at all.
+        assert!(!has_zero_padding(&packed[..1], 3));
+    }
+
+    /// Nibble vectors of any length up to a few thousand, odd lengths included.
+    fn nibbles() -> impl Strategy<Value = Vec<u8>> {
+        proptest::collection::vec(0u8..16, 0..2000)
+    }
+
+    /// `vecs` m-vectors of `m` nibbles each, with the nibbles above `m` in the
+    /// last limb cleared, for an even `m` up to `MAX_M`.
+    fn m_vecs() -> impl Strategy<Value = (usize, usize, Vec<u64>)> {
+        (1usize..=MAX_M / 2, 0usize..8).prop_flat_map(|(half_m, vecs)| {
+            let m = 2 * half_m;
+            let limbs = m.div_ceil(16);
+            proptest::collection::vec(any::<u64>(), vecs * limbs).prop_map(move |mut v| {
+                if m % 16 != 0 {
+                    let mask = (1u64 << ((m % 16) * 4)) - 1;
+                    v.iter_mut()
+                        .skip(limbs - 1)
+                        .step_by(limbs)
+                        .for_each(|l| *l &= mask);
+                }
+                (m, vecs, v)
+            })
+        })
+    }
+
+    fn encode_then_decode(nibbles: &[u8]) -> Vec<u8> {
+        let len = nibbles.len();
+        let mut packed = vec![0u8; len.div_ceil(2)];
+        encode(nibbles, &mut packed, len);
+        let mut decoded = vec![0u8; len];
+        decode(&packed, &mut decoded, len);
+        decoded
+    }
+
+    fn pack_then_unpack(m: usize, vecs: usize, limbs: &[u64]) -> Vec<u64> {
+        let mut packed = vec![0u8; vecs * m / 2];
+        pack_m_vecs(limbs, &mut packed, vecs, m);
+        let mut unpacked = vec![u64::MAX; limbs.len()];
+        unpack_m_vecs(&packed, &mut unpacked, vecs, m);
+        unpacked
+    }
+
+    proptest! {
+        #[test]
+        fn decode_inverts_encode(x in nibbles()) {
+            prop_assert_eq!(encode_then_decode(&x), x);
+        }
+
+        #[test]
+        fn encode_inverts_decode(bytes in proptest::collection::vec(any::<u8>(), 0..1000), odd in any::<bool>()) {
+            // An odd length drops the high nibble of the last byte.
+            let len = (2 * bytes.len()).saturating_sub(usize::from(odd));
+            let mut nibbles = vec![0u8; len];
+            decode(&bytes, &mut nibbles, len);
+            let mut packed = vec![0u8; len.div_ceil(2)];
+            encode(&nibbles, &mut packed, len);
+            let mut expected = bytes.clone();
+            if odd && let Some(last) = expected.last_mut() {
+                *last &= 0x0f;
+            }
+            prop_assert_eq!(packed, expected);
+        }
+
+        #[test]
+        fn unpack_inverts_pack((m, vecs, limbs) in m_vecs()) {
+            prop_assert_eq!(pack_then_unpack(m, vecs, &limbs), limbs);
+        }
+    }
+
+    /// Lengths 0 and 1 and the exact sizes each parameter set encodes.
+    #[test]
+    fn codec_roundtrip_edge_sizes() {
+        fn sizes<P: MayoParameter>() {
+            for len in [0, 1, P::M, P::V, P::K * P::O, P::K * P::N, P::V * P::O] {
+                let x: Vec<u8> = (0..len).map(|i| [15u8, 0, 9, 6, 1][i % 5]).collect();
+                assert_eq!(encode_then_decode(&x), x, "{} len={len}", P::NAME);
+            }
+            let vecs = P::P3_LIMBS / P::M_VEC_LIMBS;
+            let mut limbs: Vec<u64> = (0..P::P3_LIMBS as u64)
+                .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+                .collect();
+            if P::M % 16 != 0 {
+                let mask = (1u64 << ((P::M % 16) * 4)) - 1;
+                for v in limbs.chunks_exact_mut(P::M_VEC_LIMBS) {
+                    v[P::M_VEC_LIMBS - 1] &= mask;
+                }
+            }
+            assert_eq!(pack_then_unpack(P::M, vecs, &limbs), limbs, "{}", P::NAME);
+        }
+        sizes::<crate::Mayo1>();
+        sizes::<crate::Mayo2>();
+        sizes::<crate::Mayo3>();
+        sizes::<crate::Mayo5>();
+    }
+
+    /// dudect-style check that `decode` timing does not depend on the input.
+    ///
+    /// Times decoding of a fixed all-zero buffer against random buffers in
+    /// randomly interleaved order and applies Welch's t-test to the two
+    /// timing populations. |t| above 10 is strong evidence of a leak. Wall
+    /// clock timing is noisy on shared machines, so this is not run by default:
+    ///   cargo test --release -p pq-mayo --lib decode_timing -- --ignored --nocapture
+    #[test]
+    #[ignore = "timing test; run with --release --ignored --nocapture"]
+    #[allow(clippy::cast_precision_loss)]
+    fn decode_timing_is_input_independent() {
+        use std::hint::black_box;
+        use std::time::Instant;
+
+        const LEN: usize = 1298;
+        const SAMPLES: usize = 200_000;
+
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        let fixed = vec![0u8; LEN.div_ceil(2)];
+        let mut random = vec![0u8; LEN.div_ceil(2)];
+        let mut out = vec![0u8; LEN];
+        let mut stats = [(0f64, 0f64, 0f64); 2];
+
+        for _ in 0..SAMPLES {
+            let class = usize::from(next() & 1 == 1);
+            if class == 1 {
+                random.iter_mut().for_each(|b| *b = next().to_le_bytes()[0]);
+            }
+            let input = if class == 0 { &fixed } else { &random };
+            let start = Instant::now();
+            decode(black_box(input), &mut out, LEN);
+            black_box(&out);
+            let ns = start.elapsed().as_nanos() as f64;
+
+            // Welford's online mean/variance.
+            let (n, mean, m2) = &mut stats[class];
+            *n += 1.0;
+            let delta = ns - *mean;
+            *mean += delta / *n;
+            *m2 += delta * (ns - *mean);
+        }
+
+        let [(n0, mean0, m20), (n1, mean1, m21)] = stats;
+        let var0 = m20 / (n0 - 1.0);
+        let var1 = m21 / (n1 - 1.0);
+        let t = (mean0 - mean1) / (var0 / n0 + var1 / n1).sqrt();
+        println!("decode timing: fixed {mean0:.1} ns, random {mean1:.1} ns, t = {t:.2}");
+        assert!(
+            t.abs() < 10.0,
+            "decode timing depends on input (t = {t:.2})"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! COSE_Key and COSE_Sign1 support for MAYO keys.
+//!
+//! MAYO has no IANA-registered COSE algorithm yet, so the `alg` values used
+//! here are the text form of [`MayoParameter::OID`] (for example
+//! `"1.3.9999.8.1.3"` for MAYO-1). Verifying keys are encoded
+//! with the `AKP` key type, placing the compact public key in the `pub`
+//! parameter.
+
+use crate::error::{Error, Result};
+use crate::mayo_signature::Signature;
+use crate::params::MayoParameter;
+use crate::signing_key::SigningKey;
+use crate::verifying_key::VerifyingKey;
+use coset::cbor::value::Value;
+use coset::{
+    Algorithm, CoseError, CoseKey, CoseKeyBuilder, CoseSign1, CoseSign1Builder, HeaderBuilder,
+    KeyType, Label, iana,
+};
+use signature::Signer;
+
+/// The COSE `alg` value for parameter set `P`, if it has an OID.
+fn algorithm<P: MayoParameter>() -> Option<Algorithm> {
+    P::OID.map(|oid| Algorithm::Text(oid.to_string()))
+}
+
+impl<P: MayoParameter> VerifyingKey<P> {
+    /// Encode this key as a COSE_Key.
+    ///
+    /// `alg` is left unset for a parameter set without an OID.
+    pub fn to_cose_key(&self) -> CoseKey {
+        let mut key = CoseKeyBuilder::new()
+            .key_type(iana::KeyType::AKP)
+            .param(
+                iana::AkpKeyParameter::Pub as i64,
+                Value::Bytes(self.bytes.clone()),
+            )
+            .build();
+        key.alg = algorithm::<P>();
+        key
+    }
+
+    /// Decode a COSE_Key produced by [`to_cose_key`](Self::to_cose_key).
+    ///
+    /// The key type must be `AKP` and `alg` must name this parameter set.
+    pub fn from_cose_key(key: &CoseKey) -> coset::Result<Self> {
+        if key.kty != KeyType::Assigned(iana::KeyType::AKP) {
+            return Err(CoseError::UnexpectedItem("key type", "AKP"));
+        }
+        if algorithm::<P>().is_none() || key.alg != algorithm::<P>() {
+            return Err(CoseError::UnexpectedItem("algorithm", "MAYO OID"));
+        }
+        let pub_label = Label::Int(iana::AkpKeyParameter::Pub as i64);
+        let bytes = key
+            .params
+            .iter()
+            .find_map(|(label, value)| (*label == pub_label).then_some(value))
+            .and_then(Value::as_bytes)
+            .ok_or(CoseError::UnexpectedItem("missing parameter", "pub"))?;
+        Self::try_from(bytes.as_slice())
+            .map_err(|_| CoseError::UnexpectedItem("key length", "P::CPK_BYTES"))
+    }
+
+    /// Verify a COSE_Sign1 over its attached payload and `external_aad`.
+    ///
+    /// Fails if the protected `alg` header does not name this parameter set.
+    pub fn verify_cose_sign1(&self, sign1: &CoseSign1, external_aad: &[u8]) -> Result<()> {
+        if algorithm::<P>().is_none() || sign1.protected.header.alg != algorithm::<P>() {
+            return Err(Error::VerificationFailed);
+        }
+        sign1.verify_signature(external_aad, |sig, tbs| {
+            let sig = Signature::<P>::try_from(sig)?;
+            self.verify(tbs, &sig)
+        })
+    }
+}
+
+impl<P: MayoParameter> SigningKey<P> {
+    /// Sign `payload` as a COSE_Sign1 with an attached payload.
+    ///
+    /// The protected header carries the MAYO `alg`, and the signature covers
+    /// the standard `Sig_structure` including `external_aad`. Returns
+    /// [`Error::Signing`] for a parameter set without an OID.
+    pub fn sign_cose_sign1(&self, payload: &[u8], external_aad: &[u8]) -> Result<CoseSign1> {
+        let alg = algorithm::<P>().ok_or(Error::Signing)?;
+        let protected = HeaderBuilder::new().algorithm_label(alg).build();
+        Ok(CoseSign1Builder::new()
+            .protected(protected)
+            .payload(payload.to_vec())
+            .try_create_signature(external_aad, |tbs| {
+                self.try_sign(tbs)
+                    .map(Vec::from)
+                    .map_err(|_| Error::Signing)
+            })?
+            .build())
+    }
+}
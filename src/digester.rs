@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Message digest step of signing and verification.
+//!
+//! MAYO first compresses the message to `DIGEST_BYTES` bytes and works only
+//! with that digest afterwards. Signing and verification are generic over
+//! [`Digester`] for this one step, so a deployment that binds the message
+//! with another hash first, e.g. for hash-then-sign infrastructure, can swap
+//! it without touching the XOF expansions. [`XofDigester`] is the
+//! specification's choice and the one the stable API uses; the `internals`
+//! feature exports [`Digester`] with sign and verify entry points taking
+//! another.
+
+use crate::params::MayoParameter;
+use crate::xof::{Shake256Xof, Xof};
+use core::marker::PhantomData;
+
+/// Compresses a message to the digest that is hashed with the salt.
+///
+/// Implement it to sign and verify with
+/// [`internals::sign_with_digester`](crate::internals::sign_with_digester)
+/// and
+/// [`internals::verify_with_digester`](crate::internals::verify_with_digester).
+/// The digest should be collision resistant: two messages with the same
+/// digest share every signature.
+pub trait Digester {
+    /// Fill `out` (`DIGEST_BYTES` long) with the digest of `msg`.
+    fn digest(msg: &[u8], out: &mut [u8]);
+}
+
+/// The digest of the specification: the first `DIGEST_BYTES` bytes of the
+/// scheme's XOF, i.e. SHAKE256, over the message.
+pub(crate) struct XofDigester<X>(PhantomData<X>);
+
+impl<X: Xof> Digester for XofDigester<X> {
+    fn digest(msg: &[u8], out: &mut [u8]) {
+        X::hash(&[msg], out);
+    }
+}
+
+/// A message that already is the digest, `DIGEST_BYTES` long.
+///
+/// Lets a digest computed elsewhere, e.g. streamed by `digest_reader`,
+/// returned by [`message_digest`] or finalized by a
+/// `signature::DigestSigner` caller, be signed and verified exactly like
+/// the message it was computed from.
+pub(crate) struct Prehashed;
+
+impl Digester for Prehashed {
+    fn digest(msg: &[u8], out: &mut [u8]) {
+        out.copy_from_slice(msg);
+    }
+}
+
+/// The digest MAYO signing computes from a message before anything else,
+/// from [`message_digest`].
+///
+/// It can only be made by hashing a message, so
+/// [`SigningKey::sign_with_precomputed_digest`](crate::SigningKey::sign_with_precomputed_digest)
+/// always signs a real message rather than bytes of the caller's choosing.
+/// The bytes can be read with [`AsRef`] but not supplied.
+#[derive(Clone)]
+pub struct MessageDigest<P: MayoParameter> {
+    bytes: Vec<u8>,
+    _marker: PhantomData<P>,
+}
+
+impl<P: MayoParameter> AsRef<[u8]> for MessageDigest<P> {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<P: MayoParameter> PartialEq for MessageDigest<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl<P: MayoParameter> Eq for MessageDigest<P> {}
+
+impl<P: MayoParameter> core::fmt::Debug for MessageDigest<P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MessageDigest")
+            .field("variant", &P::NAME)
+            .field("bytes", &hex::encode(self.as_ref()))
+            .finish()
+    }
+}
+
+/// Hash `msg` to the `DIGEST_BYTES`-long digest signing starts from,
+/// `SHAKE256(msg)` truncated.
+///
+/// Pass it to
+/// [`SigningKey::sign_with_precomputed_digest`](crate::SigningKey::sign_with_precomputed_digest)
+/// to sign one message under many keys while hashing it only once.
+pub fn message_digest<P: MayoParameter>(msg: &[u8]) -> MessageDigest<P> {
+    let mut bytes = vec![0u8; P::DIGEST_BYTES];
+    XofDigester::<Shake256Xof>::digest(msg, &mut bytes);
+    MessageDigest {
+        bytes,
+        _marker: PhantomData,
+    }
+}
+
+/// Size of the chunks [`digest_reader`] reads at a time.
+#[cfg(feature = "std")]
+const READ_CHUNK_BYTES: usize = 8192;
+
+/// Fill `out` with the [`XofDigester`] digest of everything `reader` yields,
+/// reading it in fixed-size chunks instead of all at once.
+#[cfg(feature = "std")]
+pub(crate) fn digest_reader<X: Xof>(
+    mut reader: impl std::io::Read,
+    out: &mut [u8],
+) -> std::io::Result<()> {
+    let mut xof = X::default();
+    let mut chunk = vec![0u8; READ_CHUNK_BYTES];
+    loop {
+        match reader.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => xof.absorb(&chunk[..n]),
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    xof.squeeze(out);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keygen::{expand_p1_p2, mayo_keypair_compact};
+    use crate::matrix_ops::P3Input;
+    use crate::sign::{SignScratch, expand_sk, mayo_sign_signature_with_expanded_sk};
+    use crate::verify::{VerifyScratch, mayo_verify, mayo_verify_split_with_scratch};
+    use crate::xof::Shake256Xof;
+    use crate::{Mayo1, MayoParameter};
+
+    /// SHAKE256 over a domain-separated message: a different digest than
+    /// the specification's, standing in for e.g. SHA3-512.
+    struct DomainDigester;
+
+    impl Digester for DomainDigester {
+        fn digest(msg: &[u8], out: &mut [u8]) {
+            Shake256Xof::hash(&[b"alt-digest", msg], out);
+        }
+    }
+
+    fn verify_with<D: Digester>(msg: &[u8], sig: &[u8], cpk: &[u8]) -> bool {
+        let pk = expand_p1_p2::<Mayo1>(&cpk[..Mayo1::PK_SEED_BYTES]).expect("expand");
+        let (p1, p2) = pk.split_at(Mayo1::P1_LIMBS);
+        let p3 = P3Input::Packed(&cpk[Mayo1::PK_SEED_BYTES..]);
+        let mut scratch = VerifyScratch::new::<Mayo1>();
+        mayo_verify_split_with_scratch::<Mayo1, Shake256Xof, D>(
+            msg,
+            sig,
+            p1,
+            p2,
+            p3,
+            &mut scratch.buffers::<Mayo1>(),
+        )
+        .is_ok()
+    }
+
+    #[test]
+    fn alternate_digester_sign_verify() {
+        let mut rng = rand::rng();
+        let mut cpk = vec![0u8; Mayo1::CPK_BYTES];
+        let mut csk = vec![0u8; Mayo1::CSK_BYTES];
+        mayo_keypair_compact::<Mayo1, Shake256Xof>(&mut cpk, &mut csk, &mut rng).expect("keygen");
+        let esk = expand_sk::<Mayo1, Shake256Xof>(&csk).expect("expand");
+
+        let msg = b"hash then sign";
+        let mut sig = vec![0u8; Mayo1::SIG_BYTES];
+        let mut scratch = SignScratch::<Mayo1>::new();
+        mayo_sign_signature_with_expanded_sk::<Mayo1, Shake256Xof, DomainDigester>(
+            &mut sig,
+            msg,
+            &csk,
+            &esk,
+            |r| r.fill(0),
+            &mut scratch,
+        )
+        .expect("sign");
+
+        assert!(verify_with::<DomainDigester>(msg, &sig, &cpk));
+        // The digest really changed: the specification's digest disagrees.
+        assert!(!verify_with::<XofDigester<Shake256Xof>>(msg, &sig, &cpk));
+        assert!(mayo_verify::<Mayo1, Shake256Xof>(msg, &sig, &cpk).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn digest_reader_matches_digest() {
+        // Longer than one chunk and not a multiple of it.
+        let msg: Vec<u8> = (0..=255u8)
+            .cycle()
+            .take(3 * READ_CHUNK_BYTES + 17)
+            .collect();
+        let mut expected = [0u8; 48];
+        XofDigester::<Shake256Xof>::digest(&msg, &mut expected);
+        let mut got = [0u8; 48];
+        digest_reader::<Shake256Xof>(msg.as_slice(), &mut got).expect("read");
+        assert_eq!(got, expected);
+    }
+
+    #[test]
+    fn default_digester_is_truncated_shake256() {
+        let mut expected = [0u8; 64];
+        Shake256Xof::hash(&[b"msg"], &mut expected);
+        let mut got = [0u8; 32];
+        XofDigester::<Shake256Xof>::digest(b"msg", &mut got);
+        assert_eq!(got, expected[..32]);
+    }
+}
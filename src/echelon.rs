@@ -4,6 +4,7 @@
 
 use crate::bitsliced::vec_mul_add_u64;
 use crate::gf16::inverse_f;
+use crate::params::{MAX_A_COLS, MAX_M};
 
 #[inline]
 fn low_u8(value: u64) -> u8 {
@@ -40,6 +41,24 @@ pub(crate) fn ct_compare_8(a: u8, b: u8) -> u8 {
     0u8.wrapping_sub(nonzero)
 }
 
+/// Constant-time select for u8: returns `a` where `mask` is all-ones and `b`
+/// where it is zero.
+///
+/// `mask` must be either `0x00` or `0xFF`, as produced by [`ct_compare_8`].
+#[inline]
+pub(crate) fn ct_select_u8(mask: u8, a: u8, b: u8) -> u8 {
+    (mask & a) | (!mask & b)
+}
+
+/// Constant-time select for u64: returns `a` where `mask` is all-ones and `b`
+/// where it is zero.
+///
+/// `mask` must be either `0` or `u64::MAX`, as produced by [`ct_compare_64`].
+#[inline]
+pub(crate) fn ct_select_u64(mask: u64, a: u64, b: u64) -> u64 {
+    (mask & a) | (!mask & b)
+}
+
 /// Pack a row of GF(16) nibbles into u64 limbs (safe version).
 fn ef_pack_m_vec_safe(input: &[u8], output: &mut [u64], ncols: usize) {
     for v in output.iter_mut() {
@@ -62,25 +81,48 @@ fn ef_pack_m_vec_safe(input: &[u8], output: &mut [u64], ncols: usize) {
     }
 }
 
-/// Unpack u64 limbs into a row of GF(16) nibbles (safe version).
-fn ef_unpack_m_vec_safe(legs: usize, input: &[u64], output: &mut [u8]) {
-    for i in (0..legs * 16).step_by(2) {
-        let limb_idx = (i / 2) / 8;
-        let byte_idx = (i / 2) % 8;
-        let byte_val = low_u8((input[limb_idx] >> (byte_idx * 8)) & 0xFF);
-        output[i] = byte_val & 0xF;
-        output[i + 1] = byte_val >> 4;
+/// Unpack u64 limbs into a row of `ncols` GF(16) nibbles (safe version).
+fn ef_unpack_m_vec_safe(input: &[u64], output: &mut [u8], ncols: usize) {
+    for (i, out) in output[..ncols].iter_mut().enumerate() {
+        *out = m_extract_element(input, i);
     }
 }
 
 /// Put matrix in row echelon form with leading ones, in constant time.
 ///
 /// `a` is an `nrows x ncols` matrix of GF(16) elements stored as bytes.
+///
+/// Like the MAYO reference implementation, the pivot search for column `c`
+/// only looks at rows `c + nrows - ncols ..= c + 32`. That finds every pivot
+/// with overwhelming probability for random inputs, as in the reference, but
+/// not for every full rank matrix: a pivot that only exists further down is
+/// missed, and the result is then not in echelon form. The reference accepts
+/// the same negligible risk; `ef_full` drops the window.
+///
+/// Works on the stack, so `a` must be at most `MAX_M x MAX_A_COLS`, the
+/// largest system any parameter set signs with.
 pub(crate) fn ef(a: &mut [u8], nrows: usize, ncols: usize) {
+    let mut work = [0u64; (MAX_M + 2) * MAX_A_COLS.div_ceil(16)];
+    ef_windowed::<true>(a, nrows, ncols, &mut work);
+}
+
+/// [`ef`] with the pivot search over every row, exact for any shape and rank.
+///
+/// Still constant time: the rows visited depend only on the dimensions.
+#[cfg(any(test, feature = "gf16-internals"))]
+pub(crate) fn ef_full(a: &mut [u8], nrows: usize, ncols: usize) {
+    let mut work = vec![0u64; (nrows + 2) * ncols.div_ceil(16)];
+    ef_windowed::<false>(a, nrows, ncols, &mut work);
+}
+
+/// The elimination behind [`ef`] and [`ef_full`]. `work` holds the packed
+/// matrix and two pivot rows, `(nrows + 2) * ncols.div_ceil(16)` limbs.
+fn ef_windowed<const WINDOW: bool>(a: &mut [u8], nrows: usize, ncols: usize, work: &mut [u64]) {
     let row_len = ncols.div_ceil(16);
+    let (packed_a, pivot_rows) = work[..(nrows + 2) * row_len].split_at_mut(row_len * nrows);
+    let (pivot_row_packed, pivot_row2) = pivot_rows.split_at_mut(row_len);
 
     // Pack the matrix A into nibble-sliced form
-    let mut packed_a = vec![0u64; row_len * nrows];
     for i in 0..nrows {
         ef_pack_m_vec_safe(
             &a[i * ncols..(i + 1) * ncols],
@@ -89,13 +131,14 @@ pub(crate) fn ef(a: &mut [u8], nrows: usize, ncols: usize) {
         );
     }
 
-    let mut pivot_row_packed = vec![0u64; row_len];
-    let mut pivot_row2 = vec![0u64; row_len];
-
     let mut pivot_row: usize = 0;
 
     for pivot_col in 0..ncols {
-        let pivot_row_lower_bound = pivot_col.saturating_add(nrows).saturating_sub(ncols);
+        let pivot_row_lower_bound = if WINDOW {
+            pivot_col.saturating_add(nrows).saturating_sub(ncols)
+        } else {
+            0
+        };
         let pivot_row_upper_bound = (nrows - 1).min(pivot_col);
 
         // Zero out pivot row buffers
@@ -106,7 +149,11 @@ pub(crate) fn ef(a: &mut [u8], nrows: usize, ncols: usize) {
         let mut pivot: u8 = 0;
         let mut pivot_is_zero: u64 = u64::MAX;
 
-        let search_upper = (nrows - 1).min(pivot_row_upper_bound + 32);
+        let search_upper = if WINDOW {
+            (nrows - 1).min(pivot_row_upper_bound + 32)
+        } else {
+            nrows - 1
+        };
         for row in pivot_row_lower_bound..=search_upper {
             let is_pivot_row = !ct_compare_64(row, pivot_row);
             let below_pivot_row = ct_64_is_greater_than(row, pivot_row);
@@ -115,21 +162,20 @@ pub(crate) fn ef(a: &mut [u8], nrows: usize, ncols: usize) {
                 pivot_row_packed[j] ^= (is_pivot_row | (below_pivot_row & pivot_is_zero))
                     & packed_a[row * row_len + j];
             }
-            pivot = m_extract_element(&pivot_row_packed, pivot_col);
+            pivot = m_extract_element(pivot_row_packed, pivot_col);
             pivot_is_zero = !ct_compare_64(usize::from(pivot), 0);
         }
 
         // Multiply pivot row by inverse of pivot
         let inverse = inverse_f(pivot);
-        vec_mul_add_u64(row_len, &pivot_row_packed, inverse, &mut pivot_row2);
+        vec_mul_add_u64(row_len, pivot_row_packed, inverse, pivot_row2);
 
         // Conditionally write pivot row to the correct row
         for row in pivot_row_lower_bound..=pivot_row_upper_bound {
             let do_copy = !ct_compare_64(row, pivot_row) & !pivot_is_zero;
-            let do_not_copy = !do_copy;
             for col in 0..row_len {
-                packed_a[row * row_len + col] = (do_not_copy & packed_a[row * row_len + col])
-                    .wrapping_add(do_copy & pivot_row2[col]);
+                packed_a[row * row_len + col] =
+                    ct_select_u64(do_copy, pivot_row2[col], packed_a[row * row_len + col]);
             }
         }
 
@@ -140,7 +186,7 @@ pub(crate) fn ef(a: &mut [u8], nrows: usize, ncols: usize) {
                 m_extract_element(&packed_a[row * row_len..(row + 1) * row_len], pivot_col);
             vec_mul_add_u64(
                 row_len,
-                &pivot_row2,
+                pivot_row2,
                 below_pivot.wrapping_mul(elt_to_elim),
                 &mut packed_a[row * row_len..(row + 1) * row_len],
             );
@@ -150,13 +196,179 @@ pub(crate) fn ef(a: &mut [u8], nrows: usize, ncols: usize) {
     }
 
     // Unpack the matrix
-    let mut temp = vec![0u8; ncols + 16]; // Extra space for unpacking
     for i in 0..nrows {
         ef_unpack_m_vec_safe(
-            row_len,
             &packed_a[i * row_len..(i + 1) * row_len],
-            &mut temp,
+            &mut a[i * ncols..(i + 1) * ncols],
+            ncols,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf16::mul_f;
+
+    /// SplitMix64 nibbles. A linear generator such as xorshift would cap the
+    /// rank at its 64 state bits, too few for the larger signing systems.
+    fn random_matrix(seed: u64, len: usize) -> Vec<u8> {
+        let mut s = seed;
+        (0..len)
+            .map(|_| {
+                s = s.wrapping_add(0x9E37_79B9_7F4A_7C15);
+                let mut z = s;
+                z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+                low_u8((z ^ (z >> 31)) >> 60)
+            })
+            .collect()
+    }
+
+    /// Textbook Gauss-Jordan elimination to the reduced row echelon form,
+    /// which is unique for a given row space.
+    fn reference_rref(a: &mut [u8], nrows: usize, ncols: usize) {
+        let mut pivot_row = 0;
+        for col in 0..ncols {
+            let Some(r) = (pivot_row..nrows).find(|&r| a[r * ncols + col] != 0) else {
+                continue;
+            };
+            for j in 0..ncols {
+                a.swap(pivot_row * ncols + j, r * ncols + j);
+            }
+            let inv = inverse_f(a[pivot_row * ncols + col]);
+            for j in 0..ncols {
+                a[pivot_row * ncols + j] = mul_f(inv, a[pivot_row * ncols + j]);
+            }
+            for r in (0..nrows).filter(|&r| r != pivot_row) {
+                let factor = a[r * ncols + col];
+                for j in 0..ncols {
+                    a[r * ncols + j] ^= mul_f(factor, a[pivot_row * ncols + j]);
+                }
+            }
+            pivot_row += 1;
+            if pivot_row == nrows {
+                break;
+            }
+        }
+    }
+
+    /// Each nonzero row starts with a 1 strictly right of the row above's,
+    /// and zero rows are at the bottom.
+    fn is_echelon_with_leading_ones(a: &[u8], nrows: usize, ncols: usize) -> bool {
+        let mut last_lead = None;
+        for row in a.chunks_exact(ncols).take(nrows) {
+            match row.iter().position(|&x| x != 0) {
+                Some(lead) => {
+                    if row[lead] != 1 || last_lead.is_some_and(|l| lead <= l) {
+                        return false;
+                    }
+                    last_lead = Some(lead);
+                }
+                None => last_lead = Some(ncols),
+            }
+        }
+        true
+    }
+
+    fn check_ef_full(nrows: usize, ncols: usize, a: Vec<u8>) {
+        let mut got = a.clone();
+        ef_full(&mut got, nrows, ncols);
+        assert!(
+            is_echelon_with_leading_ones(&got, nrows, ncols),
+            "{nrows}x{ncols}: {got:?}"
         );
-        a[i * ncols..(i + 1) * ncols].copy_from_slice(&temp[..ncols]);
+
+        let mut expected = a;
+        reference_rref(&mut expected, nrows, ncols);
+        reference_rref(&mut got, nrows, ncols);
+        assert_eq!(got, expected, "{nrows}x{ncols}: row space changed");
+    }
+
+    #[test]
+    fn ef_full_matches_reference_on_random_matrices() {
+        let shapes = [
+            (1, 1),
+            (3, 3),
+            (4, 9),
+            (9, 4),
+            (17, 33),
+            (40, 20),
+            (64, 129),
+        ];
+        for (seed, &(nrows, ncols)) in (1u64..).zip(shapes.iter().cycle().take(70)) {
+            check_ef_full(nrows, ncols, random_matrix(seed, nrows * ncols));
+        }
+    }
+
+    #[test]
+    fn ef_full_handles_rank_deficient_matrices() {
+        for seed in 1u64..20 {
+            // Zero the first columns and duplicate rows so the rank is low and
+            // pivots sit far from the diagonal.
+            let (nrows, ncols) = (48, 60);
+            let mut a = random_matrix(seed, nrows * ncols);
+            for (i, row) in a.chunks_exact_mut(ncols).enumerate() {
+                row[..40].fill(0);
+                if i % 3 != 0 {
+                    row.fill(0);
+                }
+            }
+            check_ef_full(nrows, ncols, a);
+        }
+        check_ef_full(5, 7, vec![0; 35]);
+    }
+
+    fn ef_matches_ef_full<P: crate::MayoParameter>() {
+        // The shape signing uses: m x (k*o + 1). These fixed random inputs
+        // all have their pivots inside the window.
+        let (nrows, ncols) = (P::M, P::A_COLS);
+        for seed in 1u64..10 {
+            let a = random_matrix(seed, nrows * ncols);
+            let mut windowed = a.clone();
+            ef(&mut windowed, nrows, ncols);
+            let mut full = a;
+            ef_full(&mut full, nrows, ncols);
+            assert_eq!(windowed, full, "{} seed {seed}", P::NAME);
+            assert!(
+                full.chunks_exact(ncols)
+                    .all(|row| row.iter().any(|&x| x != 0)),
+                "{} seed {seed}: not full rank",
+                P::NAME
+            );
+        }
+    }
+
+    #[test]
+    fn ef_matches_ef_full_on_random_signing_shapes() {
+        ef_matches_ef_full::<crate::Mayo1>();
+        ef_matches_ef_full::<crate::Mayo2>();
+        ef_matches_ef_full::<crate::Mayo3>();
+        ef_matches_ef_full::<crate::Mayo5>();
+    }
+
+    #[test]
+    fn ef_window_misses_a_distant_pivot() {
+        // Full row rank, but column 0's only pivot is row 39, past the
+        // window's last row 32.
+        let (nrows, ncols) = (40, 41);
+        let mut a = vec![0u8; nrows * ncols];
+        for r in 0..nrows - 1 {
+            a[r * ncols + r + 1] = 1;
+        }
+        a[(nrows - 1) * ncols] = 1;
+
+        let mut full = a.clone();
+        ef_full(&mut full, nrows, ncols);
+        assert!(
+            full.chunks_exact(ncols)
+                .all(|row| row.iter().any(|&x| x != 0))
+        );
+
+        assert!(is_echelon_with_leading_ones(&full, nrows, ncols));
+
+        let mut windowed = a;
+        ef(&mut windowed, nrows, ncols);
+        assert!(!is_echelon_with_leading_ones(&windowed, nrows, ncols));
     }
 }
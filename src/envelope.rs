@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Self-describing detached signatures.
+//!
+//! A bare [`Signature`] says nothing about the parameter set it was made
+//! with. An envelope prefixes it with a small header so that a signature file
+//! can be recognized, and so that a Mayo1 signature handed to a Mayo3
+//! verifier is rejected by its header rather than by a failed verification:
+//!
+//! | Offset | Length | Field |
+//! |--------|--------|-------|
+//! | 0 | 4 | [`MAGIC`], `"MAYO"` |
+//! | 4 | 1 | parameter set id, see [`param_id`] |
+//! | 5 | 1 | `SALT_BYTES` of the parameter set |
+//! | 6 | `SIG_BYTES` | the signature |
+//!
+//! The message is not included; the envelope is detached.
+
+use core::any::TypeId;
+
+use crate::error::{Error, Result};
+use crate::mayo_signature::Signature;
+use crate::params::{Mayo1, Mayo2, Mayo3, Mayo5, MayoParameter};
+use crate::signing_key::SigningKey;
+use crate::verifying_key::VerifyingKey;
+
+/// The four bytes every envelope starts with.
+pub const MAGIC: [u8; 4] = *b"MAYO";
+
+/// Length of the header preceding the signature.
+pub const HEADER_LEN: usize = MAGIC.len() + 2;
+
+/// The one-byte id of parameter set `P` in an envelope header.
+///
+/// | Type | Id |
+/// |------|----|
+/// | [`Mayo1`] | 1 |
+/// | [`Mayo2`] | 2 |
+/// | [`Mayo3`] | 3 |
+/// | [`Mayo5`] | 5 |
+///
+/// Returns `None` for a parameter set defined outside this crate.
+///
+/// The match is on the type itself, so a custom parameter set that reuses a
+/// built-in `NAME` still gets no id.
+pub fn param_id<P: MayoParameter>() -> Option<u8> {
+    let id = TypeId::of::<P>();
+    if id == TypeId::of::<Mayo1>() {
+        Some(1)
+    } else if id == TypeId::of::<Mayo2>() {
+        Some(2)
+    } else if id == TypeId::of::<Mayo3>() {
+        Some(3)
+    } else if id == TypeId::of::<Mayo5>() {
+        Some(5)
+    } else {
+        None
+    }
+}
+
+/// Sign `msg` and wrap the signature in an envelope.
+///
+/// Returns [`Error::Signing`] if signing fails or `P` has no envelope id.
+pub fn seal<P: MayoParameter>(
+    signing_key: &SigningKey<P>,
+    rng: &mut impl rand::CryptoRng,
+    msg: &[u8],
+) -> Result<Vec<u8>> {
+    let id = param_id::<P>().ok_or(Error::Signing)?;
+    let salt_len = u8::try_from(P::SALT_BYTES).map_err(|_| Error::Signing)?;
+    let signature = signing_key.sign_with_rng(rng, msg)?;
+
+    let mut envelope = Vec::with_capacity(HEADER_LEN + P::SIG_BYTES);
+    envelope.extend_from_slice(&MAGIC);
+    envelope.push(id);
+    envelope.push(salt_len);
+    envelope.extend_from_slice(signature.as_ref());
+    Ok(envelope)
+}
+
+/// Check an envelope's header and verify its signature over `msg`.
+///
+/// The magic, parameter set id and salt length are checked before any
+/// verification work, and a mismatch in any of them returns
+/// [`Error::VerificationFailed`]. A signature of the wrong length returns
+/// [`Error::InvalidSignatureLength`].
+pub fn open<P: MayoParameter>(
+    verifying_key: &VerifyingKey<P>,
+    envelope: &[u8],
+    msg: &[u8],
+) -> Result<()> {
+    let (header, sig) = envelope
+        .split_at_checked(HEADER_LEN)
+        .ok_or(Error::VerificationFailed)?;
+    let id = param_id::<P>().ok_or(Error::VerificationFailed)?;
+    if header[..MAGIC.len()] != MAGIC
+        || header[MAGIC.len()] != id
+        || usize::from(header[MAGIC.len() + 1]) != P::SALT_BYTES
+    {
+        return Err(Error::VerificationFailed);
+    }
+
+    let signature = Signature::<P>::try_from(sig)?;
+    verifying_key.verify(msg, &signature)
+}
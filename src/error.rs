@@ -3,7 +3,12 @@
 //! Error types for the MAYO signature scheme.
 
 /// Errors that can occur during MAYO operations.
+///
+/// New variants may be added in minor releases; use the predicates such as
+/// [`is_verification_failure`](Self::is_verification_failure) rather than
+/// matching exhaustively.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum Error {
     /// Key generation failed.
     #[error("key generation failed")]
@@ -38,11 +43,113 @@ pub enum Error {
         /// Actual length.
         got: usize,
     },
+    /// Invalid caller-supplied signing entropy length.
+    #[error("invalid entropy length: expected {expected}, got {got}")]
+    InvalidEntropyLength {
+        /// Expected length.
+        expected: usize,
+        /// Actual length.
+        got: usize,
+    },
+    /// A signing context string longer than the one-byte length prefix allows.
+    #[error("invalid context length: at most {max}, got {got}")]
+    InvalidContextLength {
+        /// Longest accepted context.
+        max: usize,
+        /// Actual length.
+        got: usize,
+    },
+    /// A signing context that the associated-data modes reserve for their
+    /// own domain tags.
+    #[error("reserved context string")]
+    ReservedContext,
+    /// The seed is all `0x00` or all `0xFF`, which usually means it was never
+    /// filled with randomness.
+    #[error("weak seed: all bytes are identical 0x00 or 0xFF")]
+    WeakSeed,
+    /// A key of the right length whose contents are inconsistent.
+    #[error("malformed key")]
+    MalformedKey,
+    /// Encoded data is tagged with a different parameter set.
+    #[error("parameter set mismatch: expected id {expected}, got {got}")]
+    ParameterSetMismatch {
+        /// Id of the expected parameter set.
+        expected: u8,
+        /// Id found in the input.
+        got: u8,
+    },
+    /// A string form of a key or signature is not valid hex.
+    #[error("invalid hex encoding")]
+    InvalidHex,
+    /// Reading the message failed.
+    #[cfg(feature = "std")]
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// A line of a KAT response file could not be parsed.
+    #[cfg(feature = "kat")]
+    #[error("invalid KAT file at line {line}")]
+    InvalidKatFile {
+        /// 1-based line number.
+        line: usize,
+    },
+    /// A KAT vector did not reproduce.
+    #[cfg(feature = "kat")]
+    #[error("KAT vector {count}: {field} mismatch")]
+    KatMismatch {
+        /// The vector's `count`.
+        count: usize,
+        /// The field that differed.
+        field: &'static str,
+    },
+}
+
+/// Why a signature failed [`VerifyingKey::verify_verbose`](crate::VerifyingKey::verify_verbose).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum VerifyDetail {
+    /// The padding nibble of the encoded solution is not zero.
+    #[error("non-canonical signature encoding")]
+    NonCanonical,
+    /// The public map evaluated at the signature differs from the target
+    /// derived from the message and salt.
+    #[error("{mismatched} of {total} target entries differ")]
+    Mismatch {
+        /// Number of differing entries.
+        mismatched: usize,
+        /// Number of entries compared, `m`.
+        total: usize,
+    },
+    /// The key or signature could not be processed at all.
+    #[error("malformed input")]
+    Malformed,
+}
+
+impl Error {
+    /// Whether a well-formed signature failed to verify.
+    pub fn is_verification_failure(&self) -> bool {
+        matches!(self, Self::VerificationFailed)
+    }
+
+    /// Whether an input key, signature, seed, entropy or context had the wrong
+    /// length.
+    pub fn is_length_error(&self) -> bool {
+        matches!(
+            self,
+            Self::InvalidKeyLength { .. }
+                | Self::InvalidSignatureLength { .. }
+                | Self::InvalidSeedLength { .. }
+                | Self::InvalidEntropyLength { .. }
+                | Self::InvalidContextLength { .. }
+        )
+    }
 }
 
+#[cfg(feature = "signature-traits")]
 impl From<Error> for signature::Error {
+    /// Keeps `e` as the source, so it can be recovered with
+    /// `source().and_then(|s| s.downcast_ref::<Error>())`.
     fn from(e: Error) -> Self {
-        signature::Error::from_source(e.to_string())
+        signature::Error::from_source(e)
     }
 }
 
@@ -1,8 +1,25 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 //! Scalar GF(16) arithmetic over GF(2)\[x\]/(x^4 + x + 1).
+//!
+//! With the `gf16-internals` feature this module is public and exposes
+//! [`mul`], [`add`], [`inverse`], a dense [`Matrix`] type and a constant-time
+//! [`row_echelon_form`]. These exist for test vectors and tooling, not for
+//! building protocols, and are **not** covered by SemVer: they may change or
+//! disappear in any release.
+//!
+//! Elements are one per byte; only the low nibble of each input is used.
 
 /// Multiply two GF(16) elements: a * b mod (x^4 + x + 1).
+///
+/// Constant time: there are no branches and no memory accesses indexed by
+/// the operands. A 16 x 16 product table would be smaller code but is
+/// indexed by secret elements, which leaks through the cache. Each
+/// `(a & 2^i) * b` is an integer multiply by `0` or `2^i`, a single
+/// fixed-latency instruction on the targets we support. The multiply-free
+/// alternative that selects `b << i` with the mask `-(a >> i & 1)` (see the
+/// `gf16/mul` benchmark) gives the same products but is not faster, so this
+/// form is kept.
 #[inline]
 pub(crate) fn mul_f(a: u8, b: u8) -> u8 {
     // Carryless multiply
@@ -90,3 +107,288 @@ pub(crate) fn mat_mul(
         }
     }
 }
+
+/// Multiply two GF(16) elements.
+#[cfg(feature = "gf16-internals")]
+#[inline]
+pub fn mul(a: u8, b: u8) -> u8 {
+    mul_f(a & 0x0f, b & 0x0f)
+}
+
+/// Add two GF(16) elements.
+#[cfg(feature = "gf16-internals")]
+#[inline]
+pub fn add(a: u8, b: u8) -> u8 {
+    add_f(a, b) & 0x0f
+}
+
+/// Multiplicative inverse of a GF(16) element. The inverse of 0 is 0.
+#[cfg(feature = "gf16-internals")]
+#[inline]
+pub fn inverse(a: u8) -> u8 {
+    inverse_f(a & 0x0f)
+}
+
+/// Bring the `rows x cols` row-major matrix to row echelon form, in place.
+///
+/// Each nonzero row is scaled so that its leading entry is 1, and every
+/// leading 1 is strictly right of the one in the row above; zero rows end up
+/// at the bottom. Entries above a leading 1 are not cleared, so this is not
+/// the reduced form. Pivots are chosen by adding the following rows into the
+/// pivot row until its entry is nonzero, rather than by swapping, so the
+/// result is not necessarily the one textbook elimination would give, but it
+/// spans the same row space.
+///
+/// The elimination is constant time: the rows visited and the operations
+/// performed depend only on `rows` and `cols`, never on the elements. Unlike
+/// the routine signing uses, every row is searched for each pivot, so the
+/// result is exact for any shape and rank.
+///
+/// Returns `false`, leaving `matrix` untouched, if `matrix.len() != rows *
+/// cols` or any element is `>= 16`.
+#[cfg(feature = "gf16-internals")]
+pub fn row_echelon_form(matrix: &mut [u8], rows: usize, cols: usize) -> bool {
+    if rows.checked_mul(cols) != Some(matrix.len()) || matrix.iter().any(|&x| x > 0x0f) {
+        return false;
+    }
+    if !matrix.is_empty() {
+        crate::echelon::ef_full(matrix, rows, cols);
+    }
+    true
+}
+
+/// A dense row-major matrix over GF(16), one element per byte.
+#[cfg(feature = "gf16-internals")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<u8>,
+}
+
+#[cfg(feature = "gf16-internals")]
+impl Matrix {
+    /// The all-zero `rows x cols` matrix.
+    pub fn zero(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![0u8; rows * cols],
+        }
+    }
+
+    /// Build a matrix from row-major elements.
+    ///
+    /// Returns `None` if `data.len() != rows * cols` or any element is not
+    /// a valid GF(16) element (`>= 16`).
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<u8>) -> Option<Self> {
+        if data.len() != rows.checked_mul(cols)? || data.iter().any(|&x| x > 0x0f) {
+            return None;
+        }
+        Some(Self { rows, cols, data })
+    }
+
+    /// Number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Number of columns.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// The row-major elements.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The element at `(row, col)`, or `None` if out of bounds.
+    pub fn get(&self, row: usize, col: usize) -> Option<u8> {
+        if row >= self.rows || col >= self.cols {
+            return None;
+        }
+        Some(self.data[row * self.cols + col])
+    }
+
+    /// Set the element at `(row, col)` to the low nibble of `value`.
+    ///
+    /// Returns `false` (and does nothing) if out of bounds.
+    pub fn set(&mut self, row: usize, col: usize, value: u8) -> bool {
+        if row >= self.rows || col >= self.cols {
+            return false;
+        }
+        self.data[row * self.cols + col] = value & 0x0f;
+        true
+    }
+
+    /// Matrix sum `self + rhs`, or `None` if the shapes differ.
+    pub fn add(&self, rhs: &Self) -> Option<Self> {
+        if self.rows != rhs.rows || self.cols != rhs.cols {
+            return None;
+        }
+        let data = self
+            .data
+            .iter()
+            .zip(&rhs.data)
+            .map(|(&a, &b)| add_f(a, b))
+            .collect();
+        Some(Self {
+            rows: self.rows,
+            cols: self.cols,
+            data,
+        })
+    }
+
+    /// Matrix product `self * rhs`, or `None` if `self.cols() != rhs.rows()`.
+    pub fn mul(&self, rhs: &Self) -> Option<Self> {
+        if self.cols != rhs.rows {
+            return None;
+        }
+        let mut out = Self::zero(self.rows, rhs.cols);
+        if self.cols > 0 {
+            mat_mul(
+                &self.data,
+                &rhs.data,
+                &mut out.data,
+                self.cols,
+                self.rows,
+                rhs.cols,
+            );
+        }
+        Some(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitsliced::vec_mul_add_u64;
+
+    /// Schoolbook polynomial multiplication mod x^4 + x + 1, bit by bit.
+    fn reference_mul(a: u8, b: u8) -> u8 {
+        let mut p = 0u8;
+        let mut a = a & 0xf;
+        for i in 0..4 {
+            if (b >> i) & 1 == 1 {
+                p ^= a;
+            }
+            a <<= 1;
+            if a & 0x10 != 0 {
+                a ^= 0x13;
+            }
+        }
+        p
+    }
+
+    fn samples() -> impl Iterator<Item = u64> {
+        let mut s = 0x2545_F491_4F6C_DD1Du64;
+        core::iter::repeat_with(move || {
+            s ^= s << 13;
+            s ^= s >> 7;
+            s ^= s << 17;
+            s
+        })
+        .take(256)
+        .chain([0, u64::MAX, 0x0123_4567_89AB_CDEF])
+    }
+
+    #[test]
+    fn mul_f_matches_reference() {
+        for a in 0..16u8 {
+            for b in 0..16u8 {
+                assert_eq!(mul_f(a, b), reference_mul(a, b), "{a} * {b}");
+            }
+        }
+    }
+
+    /// Carryless multiply with masks instead of integer multiplies.
+    fn masked_mul(a: u8, b: u8) -> u8 {
+        let mut p = 0u8;
+        for i in 0..4 {
+            p ^= (b << i) & 0u8.wrapping_sub((a >> i) & 1);
+        }
+        let top_p = p & 0xf0;
+        (p ^ (top_p >> 4) ^ (top_p >> 3)) & 0x0f
+    }
+
+    #[test]
+    fn mul_f_matches_masked_mul() {
+        for a in 0..16u8 {
+            for b in 0..16u8 {
+                assert_eq!(mul_f(a, b), masked_mul(a, b), "{a} * {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn field_axioms() {
+        assert_eq!(inverse_f(0), 0);
+        for a in 1..16u8 {
+            assert_eq!(mul_f(a, inverse_f(a)), 1, "a = {a}");
+        }
+        for a in 0..16u8 {
+            for b in 0..16u8 {
+                assert_eq!(sub_f(a, b), add_f(a, b));
+                for c in 0..16u8 {
+                    assert_eq!(
+                        mul_f(a, add_f(b, c)),
+                        add_f(mul_f(a, b), mul_f(a, c)),
+                        "{a} * ({b} + {c})"
+                    );
+                    assert_eq!(mul_f(mul_f(a, b), c), mul_f(a, mul_f(b, c)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mul_fx8_matches_mul_f() {
+        for a in 0..16u8 {
+            for b in samples() {
+                let b = b & 0x0f0f_0f0f_0f0f_0f0f;
+                let got = mul_fx8(a, b).to_le_bytes();
+                for (i, byte) in b.to_le_bytes().into_iter().enumerate() {
+                    assert_eq!(got[i], mul_f(a, byte), "a = {a}, lane {i}");
+                }
+            }
+        }
+    }
+
+    /// The bitsliced multiply-add over 16 nibbles per limb agrees with
+    /// `mul_fx8` applied to the even and odd nibbles separately.
+    #[test]
+    fn vec_mul_add_u64_matches_mul_fx8() {
+        const LOW: u64 = 0x0f0f_0f0f_0f0f_0f0f;
+        for a in 0..16u8 {
+            for (src, acc) in samples().zip(samples().skip(1)) {
+                let mut got = [acc];
+                vec_mul_add_u64(1, &[src], a, &mut got);
+                let product = mul_fx8(a, src & LOW) | (mul_fx8(a, (src >> 4) & LOW) << 4);
+                assert_eq!(got[0], acc ^ product, "a = {a}, src = {src:#x}");
+            }
+        }
+    }
+
+    #[test]
+    fn mul_table_packs_powers_of_two() {
+        for b in 0..16u8 {
+            let table = mul_table(b).to_le_bytes();
+            for (i, entry) in table.into_iter().enumerate() {
+                assert_eq!(entry & 0xf, mul_f(b, 1 << i), "b = {b}, lane {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn lincomb_is_strided_dot_product() {
+        let a = [3u8, 7, 0, 15, 9];
+        let b: Vec<u8> = (0..15u8).map(|i| i % 16).collect();
+        let expected = a
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, &x)| add_f(acc, mul_f(x, b[i * 3])));
+        assert_eq!(lincomb(&a, &b, a.len(), 3), expected);
+        assert_eq!(lincomb(&a, &b, 0, 3), 0);
+    }
+}
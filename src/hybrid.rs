@@ -0,0 +1,234 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Hybrid MAYO + Ed25519 signatures.
+
+use crate::error::{Error, Result};
+use crate::keypair::KeyPair;
+use crate::mayo_signature::Signature;
+use crate::params::MayoParameter;
+use crate::signing_key::SigningKey;
+use crate::verifying_key::VerifyingKey;
+use rand::CryptoRng;
+use signature::{Signer, Verifier};
+use zeroize::ZeroizeOnDrop;
+
+const LENGTH_PREFIX_BYTES: usize = 4;
+const ED25519_SIG_BYTES: usize = ed25519_dalek::SIGNATURE_LENGTH;
+
+/// A hybrid signing key: a MAYO [`SigningKey`] paired with an Ed25519 key.
+#[derive(Clone)]
+pub struct HybridSigningKey<P: MayoParameter> {
+    mayo: SigningKey<P>,
+    ed25519: ed25519_dalek::SigningKey,
+}
+
+/// A hybrid verifying key: a MAYO [`VerifyingKey`] paired with an Ed25519 key.
+#[derive(Clone)]
+pub struct HybridVerifyingKey<P: MayoParameter> {
+    mayo: VerifyingKey<P>,
+    ed25519: ed25519_dalek::VerifyingKey,
+}
+
+/// A hybrid signature: a MAYO [`Signature`] and an Ed25519 signature over the
+/// same message. It is valid only if both components verify.
+///
+/// The wire format produced by [`to_bytes`](Self::to_bytes) is:
+///
+/// | Offset | Length | Field |
+/// |--------|--------|-------|
+/// | 0 | 4 | MAYO signature length, big-endian `u32` |
+/// | 4 | `P::SIG_BYTES` | MAYO signature |
+/// | 4 + `P::SIG_BYTES` | 64 | Ed25519 signature |
+///
+/// When decoding, the length prefix must equal `P::SIG_BYTES`.
+#[derive(Clone)]
+pub struct HybridSignature<P: MayoParameter> {
+    mayo: Signature<P>,
+    ed25519: ed25519_dalek::Signature,
+}
+
+impl<P: MayoParameter> HybridSigningKey<P> {
+    /// Combine an existing MAYO signing key with an Ed25519 signing key.
+    pub fn new(mayo: SigningKey<P>, ed25519: ed25519_dalek::SigningKey) -> Self {
+        Self { mayo, ed25519 }
+    }
+
+    /// Generate fresh MAYO and Ed25519 keys.
+    pub fn generate(rng: &mut impl CryptoRng) -> Result<Self> {
+        let mayo = KeyPair::<P>::generate(rng)?.into_signing_key();
+        let ed25519 = ed25519_dalek::SigningKey::generate(rng);
+        Ok(Self { mayo, ed25519 })
+    }
+
+    /// The MAYO component.
+    pub fn mayo(&self) -> &SigningKey<P> {
+        &self.mayo
+    }
+
+    /// The Ed25519 component.
+    pub fn ed25519(&self) -> &ed25519_dalek::SigningKey {
+        &self.ed25519
+    }
+
+    /// Derive the matching hybrid verifying key.
+    pub fn verifying_key(&self) -> HybridVerifyingKey<P> {
+        HybridVerifyingKey {
+            mayo: VerifyingKey::from(&self.mayo),
+            ed25519: self.ed25519.verifying_key(),
+        }
+    }
+}
+
+impl<P: MayoParameter> ZeroizeOnDrop for HybridSigningKey<P> {}
+
+impl<P: MayoParameter> core::fmt::Debug for HybridSigningKey<P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HybridSigningKey")
+            .field("variant", &P::NAME)
+            .field("mayo", &"**FILTERED**")
+            .field("ed25519", &"**FILTERED**")
+            .finish()
+    }
+}
+
+impl<P: MayoParameter> Signer<HybridSignature<P>> for HybridSigningKey<P> {
+    fn try_sign(&self, msg: &[u8]) -> core::result::Result<HybridSignature<P>, signature::Error> {
+        Ok(HybridSignature {
+            mayo: self.mayo.try_sign(msg)?,
+            ed25519: self.ed25519.try_sign(msg)?,
+        })
+    }
+}
+
+impl<P: MayoParameter> HybridVerifyingKey<P> {
+    /// Combine an existing MAYO verifying key with an Ed25519 verifying key.
+    pub fn new(mayo: VerifyingKey<P>, ed25519: ed25519_dalek::VerifyingKey) -> Self {
+        Self { mayo, ed25519 }
+    }
+
+    /// The MAYO component.
+    pub fn mayo(&self) -> &VerifyingKey<P> {
+        &self.mayo
+    }
+
+    /// The Ed25519 component.
+    pub fn ed25519(&self) -> &ed25519_dalek::VerifyingKey {
+        &self.ed25519
+    }
+}
+
+impl<P: MayoParameter> PartialEq for HybridVerifyingKey<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.mayo == other.mayo && self.ed25519 == other.ed25519
+    }
+}
+
+impl<P: MayoParameter> Eq for HybridVerifyingKey<P> {}
+
+impl<P: MayoParameter> core::fmt::Debug for HybridVerifyingKey<P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HybridVerifyingKey")
+            .field("variant", &P::NAME)
+            .field("mayo", &self.mayo)
+            .field("ed25519", &hex::encode(self.ed25519.as_bytes()))
+            .finish()
+    }
+}
+
+impl<P: MayoParameter> Verifier<HybridSignature<P>> for HybridVerifyingKey<P> {
+    fn verify(
+        &self,
+        msg: &[u8],
+        signature: &HybridSignature<P>,
+    ) -> core::result::Result<(), signature::Error> {
+        // Check both halves before deciding so the outcome does not reveal
+        // which component was rejected.
+        let mayo_ok = self.mayo.verify(msg, &signature.mayo).is_ok();
+        let ed25519_ok = self.ed25519.verify_strict(msg, &signature.ed25519).is_ok();
+        if mayo_ok & ed25519_ok {
+            Ok(())
+        } else {
+            Err(Error::VerificationFailed.into())
+        }
+    }
+}
+
+impl<P: MayoParameter> HybridSignature<P> {
+    /// Total encoded length in bytes.
+    pub const BYTES: usize = LENGTH_PREFIX_BYTES + P::SIG_BYTES + ED25519_SIG_BYTES;
+
+    /// The MAYO component.
+    pub fn mayo(&self) -> &Signature<P> {
+        &self.mayo
+    }
+
+    /// The Ed25519 component.
+    pub fn ed25519(&self) -> &ed25519_dalek::Signature {
+        &self.ed25519
+    }
+
+    /// Encode in the length-prefixed wire format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mayo_len =
+            u32::try_from(P::SIG_BYTES).expect("MAYO signature sizes fit in a u32 length prefix");
+        let mut out = Vec::with_capacity(Self::BYTES);
+        out.extend_from_slice(&mayo_len.to_be_bytes());
+        out.extend_from_slice(self.mayo.as_ref());
+        out.extend_from_slice(&self.ed25519.to_bytes());
+        out
+    }
+}
+
+impl<P: MayoParameter> PartialEq for HybridSignature<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.mayo == other.mayo && self.ed25519 == other.ed25519
+    }
+}
+
+impl<P: MayoParameter> Eq for HybridSignature<P> {}
+
+impl<P: MayoParameter> core::fmt::Debug for HybridSignature<P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HybridSignature")
+            .field("variant", &P::NAME)
+            .field("mayo", &self.mayo)
+            .field("ed25519", &hex::encode(self.ed25519.to_bytes()))
+            .finish()
+    }
+}
+
+impl<P: MayoParameter> TryFrom<&[u8]> for HybridSignature<P> {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        let invalid = || Error::InvalidSignatureLength {
+            expected: Self::BYTES,
+            got: bytes.len(),
+        };
+        if bytes.len() != Self::BYTES {
+            return Err(invalid());
+        }
+        let (prefix, rest) = bytes.split_at(LENGTH_PREFIX_BYTES);
+        let mut len = [0u8; LENGTH_PREFIX_BYTES];
+        len.copy_from_slice(prefix);
+        if usize::try_from(u32::from_be_bytes(len)).ok() != Some(P::SIG_BYTES) {
+            return Err(invalid());
+        }
+        let (mayo, ed25519) = rest.split_at(P::SIG_BYTES);
+        let ed25519 = ed25519_dalek::Signature::from_slice(ed25519).map_err(|_| invalid())?;
+        Ok(Self {
+            mayo: Signature::try_from(mayo)?,
+            ed25519,
+        })
+    }
+}
+
+impl<P: MayoParameter> From<HybridSignature<P>> for Vec<u8> {
+    fn from(sig: HybridSignature<P>) -> Vec<u8> {
+        sig.to_bytes()
+    }
+}
+
+impl<P: MayoParameter> signature::SignatureEncoding for HybridSignature<P> {
+    type Repr = Vec<u8>;
+}
@@ -0,0 +1,287 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Intermediate values for cross-implementation testing.
+//!
+//! Available with the `internals` feature. These functions expose values that
+//! the signing and verification code computes internally, so that test
+//! harnesses can byte-compare them against the MAYO reference implementation.
+//! [`SignStages`] runs the stages of a signing attempt one at a time, for
+//! benchmarking, and [`compute_target`] is a building block for experimental
+//! protocols built on MAYO. [`sign_with_digester`] and
+//! [`verify_with_digester`] swap the message digest for any [`Digester`].
+//! They are **not** covered by SemVer.
+//!
+//! # Bitsliced layout
+//!
+//! The public matrices are stored as sequences of m-vectors. An m-vector holds
+//! `m` GF(16) elements in `M_VEC_LIMBS` little-endian `u64` limbs: element `i`
+//! is the nibble at bits `4 * (i % 16)` of limb `i / 16`. Nibbles above `m` in
+//! the last limb are zero.
+//!
+//! P1 is the upper triangle of a `v x v` matrix of m-vectors, stored row by row
+//! (`v * (v + 1) / 2` entries). P2 is a full `v x o` matrix of m-vectors,
+//! stored row-major. Here `v = n - o`.
+//!
+//! | Type | v | o | `M_VEC_LIMBS` | P1 limbs | P2 limbs |
+//! |------|---|---|---------------|----------|----------|
+//! | [`Mayo1`](crate::Mayo1) | 78 | 8 | 5 | 15,405 | 3,120 |
+//! | [`Mayo2`](crate::Mayo2) | 80 | 16 | 4 | 12,960 | 5,120 |
+//! | [`Mayo3`](crate::Mayo3) | 108 | 10 | 7 | 41,202 | 7,560 |
+//! | [`Mayo5`](crate::Mayo5) | 142 | 12 | 9 | 91,377 | 15,336 |
+
+use crate::codec::decode;
+use crate::digester::XofDigester;
+use crate::echelon::ef;
+use crate::error::{Error, Result};
+use crate::keygen::expand_p1_p2;
+use crate::matrix_ops::{compute_m_and_vpv, compute_rhs};
+use crate::mayo_signature::Signature;
+use crate::params::MayoParameter;
+use crate::sample::{SampleSolutionArgs, sample_solution, sample_solution_detailed};
+use crate::sign::{
+    ExpandedSecretKey, SignScratch, compute_a, expand_sk, mayo_sign_signature_with_expanded_sk,
+};
+use crate::signing_key::SigningKey;
+use crate::verify;
+use crate::verifying_key::VerifyingKey;
+use crate::xof::{Shake256Xof, Xof};
+use core::marker::PhantomData;
+use zeroize::Zeroizing;
+
+pub use crate::digester::Digester;
+pub use crate::sample::SampleOutcome;
+
+/// Expand P1 and P2 from a public seed with AES-128-CTR.
+///
+/// Returns `(P1, P2)` with `P::P1_LIMBS` and `P::P2_LIMBS` limbs respectively,
+/// in the layout described in the [module docs](self), or
+/// [`Error::InvalidKeyLength`] if `seed_pk` is not `P::PK_SEED_BYTES` long.
+pub fn expand_public_matrices<P: MayoParameter>(seed_pk: &[u8]) -> Result<(Vec<u64>, Vec<u64>)> {
+    if seed_pk.len() != P::PK_SEED_BYTES {
+        return Err(Error::InvalidKeyLength {
+            expected: P::PK_SEED_BYTES,
+            got: seed_pk.len(),
+        });
+    }
+    let mut p1 = expand_p1_p2::<P>(seed_pk)?;
+    let p2 = p1.split_off(P::P1_LIMBS);
+    Ok((p1, p2))
+}
+
+/// The target vector `t` that a signature with `salt` must map to for `msg`.
+///
+/// This is `t = SHAKE256(SHAKE256(msg) || salt)`, decoded into `P::M` GF(16)
+/// elements, one per byte, exactly as verification recomputes it. It is meant
+/// for experimental constructions such as threshold or aggregate schemes that
+/// need the target directly; ordinary signing and verification never need to
+/// call it.
+///
+/// Returns [`Error::InvalidSignatureLength`] (with lengths counted in salt
+/// bytes) if `salt` is not `P::SALT_BYTES` long.
+pub fn compute_target<P: MayoParameter>(msg: &[u8], salt: &[u8]) -> Result<Vec<u8>> {
+    if salt.len() != P::SALT_BYTES {
+        return Err(Error::InvalidSignatureLength {
+            expected: P::SALT_BYTES,
+            got: salt.len(),
+        });
+    }
+    let mut tmp = vec![0u8; P::DIGEST_BYTES + P::SALT_BYTES];
+    let mut tenc = vec![0u8; P::M_BYTES];
+    let mut t = vec![0u8; P::M];
+    verify::compute_target::<P, Shake256Xof, XofDigester<Shake256Xof>>(
+        msg, salt, &mut tmp, &mut tenc, &mut t,
+    );
+    Ok(t)
+}
+
+/// Sign `msg` with its digest computed by `D` instead of SHAKE256.
+///
+/// Everything after the digest, including the check that the signature
+/// verifies before it is returned, is as in
+/// [`SigningKey::sign_with_rng`]. The signature only verifies with
+/// [`verify_with_digester`] and the same `D`.
+pub fn sign_with_digester<P: MayoParameter, D: Digester>(
+    signing_key: &SigningKey<P>,
+    rng: &mut impl rand::CryptoRng,
+    msg: &[u8],
+) -> Result<Signature<P>> {
+    let esk = expand_sk::<P, Shake256Xof>(signing_key.as_ref())?;
+    let mut sig = vec![0u8; P::SIG_BYTES];
+    mayo_sign_signature_with_expanded_sk::<P, Shake256Xof, D>(
+        &mut sig,
+        msg,
+        signing_key.as_ref(),
+        &esk,
+        |r| rng.fill_bytes(r),
+        &mut SignScratch::new(),
+    )?;
+    Signature::try_from(sig)
+}
+
+/// Verify a signature from [`sign_with_digester`] with the same `D`.
+///
+/// Returns [`Error::VerificationFailed`] if it does not verify, as
+/// [`VerifyingKey::verify`] does.
+pub fn verify_with_digester<P: MayoParameter, D: Digester>(
+    verifying_key: &VerifyingKey<P>,
+    msg: &[u8],
+    signature: &Signature<P>,
+) -> Result<()> {
+    verify::mayo_verify_with_digester::<P, Shake256Xof, D>(
+        msg,
+        signature.as_ref(),
+        verifying_key.as_ref(),
+    )
+}
+
+/// The stages of one signing attempt, runnable one at a time.
+///
+/// Signing spends its time in a handful of steps: expanding the secret key,
+/// computing the `M_i` matrices and `v_i^t P1 v_j`, assembling the
+/// linearized system `A`, and sampling a solution, which is dominated by
+/// putting `A` in echelon form. This type holds the inputs and outputs of each
+/// step so a benchmark can time them in isolation.
+///
+/// The vinegar values, `r` and the target `t` are derived from the secret
+/// seed rather than from a message and salt, so the data is realistic but no
+/// signature is produced. Each method reruns its stage from the output of the
+/// previous one and may be called repeatedly; call them in order at least
+/// once first.
+pub struct SignStages<P: MayoParameter> {
+    signing_key: SigningKey<P>,
+    esk: ExpandedSecretKey,
+    t: Vec<u8>,
+    vdec: Zeroizing<Vec<u8>>,
+    r: Zeroizing<Vec<u8>>,
+    vl: Zeroizing<Vec<u64>>,
+    vpv: Zeroizing<Vec<u64>>,
+    pv: Zeroizing<Vec<u64>>,
+    y: Zeroizing<Vec<u8>>,
+    a_matrix: Zeroizing<Vec<u8>>,
+    a_work: Zeroizing<Vec<u8>>,
+    vl_work: Zeroizing<Vec<u64>>,
+    a_scratch: Zeroizing<Vec<u64>>,
+    x: Zeroizing<Vec<u8>>,
+    _marker: PhantomData<P>,
+}
+
+impl<P: MayoParameter> core::fmt::Debug for SignStages<P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SignStages")
+            .field("variant", &P::NAME)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P: MayoParameter> SignStages<P> {
+    /// Expand `signing_key` and derive the per-attempt inputs.
+    pub fn new(signing_key: &SigningKey<P>) -> Result<Self> {
+        let esk = expand_sk::<P, Shake256Xof>(signing_key.as_ref())?;
+        let seed_sk = signing_key.secret_seed();
+
+        let mut v_and_r = Zeroizing::new(vec![0u8; P::K * P::V_BYTES + P::R_BYTES]);
+        Shake256Xof::hash(&[seed_sk, &[0]], &mut v_and_r);
+        let mut vdec = Zeroizing::new(vec![0u8; P::K * P::V]);
+        for i in 0..P::K {
+            decode(&v_and_r[i * P::V_BYTES..], &mut vdec[i * P::V..], P::V);
+        }
+        let mut r = Zeroizing::new(vec![0u8; P::A_COLS]);
+        decode(&v_and_r[P::K * P::V_BYTES..], &mut r, P::K * P::O);
+
+        let mut tenc = vec![0u8; P::M_BYTES];
+        Shake256Xof::hash(&[seed_sk, &[1]], &mut tenc);
+        let mut t = vec![0u8; P::M];
+        decode(&tenc, &mut t, P::M);
+
+        let a_len = P::M.div_ceil(8) * 8 * P::A_COLS;
+        let a_width = (P::O * P::K).div_ceil(16) * 16;
+        Ok(Self {
+            signing_key: signing_key.clone(),
+            esk,
+            t,
+            vdec,
+            r,
+            vl: Zeroizing::new(vec![0u64; P::K * P::O * P::M_VEC_LIMBS]),
+            vpv: Zeroizing::new(vec![0u64; P::K * P::K * P::M_VEC_LIMBS]),
+            pv: Zeroizing::new(vec![0u64; P::V * P::K * P::M_VEC_LIMBS]),
+            y: Zeroizing::new(vec![0u8; P::M]),
+            a_matrix: Zeroizing::new(vec![0u8; a_len]),
+            a_work: Zeroizing::new(vec![0u8; a_len]),
+            vl_work: Zeroizing::new(vec![0u64; P::K * P::O * P::M_VEC_LIMBS]),
+            a_scratch: Zeroizing::new(vec![0u64; a_width * P::M.div_ceil(8)]),
+            x: Zeroizing::new(vec![0u8; P::A_COLS]),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Expand the secret key: P1, `L = (P1 + P1^t) O + P2` and `O`.
+    ///
+    /// [`new`](Self::new) already does this once; calling it again redoes
+    /// the expansion so it can be timed on its own.
+    pub fn expand_secret_key(&mut self) -> Result<()> {
+        self.esk = expand_sk::<P, Shake256Xof>(self.signing_key.as_ref())?;
+        Ok(())
+    }
+
+    /// Compute `V L` (the `M_i` matrices), `V P1 V^t`, and the right-hand
+    /// side `y = t - vPv`.
+    pub fn compute_m_and_vpv(&mut self) {
+        let (p1, l) = self.esk.p1_l.split_at(P::P1_LIMBS);
+        self.vl.fill(0);
+        self.vpv.fill(0);
+        compute_m_and_vpv::<P>(&self.vdec, l, p1, &mut self.vl, &mut self.vpv, &mut self.pv);
+        self.y.fill(0);
+        compute_rhs::<P>(&mut self.vpv, &self.t, &mut self.y);
+    }
+
+    /// Assemble the linearized `m x (k o + 1)` system `A` from the `M_i`.
+    pub fn compute_a(&mut self) {
+        self.vl_work.copy_from_slice(&self.vl);
+        self.a_matrix.fill(0);
+        compute_a::<P>(&mut self.vl_work, &mut self.a_scratch, &mut self.a_matrix);
+        for i in 0..P::M {
+            self.a_matrix[(1 + i) * P::A_COLS - 1] = 0;
+        }
+    }
+
+    /// Put a copy of `A` in row echelon form.
+    pub fn echelon_form(&mut self) {
+        self.a_work.copy_from_slice(&self.a_matrix);
+        ef(&mut self.a_work, P::M, P::A_COLS);
+    }
+
+    /// Sample a solution of `A x = y` from a copy of `A`, including its
+    /// echelon form and back-substitution.
+    ///
+    /// Returns whether the system had full rank; signing would retry with
+    /// the next counter otherwise.
+    pub fn sample_solution(&mut self) -> bool {
+        self.a_work.copy_from_slice(&self.a_matrix);
+        self.x.copy_from_slice(&self.r);
+        sample_solution(SampleSolutionArgs {
+            a: &mut self.a_work,
+            y: &self.y,
+            x: &mut self.x,
+            k: P::K,
+            o: P::O,
+            m: P::M,
+            a_cols: P::A_COLS,
+        })
+    }
+
+    /// Like [`sample_solution`](Self::sample_solution), but a failure
+    /// reports the rank `A` had instead of just `false`.
+    pub fn sample_solution_detailed(&mut self) -> SampleOutcome {
+        self.a_work.copy_from_slice(&self.a_matrix);
+        self.x.copy_from_slice(&self.r);
+        sample_solution_detailed(SampleSolutionArgs {
+            a: &mut self.a_work,
+            y: &self.y,
+            x: &mut self.x,
+            k: P::K,
+            o: P::O,
+            m: P::M,
+            a_cols: P::A_COLS,
+        })
+    }
+}
@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! JSON Web Key (JWK) import/export for MAYO verifying keys.
+//!
+//! Keys use the `AKP` (algorithm key pair) key type with the public key in
+//! the base64url-encoded `pub` member, following the layout used for other
+//! post-quantum signature JWKs. MAYO has no registered JOSE algorithm name, so
+//! `alg` is the parameter set name with a hyphen, e.g. `"MAYO-1"`.
+
+use crate::params::MayoParameter;
+use crate::verifying_key::VerifyingKey;
+use base64ct::{Base64UrlUnpadded, Encoding};
+use serde::de::Error as _;
+
+const KTY_AKP: &str = "AKP";
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Jwk {
+    kty: String,
+    #[serde(default)]
+    alg: Option<String>,
+    #[serde(rename = "pub")]
+    public: String,
+}
+
+/// The JWK `alg` value for parameter set `P`.
+fn algorithm<P: MayoParameter>() -> String {
+    P::NAME.replace('_', "-")
+}
+
+impl<P: MayoParameter> VerifyingKey<P> {
+    /// Encode this key as a JWK JSON object.
+    pub fn to_jwk(&self) -> String {
+        let jwk = Jwk {
+            kty: KTY_AKP.to_string(),
+            alg: Some(algorithm::<P>()),
+            public: Base64UrlUnpadded::encode_string(&self.bytes),
+        };
+        serde_json::to_string(&jwk).expect("JWK serialization is infallible")
+    }
+
+    /// Parse a JWK JSON object produced by [`to_jwk`](Self::to_jwk).
+    ///
+    /// `kty` must be `"AKP"` and `alg` must be present and name this
+    /// parameter set; the decoded `pub` member must have the compact public
+    /// key length.
+    pub fn from_jwk(jwk: &str) -> serde_json::Result<Self> {
+        let jwk: Jwk = serde_json::from_str(jwk)?;
+        if jwk.kty != KTY_AKP {
+            return Err(serde_json::Error::custom(
+                "unsupported JWK kty, expected AKP",
+            ));
+        }
+        match jwk.alg {
+            None => return Err(serde_json::Error::custom("missing JWK alg")),
+            Some(alg) if alg != algorithm::<P>() => {
+                return Err(serde_json::Error::custom(format!(
+                    "JWK alg mismatch: expected {}, got {alg}",
+                    algorithm::<P>()
+                )));
+            }
+            Some(_) => {}
+        }
+        let bytes = Base64UrlUnpadded::decode_vec(&jwk.public)
+            .map_err(|_| serde_json::Error::custom("invalid base64url in JWK pub"))?;
+        Self::try_from(bytes).map_err(serde_json::Error::custom)
+    }
+}
@@ -0,0 +1,281 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Known Answer Test vectors in the NIST `.rsp` format.
+//!
+//! Available with the `kat` feature. [`parse_rsp`] reads a response file such
+//! as the `PQCsignKAT_*.rsp` files published with the MAYO submission, and
+//! [`run_against`] replays its vectors through this crate.
+//! [`run_against_deterministic`] does the same for vectors of the
+//! deterministic variant, signed without a randomizer. The MAYO submission
+//! publishes no such vectors, so it is meant for vectors generated
+//! elsewhere, e.g. by another implementation's `sign_deterministic`.
+//!
+//! # File format
+//!
+//! A response file is a sequence of records separated by blank lines. Lines
+//! starting with `#` are comments and also end the current record. Every other
+//! line is `key = value`:
+//!
+//! | Key | Value |
+//! |-----|-------|
+//! | `count` | decimal vector index; starts a new record |
+//! | `seed` | hex, the 48-byte seed for [`NistDrbg`] |
+//! | `mlen` | decimal length of `msg` |
+//! | `msg` | hex, the message |
+//! | `pk` | hex, the expected compact public key |
+//! | `sk` | hex, the expected compact secret key |
+//! | `smlen` | decimal length of `sm` |
+//! | `sm` | hex, the expected signature followed by the message |
+//!
+//! Other keys are ignored.
+
+use crate::error::{Error, Result};
+use crate::keypair::KeyPair;
+use crate::mayo_signature::Signature;
+use crate::params::MayoParameter;
+use aes::Aes256;
+use aes::cipher::{Array, BlockCipherEncrypt, KeyInit};
+use core::convert::Infallible;
+use zeroize::Zeroize;
+
+/// Length of the seed that initializes [`NistDrbg`].
+pub const SEED_BYTES: usize = 48;
+
+/// One record of a response file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KatVector {
+    /// Vector index.
+    pub count: usize,
+    /// Seed for the DRBG that generates the key pair and the signing salt.
+    pub seed: Vec<u8>,
+    /// Declared message length.
+    pub mlen: usize,
+    /// Message to sign.
+    pub msg: Vec<u8>,
+    /// Expected compact public key.
+    pub pk: Vec<u8>,
+    /// Expected compact secret key.
+    pub sk: Vec<u8>,
+    /// Declared signed message length.
+    pub smlen: usize,
+    /// Expected signature followed by the message.
+    pub sm: Vec<u8>,
+}
+
+/// Parse the records of a `.rsp` file.
+///
+/// Returns [`Error::InvalidKatFile`] with the 1-based line number of the first
+/// field that is not valid decimal or hex, or of a field before the first
+/// `count`.
+pub fn parse_rsp(content: &str) -> Result<Vec<KatVector>> {
+    let mut vectors = Vec::new();
+    let mut current: Option<KatVector> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            vectors.extend(current.take());
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        let invalid = || Error::InvalidKatFile { line: index + 1 };
+        let number = || value.parse::<usize>().map_err(|_| invalid());
+        let bytes = || hex::decode(value).map_err(|_| invalid());
+
+        if key == "count" {
+            vectors.extend(current.take());
+            current = Some(KatVector {
+                count: number()?,
+                ..KatVector::default()
+            });
+            continue;
+        }
+        let Some(vector) = current.as_mut() else {
+            return Err(invalid());
+        };
+        match key {
+            "seed" => vector.seed = bytes()?,
+            "mlen" => vector.mlen = number()?,
+            "msg" => vector.msg = bytes()?,
+            "pk" => vector.pk = bytes()?,
+            "sk" => vector.sk = bytes()?,
+            "smlen" => vector.smlen = number()?,
+            "sm" => vector.sm = bytes()?,
+            _ => {}
+        }
+    }
+    vectors.extend(current);
+    Ok(vectors)
+}
+
+/// Replay `vectors` through parameter set `P`.
+///
+/// For each vector this seeds a [`NistDrbg`], generates a key pair and signs
+/// `msg` from it, then checks the public key, secret key and signed message
+/// against the expected values and verifies the signature. Stops at the first
+/// vector that does not match, returning [`Error::KatMismatch`] with its
+/// `count` and the name of the field. A seed that is not [`SEED_BYTES`] long
+/// returns [`Error::InvalidSeedLength`].
+pub fn run_against<P: MayoParameter>(vectors: &[KatVector]) -> Result<()> {
+    run_with::<P>(vectors, |keypair, rng, msg| {
+        keypair.signing_key().sign_with_rng(rng, msg)
+    })
+}
+
+/// Replay `vectors` of the deterministic variant through parameter set `P`.
+///
+/// Like [`run_against`], the key pair comes from a [`NistDrbg`] seeded with
+/// the vector's seed, but the message is signed with
+/// [`SigningKey::sign_deterministic`](crate::SigningKey::sign_deterministic):
+/// the randomizer is all zeros and nothing further is drawn from the DRBG.
+/// The signature must match `sm` byte for byte. Errors are as for
+/// `run_against`.
+pub fn run_against_deterministic<P: MayoParameter>(vectors: &[KatVector]) -> Result<()> {
+    run_with::<P>(vectors, |keypair, _, msg| {
+        keypair.signing_key().sign_deterministic(msg)
+    })
+}
+
+fn run_with<P: MayoParameter>(
+    vectors: &[KatVector],
+    sign: impl Fn(&KeyPair<P>, &mut NistDrbg, &[u8]) -> Result<Signature<P>>,
+) -> Result<()> {
+    for vector in vectors {
+        let seed = <&[u8; SEED_BYTES]>::try_from(vector.seed.as_slice()).map_err(|_| {
+            Error::InvalidSeedLength {
+                expected: SEED_BYTES,
+                got: vector.seed.len(),
+            }
+        })?;
+        let mismatch = |field| Error::KatMismatch {
+            count: vector.count,
+            field,
+        };
+        let mut rng = NistDrbg::new(seed);
+
+        let keypair = KeyPair::<P>::generate(&mut rng)?;
+        if keypair.verifying_key().as_ref() != vector.pk.as_slice() {
+            return Err(mismatch("pk"));
+        }
+        if keypair.signing_key().as_ref() != vector.sk.as_slice() {
+            return Err(mismatch("sk"));
+        }
+
+        let signature = sign(&keypair, &mut rng, &vector.msg)?;
+        if vector.msg.len() != vector.mlen {
+            return Err(mismatch("mlen"));
+        }
+        if P::SIG_BYTES + vector.msg.len() != vector.smlen {
+            return Err(mismatch("smlen"));
+        }
+        let (sig, msg) = vector
+            .sm
+            .split_at_checked(P::SIG_BYTES)
+            .ok_or_else(|| mismatch("sm"))?;
+        if sig != signature.as_ref() || msg != vector.msg.as_slice() {
+            return Err(mismatch("sm"));
+        }
+
+        keypair.verifying_key().verify(&vector.msg, &signature)?;
+    }
+    Ok(())
+}
+
+/// The AES-256 CTR-DRBG, without prediction resistance, that the NIST KAT
+/// generators draw keys and salts from.
+///
+/// Only suitable for reproducing test vectors: its whole output is determined
+/// by the seed.
+pub struct NistDrbg {
+    key: [u8; 32],
+    v: [u8; 16],
+}
+
+impl core::fmt::Debug for NistDrbg {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("NistDrbg").finish_non_exhaustive()
+    }
+}
+
+impl Drop for NistDrbg {
+    fn drop(&mut self) {
+        self.key.zeroize();
+        self.v.zeroize();
+    }
+}
+
+impl NistDrbg {
+    /// Instantiate the DRBG from a 48-byte seed.
+    pub fn new(seed: &[u8; SEED_BYTES]) -> Self {
+        let mut drbg = Self {
+            key: [0u8; 32],
+            v: [0u8; 16],
+        };
+        drbg.update(seed);
+        drbg
+    }
+
+    fn update(&mut self, provided_data: &[u8; SEED_BYTES]) {
+        let cipher = Aes256::new(&Array::from(self.key));
+        let mut temp = [0u8; SEED_BYTES];
+        for chunk in temp.chunks_exact_mut(16) {
+            self.increment_v();
+            let mut block = Array::from(self.v);
+            cipher.encrypt_block(&mut block);
+            chunk.copy_from_slice(&block);
+        }
+        for (t, d) in temp.iter_mut().zip(provided_data) {
+            *t ^= d;
+        }
+        self.key.copy_from_slice(&temp[..32]);
+        self.v.copy_from_slice(&temp[32..]);
+        temp.zeroize();
+    }
+
+    fn increment_v(&mut self) {
+        for byte in self.v.iter_mut().rev() {
+            let (next, carry) = byte.overflowing_add(1);
+            *byte = next;
+            if !carry {
+                break;
+            }
+        }
+    }
+
+    fn generate(&mut self, output: &mut [u8]) {
+        let cipher = Aes256::new(&Array::from(self.key));
+        for chunk in output.chunks_mut(16) {
+            self.increment_v();
+            let mut block = Array::from(self.v);
+            cipher.encrypt_block(&mut block);
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+        self.update(&[0u8; SEED_BYTES]);
+    }
+}
+
+impl rand::TryRng for NistDrbg {
+    type Error = Infallible;
+
+    fn try_next_u32(&mut self) -> core::result::Result<u32, Infallible> {
+        let mut buf = [0u8; 4];
+        self.generate(&mut buf);
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn try_next_u64(&mut self) -> core::result::Result<u64, Infallible> {
+        let mut buf = [0u8; 8];
+        self.generate(&mut buf);
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> core::result::Result<(), Infallible> {
+        self.generate(dest);
+        Ok(())
+    }
+}
+
+impl rand::TryCryptoRng for NistDrbg {}
@@ -3,32 +3,52 @@
 //! MAYO key generation.
 
 use crate::codec::{decode, pack_m_vecs};
-use crate::error::Result;
+use crate::error::{Error, Result};
+use crate::keystream::{Aes128Ctr, Keystream};
 use crate::matrix_ops::{compute_p3, m_upper};
 use crate::params::{MAX_M, MayoParameter};
-use aes::Aes128;
-use ctr::cipher::{KeyIvInit, StreamCipher};
+use crate::xof::Xof;
 use rand::CryptoRng;
-use shake::Shake256;
-use shake::digest::{ExtendableOutput, Update, XofReader};
 use zeroize::Zeroizing;
 
-type Aes128Ctr32 = ctr::Ctr32BE<Aes128>;
-
 /// Expand P1 and P2 from a public key seed using AES-128-CTR.
-pub(crate) fn expand_p1_p2<P: MayoParameter>(seed_pk: &[u8]) -> Vec<u64> {
+///
+/// Returns [`Error::InvalidKeyLength`] if `seed_pk` is shorter than the
+/// 16-byte AES key it is used as.
+pub(crate) fn expand_p1_p2<P: MayoParameter>(seed_pk: &[u8]) -> Result<Vec<u64>> {
+    let mut p = vec![0u64; P::P1_LIMBS + P::P2_LIMBS];
+    expand_p1_p2_into::<P>(seed_pk, &mut p)?;
+    Ok(p)
+}
+
+/// [`expand_p1_p2`] into the first `P1_LIMBS + P2_LIMBS` limbs of `out`.
+pub(crate) fn expand_p1_p2_into<P: MayoParameter>(seed_pk: &[u8], out: &mut [u64]) -> Result<()> {
+    expand_p1_p2_with::<P, Aes128Ctr>(seed_pk, out)
+}
+
+/// [`expand_p1_p2_into`] with the keystream `K` in place of AES-128-CTR.
+///
+/// The keystream is read as `m / 2`-byte packed m-vectors, P1 then P2, each
+/// loaded into `M_VEC_LIMBS` little-endian limbs.
+pub(crate) fn expand_p1_p2_with<P: MayoParameter, K: Keystream>(
+    seed_pk: &[u8],
+    out: &mut [u64],
+) -> Result<()> {
+    let aes_key = seed_pk
+        .get(..16)
+        .and_then(|k| <&[u8; 16]>::try_from(k).ok())
+        .ok_or(Error::InvalidKeyLength {
+            expected: P::PK_SEED_BYTES,
+            got: seed_pk.len(),
+        })?;
+
     let m_vec_limbs = P::M_VEC_LIMBS;
     let total_limbs = P::P1_LIMBS + P::P2_LIMBS;
     let num_vecs = total_limbs / m_vec_limbs;
     let packed_size = P::M / 2;
 
-    let mut result = vec![0u64; total_limbs];
-    let iv = [0u8; 16];
-    // cipher 0.5 / hybrid-array: `new` wants `&Array` key/iv; `new_from_slices`
-    // takes plain byte slices. Both inputs are exactly 16 bytes (PK seed + IV),
-    // so the length check never fails.
-    let mut cipher = Aes128Ctr32::new_from_slices(&seed_pk[..16], &iv)
-        .expect("AES-128-CTR key and IV are both 16 bytes");
+    let result = &mut out[..total_limbs];
+    let mut keystream = K::new(aes_key);
 
     // Generate keystream for several m-vectors per AES call to amortize the
     // per-call `apply_keystream` overhead (thousands of vectors per expansion),
@@ -42,8 +62,7 @@ pub(crate) fn expand_p1_p2<P: MayoParameter>(seed_pk: &[u8]) -> Vec<u64> {
     while i < num_vecs {
         let chunk_vecs = VECS_PER_CHUNK.min(num_vecs - i);
         let chunk = &mut buf[..chunk_vecs * packed_size];
-        chunk.fill(0);
-        cipher.apply_keystream(chunk);
+        keystream.fill(chunk);
 
         for v in 0..chunk_vecs {
             let src = &chunk[v * packed_size..(v + 1) * packed_size];
@@ -57,57 +76,80 @@ pub(crate) fn expand_p1_p2<P: MayoParameter>(seed_pk: &[u8]) -> Vec<u64> {
         i += chunk_vecs;
     }
 
-    result
+    Ok(())
 }
 
-/// Generate a compact MAYO keypair.
+/// The material every use of a compact secret key starts from.
 ///
-/// Produces a compact secret key (`csk`) and compact public key (`cpk`).
-pub(crate) fn mayo_keypair_compact<P: MayoParameter>(
-    cpk: &mut [u8],
-    csk: &mut [u8],
-    rng: &mut impl CryptoRng,
+/// `SHAKE256(seed_sk)` gives `seed_pk || O_bytes`; `O` is decoded from the
+/// latter and P1, P2 are expanded from the former. Key generation, public
+/// key derivation and secret key expansion all begin here.
+pub(crate) struct SecretComponents {
+    /// The public seed.
+    pub(crate) seed_pk: Vec<u8>,
+    /// The oil space `O`, `v * o` nibbles (secret).
+    pub(crate) o: Zeroizing<Vec<u8>>,
+    /// `P1 || P2` in bitsliced limbs. Public, but wiped like the rest since
+    /// callers overwrite P2 with secret-dependent values.
+    pub(crate) p: Zeroizing<Vec<u64>>,
+}
+
+/// Derive [`SecretComponents`] from the seed at the start of `csk`.
+pub(crate) fn expand_secret_components<P: MayoParameter, X: Xof>(
+    csk: &[u8],
+) -> Result<SecretComponents> {
+    let mut s = Zeroizing::new(vec![0u8; P::PK_SEED_BYTES + P::O_BYTES]);
+    let mut o = Zeroizing::new(vec![0u8; P::V * P::O]);
+    let mut p = Zeroizing::new(vec![0u64; P::P1_LIMBS + P::P2_LIMBS]);
+    expand_secret_components_into::<P, X>(csk, &mut s, &mut o, &mut p)?;
+    let seed_pk = s[..P::PK_SEED_BYTES].to_vec();
+    Ok(SecretComponents { seed_pk, o, p })
+}
+
+/// [`expand_secret_components`] into caller-provided buffers: `s` receives
+/// `seed_pk || O_bytes` (`PK_SEED_BYTES + O_BYTES`), `o` the decoded `O`
+/// (`V * O`) and `p` the `P1_LIMBS + P2_LIMBS` limbs of `P1 || P2`.
+pub(crate) fn expand_secret_components_into<P: MayoParameter, X: Xof>(
+    csk: &[u8],
+    s: &mut [u8],
+    o: &mut [u8],
+    p: &mut [u64],
 ) -> Result<()> {
-    let m_vec_limbs = P::M_VEC_LIMBS;
-    let param_m = P::M;
     let param_v = P::V;
     let param_o = P::O;
-    let param_o_bytes = P::O_BYTES;
     let param_pk_seed_bytes = P::PK_SEED_BYTES;
-    let param_sk_seed_bytes = P::SK_SEED_BYTES;
-    let param_p3_limbs = P::P3_LIMBS;
 
-    // seed_sk <- random bytes
-    rng.fill_bytes(&mut csk[..param_sk_seed_bytes]);
-    let seed_sk = &csk[..param_sk_seed_bytes];
+    let seed_sk = &csk[..P::SK_SEED_BYTES];
 
     // S = SHAKE256(seed_sk) -> pk_seed || O_bytes
-    let mut s = Zeroizing::new(vec![0u8; param_pk_seed_bytes + param_o_bytes]);
-    let mut hasher = Shake256::default();
-    hasher.update(seed_sk);
-    let mut reader = hasher.finalize_xof();
-    reader.read(&mut s);
-
-    let seed_pk = &s[..param_pk_seed_bytes];
+    let s = &mut s[..param_pk_seed_bytes + P::O_BYTES];
+    X::hash(&[seed_sk], s);
 
-    // Decode O matrix
-    let mut o = Zeroizing::new(vec![0u8; param_v * param_o]);
-    decode(&s[param_pk_seed_bytes..], &mut o, param_v * param_o);
+    // Decode O
+    decode(&s[param_pk_seed_bytes..], o, param_v * param_o);
 
-    // Expand P1 and P2
-    let mut p = Zeroizing::new(expand_p1_p2::<P>(seed_pk));
+    expand_p1_p2_into::<P>(&s[..param_pk_seed_bytes], p)
+}
 
-    let p1_limbs = P::P1_LIMBS;
+/// Compute P3 from `components` and write the compact public key
+/// `seed_pk || Upper(P3)` into `cpk`.
+///
+/// P2 in `components` is overwritten with `P1*O + P2` along the way.
+pub(crate) fn pack_public_key<P: MayoParameter>(components: &mut SecretComponents, cpk: &mut [u8]) {
+    let m_vec_limbs = P::M_VEC_LIMBS;
+    let param_o = P::O;
+    let param_pk_seed_bytes = P::PK_SEED_BYTES;
+    let param_p3_limbs = P::P3_LIMBS;
 
     // Compute P3 = O^t * (P1*O + P2)
     let mut p3 = Zeroizing::new(vec![0u64; param_o * param_o * m_vec_limbs]);
     {
-        let (p1, p2) = p.split_at_mut(p1_limbs);
-        compute_p3::<P>(p1, p2, &o, &mut p3);
+        let (p1, p2) = components.p.split_at_mut(P::P1_LIMBS);
+        compute_p3::<P>(p1, &mut p2[..P::P2_LIMBS], &components.o, &mut p3);
     }
 
     // Store seed_pk in cpk
-    cpk[..param_pk_seed_bytes].copy_from_slice(seed_pk);
+    cpk[..param_pk_seed_bytes].copy_from_slice(&components.seed_pk);
 
     // Compute Upper(P3) and pack into cpk
     let mut p3_upper = Zeroizing::new(vec![0u64; param_p3_limbs]);
@@ -116,8 +158,84 @@ pub(crate) fn mayo_keypair_compact<P: MayoParameter>(
         &p3_upper,
         &mut cpk[param_pk_seed_bytes..],
         param_p3_limbs / m_vec_limbs,
-        param_m,
+        P::M,
     );
+}
+
+/// Generate a compact MAYO keypair.
+///
+/// Produces a compact secret key (`csk`) and compact public key (`cpk`).
+pub(crate) fn mayo_keypair_compact<P: MayoParameter, X: Xof>(
+    cpk: &mut [u8],
+    csk: &mut [u8],
+    rng: &mut impl CryptoRng,
+) -> Result<()> {
+    // seed_sk <- random bytes
+    rng.fill_bytes(&mut csk[..P::SK_SEED_BYTES]);
 
+    let mut components = expand_secret_components::<P, X>(csk)?;
+    pack_public_key::<P>(&mut components, cpk);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mayo1;
+
+    #[test]
+    fn expand_p1_p2_rejects_short_seed() {
+        assert!(matches!(
+            expand_p1_p2::<Mayo1>(&[0u8; 15]),
+            Err(Error::InvalidKeyLength {
+                expected: 16,
+                got: 15
+            })
+        ));
+        assert!(expand_p1_p2::<Mayo1>(&[0u8; 16]).is_ok());
+    }
+
+    /// Byte `i` of the stream is `i mod 251`, so every limb is predictable.
+    struct CountingKeystream(usize);
+
+    impl Keystream for CountingKeystream {
+        fn new(_key: &[u8; 16]) -> Self {
+            Self(0)
+        }
+
+        fn fill(&mut self, buf: &mut [u8]) {
+            for b in buf {
+                *b = u8::try_from(self.0 % 251).expect("below 251");
+                self.0 += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn expand_p1_p2_unpacks_keystream() {
+        let mut limbs = vec![0u64; Mayo1::P1_LIMBS + Mayo1::P2_LIMBS];
+        expand_p1_p2_with::<Mayo1, CountingKeystream>(&[0u8; 16], &mut limbs).expect("expand");
+
+        let packed = Mayo1::M / 2;
+        for (v, m_vec) in limbs.chunks_exact(Mayo1::M_VEC_LIMBS).enumerate() {
+            for (j, &limb) in m_vec.iter().enumerate() {
+                // The last limb holds the remaining `packed % 8` bytes.
+                let mut bytes = [0u8; 8];
+                let len = (packed - 8 * j).min(8);
+                for (k, b) in bytes[..len].iter_mut().enumerate() {
+                    *b = u8::try_from((v * packed + 8 * j + k) % 251).expect("below 251");
+                }
+                assert_eq!(limb, u64::from_le_bytes(bytes), "m-vector {v}, limb {j}");
+            }
+        }
+
+        // The real keystream goes through the same unpacking.
+        let mut stream = Aes128Ctr::new(&[0u8; 16]);
+        let mut first = [0u8; 8];
+        stream.fill(&mut first);
+        assert_eq!(
+            expand_p1_p2::<Mayo1>(&[0u8; 16]).expect("expand")[0],
+            u64::from_le_bytes(first)
+        );
+    }
+}
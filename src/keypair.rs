@@ -2,18 +2,23 @@
 
 //! MAYO keypair generation.
 
-use crate::error::Result;
-use crate::keygen::mayo_keypair_compact;
+use crate::error::{Error, Result};
+use crate::keygen::{expand_secret_components, mayo_keypair_compact, pack_public_key};
 use crate::params::MayoParameter;
-use crate::signing_key::SigningKey;
-use crate::verifying_key::VerifyingKey;
+use crate::seed::Seed;
+use crate::signing_key::{ExpandedSigningKey, SigningKey};
+use crate::verifying_key::{ExpandedVerifyingKey, VerifyingKey};
+use crate::xof::{Shake256Xof, Xof};
 use rand::CryptoRng;
+use subtle::ConstantTimeEq;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// A MAYO keypair containing both signing and verifying keys.
+///
+/// With the `serde` feature a keypair serializes exactly like its
+/// [`SigningKey`], as the secret seed alone; the verifying key is re-derived
+/// on deserialization.
 #[derive(Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[cfg_attr(feature = "serde", serde(bound = ""))]
 pub struct KeyPair<P: MayoParameter> {
     signing_key: SigningKey<P>,
     verifying_key: VerifyingKey<P>,
@@ -25,6 +30,15 @@ impl<P: MayoParameter> AsRef<VerifyingKey<P>> for KeyPair<P> {
     }
 }
 
+impl<P: MayoParameter> From<KeyPair<P>> for (SigningKey<P>, VerifyingKey<P>) {
+    fn from(keypair: KeyPair<P>) -> Self {
+        // `KeyPair` wipes itself on drop, so the keys are copied out and the
+        // originals cleared when `keypair` goes out of scope.
+        (keypair.signing_key.clone(), keypair.verifying_key.clone())
+    }
+}
+
+#[cfg(feature = "signature-traits")]
 impl<P: MayoParameter> signature::KeypairRef for KeyPair<P> {
     type VerifyingKey = VerifyingKey<P>;
 }
@@ -47,20 +61,90 @@ impl<P: MayoParameter> PartialEq for KeyPair<P> {
 
 impl<P: MayoParameter> Eq for KeyPair<P> {}
 
+impl<P: MayoParameter> PartialEq<SigningKey<P>> for KeyPair<P> {
+    /// Whether this keypair holds `other`, compared in constant time.
+    fn eq(&self, other: &SigningKey<P>) -> bool {
+        self.signing_key == *other
+    }
+}
+
+impl<P: MayoParameter> PartialEq<VerifyingKey<P>> for KeyPair<P> {
+    /// Whether this keypair holds `other`.
+    fn eq(&self, other: &VerifyingKey<P>) -> bool {
+        self.verifying_key == *other
+    }
+}
+
 impl<P: MayoParameter> Zeroize for KeyPair<P> {
+    /// Overwrite both keys with zeros in place.
+    ///
+    /// The verifying key is public, but it is cleared too so that a wiped
+    /// keypair holds no trace of the key at all. Dropping a keypair does the
+    /// same; call this to wipe it earlier.
     fn zeroize(&mut self) {
         self.signing_key.zeroize();
+        self.verifying_key.zeroize();
+    }
+}
+
+impl<P: MayoParameter> Drop for KeyPair<P> {
+    fn drop(&mut self) {
+        self.zeroize();
     }
 }
 
 impl<P: MayoParameter> ZeroizeOnDrop for KeyPair<P> {}
 
+#[cfg(feature = "serde")]
+impl<P: MayoParameter> serde::Serialize for KeyPair<P> {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.signing_key.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, P: MayoParameter> serde::Deserialize<'de> for KeyPair<P> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let signing_key = SigningKey::deserialize(deserializer)?;
+        Self::from_signing_key(signing_key).map_err(serde::de::Error::custom)
+    }
+}
+
+impl<P: MayoParameter> From<&Seed<P>> for KeyPair<P> {
+    /// Derive the keypair for a typed seed; this cannot fail.
+    fn from(seed: &Seed<P>) -> Self {
+        Self::from_csk(seed.bytes.clone())
+            .expect("the public seed derived from a secret key is PK_SEED_BYTES long")
+    }
+}
+
+impl<P: MayoParameter> From<SigningKey<P>> for KeyPair<P> {
+    /// Pair a signing key with its cached public key; this cannot fail.
+    ///
+    /// Same as [`KeyPair::from_signing_key`], whose only error path is the
+    /// debug-build re-derivation, and that cannot fail for a `SigningKey`
+    /// whose compact secret key is always `CSK_BYTES` long.
+    fn from(signing_key: SigningKey<P>) -> Self {
+        Self::from_signing_key(signing_key)
+            .expect("deriving a public key from a typed signing key cannot fail")
+    }
+}
+
 impl<P: MayoParameter> KeyPair<P> {
+    /// Length in bytes of the seed accepted by [`from_seed`](Self::from_seed).
+    pub const SEED_LEN: usize = P::SK_SEED_BYTES;
+
     /// Generate a new random keypair.
     pub fn generate(rng: &mut impl CryptoRng) -> Result<Self> {
         let mut cpk = vec![0u8; P::CPK_BYTES];
         let mut csk = hybrid_array::Array::<u8, P::CskSize>::default();
-        mayo_keypair_compact::<P>(&mut cpk, &mut csk, rng)?;
+        mayo_keypair_compact::<P, Shake256Xof>(&mut cpk, &mut csk, rng)?;
         Ok(Self {
             signing_key: SigningKey {
                 bytes: csk,
@@ -70,11 +154,31 @@ impl<P: MayoParameter> KeyPair<P> {
         })
     }
 
+    /// Generate `n` random keypairs in parallel.
+    ///
+    /// The `n` secret seeds are drawn from `rng` sequentially, then each
+    /// keypair is derived from its own seed on the rayon thread pool. The
+    /// result is identical to calling [`KeyPair::generate`] `n` times with the
+    /// same RNG.
+    #[cfg(feature = "rayon")]
+    pub fn generate_batch(n: usize, rng: &mut impl CryptoRng) -> Result<Vec<Self>> {
+        use rayon::prelude::*;
+        use zeroize::Zeroizing;
+
+        let mut seeds = Zeroizing::new(vec![0u8; n * P::SK_SEED_BYTES]);
+        for seed in seeds.chunks_exact_mut(P::SK_SEED_BYTES) {
+            rng.fill_bytes(seed);
+        }
+        seeds
+            .par_chunks_exact(P::SK_SEED_BYTES)
+            .map(Self::from_seed)
+            .collect()
+    }
+
     /// Generate a keypair from a specific seed.
     ///
     /// The seed must be exactly `SK_SEED_BYTES` long.
     pub fn from_seed(seed: &[u8]) -> Result<Self> {
-        use crate::error::Error;
         if seed.len() != P::SK_SEED_BYTES {
             return Err(Error::InvalidSeedLength {
                 expected: P::SK_SEED_BYTES,
@@ -84,21 +188,129 @@ impl<P: MayoParameter> KeyPair<P> {
 
         let mut csk = hybrid_array::Array::<u8, P::CskSize>::default();
         csk[..P::SK_SEED_BYTES].copy_from_slice(seed);
+        Self::from_csk(csk)
+    }
 
+    /// Derive a keypair from a master secret and a purpose label.
+    ///
+    /// The seed is `SHAKE256(master || label || len(label))[..SK_SEED_BYTES]`,
+    /// with the label length as 8 little-endian bytes so that moving bytes
+    /// between `master` and `label` cannot produce the same seed. The same
+    /// `(master, label)` always gives the same keypair, and different labels
+    /// give unrelated ones. `master` should carry at least `SK_SEED_BYTES` of
+    /// entropy; this is a derivation, not a password hash.
+    pub fn derive(master: &[u8], label: &[u8]) -> Result<Self> {
+        let label_len = (label.len() as u64).to_le_bytes();
+        let mut seed = zeroize::Zeroizing::new(vec![0u8; P::SK_SEED_BYTES]);
+        Shake256Xof::hash(&[master, label, &label_len], &mut seed);
+        Self::from_seed(&seed)
+    }
+
+    fn from_csk(csk: hybrid_array::Array<u8, P::CskSize>) -> Result<Self> {
         let mut cpk = vec![0u8; P::CPK_BYTES];
-        derive_cpk_from_csk::<P>(&csk, &mut cpk);
+        derive_cpk_from_csk::<P, Shake256Xof>(&csk, &mut cpk)?;
+        Ok(Self::from_parts(csk, cpk))
+    }
 
-        Ok(Self {
+    fn from_parts(csk: hybrid_array::Array<u8, P::CskSize>, cpk: Vec<u8>) -> Self {
+        Self {
             signing_key: SigningKey {
                 bytes: csk,
                 cpk: cpk.clone(),
             },
             verifying_key: VerifyingKey::from_bytes_unchecked(cpk),
-        })
+        }
+    }
+
+    /// Derive a keypair from a seed and check it against a stored public
+    /// key.
+    ///
+    /// The whole of `cpk` must equal the public key derived from `seed`;
+    /// nothing in it is trusted, since a stored key that merely shares the
+    /// public seed would make [`verifying_key`](Self::verifying_key) reject
+    /// this keypair's own signatures. Returns [`Error::MalformedKey`] on any
+    /// difference.
+    #[cfg(feature = "pkcs8")]
+    pub(crate) fn from_seed_and_public_key(seed: &[u8], cpk: &[u8]) -> Result<Self> {
+        if cpk.len() != P::CPK_BYTES {
+            return Err(Error::InvalidKeyLength {
+                expected: P::CPK_BYTES,
+                got: cpk.len(),
+            });
+        }
+        let keypair = Self::from_seed(seed)?;
+        if keypair.verifying_key.as_ref() != cpk {
+            return Err(Error::MalformedKey);
+        }
+        Ok(keypair)
+    }
+
+    /// Generate a keypair from a specific seed, rejecting obviously weak seeds.
+    ///
+    /// Like [`from_seed`](Self::from_seed), but returns [`Error::WeakSeed`] if
+    /// the seed is all `0x00` or all `0xFF`. Neither is a weakness of MAYO
+    /// itself, but both are what an uninitialized or erased buffer looks
+    /// like, and every caller that passes one gets the same key. Use
+    /// `from_seed` when arbitrary seeds must be accepted, e.g. for test
+    /// vectors.
+    pub fn from_seed_checked(seed: &[u8]) -> Result<Self> {
+        if seed.len() == P::SK_SEED_BYTES
+            && (seed.iter().all(|&b| b == 0x00) || seed.iter().all(|&b| b == 0xFF))
+        {
+            return Err(Error::WeakSeed);
+        }
+        Self::from_seed(seed)
+    }
+
+    /// Construct a keypair from an expanded secret key.
+    ///
+    /// The input must be laid out as `seed_sk || O || P1 || L`, the expanded
+    /// secret key format of the MAYO reference implementation (see
+    /// [`ExpandedSigningKey::to_expanded_bytes`](crate::ExpandedSigningKey::to_expanded_bytes)).
+    /// The compact key is recovered from the leading secret seed, and the
+    /// remaining material is checked against a fresh expansion of that seed.
+    ///
+    /// | Type | Expanded Secret Key Size |
+    /// |------|--------------------------|
+    /// | [`Mayo1`](crate::Mayo1) | 144,831 B |
+    /// | [`Mayo2`](crate::Mayo2) | 145,304 B |
+    /// | [`Mayo3`](crate::Mayo3) | 376,736 B |
+    /// | [`Mayo5`](crate::Mayo5) | 842,739 B |
+    ///
+    /// Returns [`Error::InvalidKeyLength`] if the input is not `ESK_BYTES` long,
+    /// and [`Error::MalformedKey`] if the expanded material is inconsistent
+    /// with the secret seed.
+    pub fn from_expanded_secret_key(esk: &[u8]) -> Result<Self> {
+        if esk.len() != P::ESK_BYTES {
+            return Err(Error::InvalidKeyLength {
+                expected: P::ESK_BYTES,
+                got: esk.len(),
+            });
+        }
+
+        let keypair = Self::from_seed(&esk[..P::SK_SEED_BYTES])?;
+        let expected = keypair.signing_key.expand().to_expanded_bytes();
+        if !bool::from(expected.ct_eq(esk)) {
+            return Err(Error::MalformedKey);
+        }
+        Ok(keypair)
     }
 
     /// Construct a keypair from a [`SigningKey`], deriving the corresponding [`VerifyingKey`].
+    ///
+    /// The public key cached in the signing key is reused. Debug builds
+    /// re-derive it from the seed and panic on a mismatch, which would mean
+    /// the signing key was corrupted after construction.
     pub fn from_signing_key(signing_key: SigningKey<P>) -> Result<Self> {
+        #[cfg(debug_assertions)]
+        {
+            let mut cpk = vec![0u8; P::CPK_BYTES];
+            derive_cpk_from_csk::<P, Shake256Xof>(&signing_key.bytes, &mut cpk)?;
+            debug_assert!(
+                cpk == signing_key.cpk,
+                "signing key's cached public key does not match its seed"
+            );
+        }
         let verifying_key = VerifyingKey::from_bytes_unchecked(signing_key.cpk.clone());
         Ok(Self {
             signing_key,
@@ -106,6 +318,36 @@ impl<P: MayoParameter> KeyPair<P> {
         })
     }
 
+    /// Re-derive the verifying key from the signing key, in place.
+    ///
+    /// Overwrites both the stored verifying key and the copy cached in the
+    /// signing key. A safety net for keypairs assembled from separately
+    /// stored parts, or whose public half is suspected corrupt; after this
+    /// call the keypair is consistent with its secret seed.
+    pub fn regenerate_public_key(&mut self) -> Result<()> {
+        let mut cpk = vec![0u8; P::CPK_BYTES];
+        derive_cpk_from_csk::<P, Shake256Xof>(&self.signing_key.bytes, &mut cpk)?;
+        self.signing_key.cpk.copy_from_slice(&cpk);
+        self.verifying_key = VerifyingKey::from_bytes_unchecked(cpk);
+        Ok(())
+    }
+
+    /// Expand both halves for repeated signing and verification.
+    ///
+    /// Both halves need P1 and P2, generated from the public seed with
+    /// AES-128-CTR. That is nearly all of the cost of expanding a verifying
+    /// key, so this expands them once, for the signing key, and gives the
+    /// verifying key a copy. The results equal [`SigningKey::expand`] and
+    /// [`VerifyingKey::expand`].
+    pub fn expand(&self) -> (ExpandedSigningKey<P>, ExpandedVerifyingKey<P>) {
+        let signing_key = self.signing_key.expand();
+        let verifying_key = ExpandedVerifyingKey::from_expanded_parts(
+            self.verifying_key.bytes.clone(),
+            signing_key.public_matrices(),
+        );
+        (signing_key, verifying_key)
+    }
+
     /// Get a reference to the signing key.
     pub fn signing_key(&self) -> &SigningKey<P> {
         &self.signing_key
@@ -115,63 +357,87 @@ impl<P: MayoParameter> KeyPair<P> {
     pub fn verifying_key(&self) -> &VerifyingKey<P> {
         &self.verifying_key
     }
+
+    /// A copy of the verifying key.
+    ///
+    /// This clones the stored public key bytes; nothing is re-derived.
+    pub fn verifying_key_cloned(&self) -> VerifyingKey<P> {
+        self.verifying_key.clone()
+    }
+
+    /// Take the signing key, dropping the verifying key.
+    ///
+    /// The key is copied out and the keypair zeroized as it drops.
+    pub fn into_signing_key(self) -> SigningKey<P> {
+        self.signing_key.clone()
+    }
+
+    /// Take the verifying key.
+    ///
+    /// The signing key is dropped, which zeroizes it.
+    pub fn into_verifying_key(self) -> VerifyingKey<P> {
+        self.verifying_key.clone()
+    }
+
+    /// The secret seed, see [`SigningKey::secret_seed`].
+    pub fn secret_seed(&self) -> &[u8] {
+        self.signing_key.secret_seed()
+    }
+
+    /// The public seed, see [`SigningKey::public_seed`].
+    pub fn public_seed(&self) -> &[u8] {
+        self.signing_key.public_seed()
+    }
 }
 
 /// Derive the compact public key from a compact secret key.
-pub(crate) fn derive_cpk_from_csk<P: MayoParameter>(csk: &[u8], cpk: &mut [u8]) {
-    use crate::codec::{decode, pack_m_vecs};
-    use crate::keygen::expand_p1_p2;
-    use crate::matrix_ops::{compute_p3, m_upper};
-    use shake::Shake256;
-    use shake::digest::{ExtendableOutput, Update, XofReader};
-    use zeroize::Zeroizing;
-
-    let m_vec_limbs = P::M_VEC_LIMBS;
-    let param_m = P::M;
-    let param_v = P::V;
-    let param_o = P::O;
-    let param_o_bytes = P::O_BYTES;
-    let param_pk_seed_bytes = P::PK_SEED_BYTES;
-    let param_sk_seed_bytes = P::SK_SEED_BYTES;
-    let param_p3_limbs = P::P3_LIMBS;
-
-    let seed_sk = &csk[..param_sk_seed_bytes];
-
-    // S = SHAKE256(seed_sk) -> pk_seed || O_bytes
-    let mut s = Zeroizing::new(vec![0u8; param_pk_seed_bytes + param_o_bytes]);
-    let mut hasher = Shake256::default();
-    hasher.update(seed_sk);
-    let mut reader = hasher.finalize_xof();
-    reader.read(&mut s);
-
-    let seed_pk = &s[..param_pk_seed_bytes];
-
-    // Decode O
-    let mut o = Zeroizing::new(vec![0u8; param_v * param_o]);
-    decode(&s[param_pk_seed_bytes..], &mut o, param_v * param_o);
-
-    // Expand P1, P2. These are public once seed_pk is stored in cpk.
-    let p = expand_p1_p2::<P>(seed_pk);
-    let p1_limbs = P::P1_LIMBS;
-
-    // Compute P3
-    let mut p3 = Zeroizing::new(vec![0u64; param_o * param_o * m_vec_limbs]);
-    {
-        let p1 = &p[..p1_limbs];
-        let mut p2 = Zeroizing::new(p[p1_limbs..p1_limbs + P::P2_LIMBS].to_vec());
-        compute_p3::<P>(p1, &mut p2, &o, &mut p3);
-    }
-
-    // Store seed_pk
-    cpk[..param_pk_seed_bytes].copy_from_slice(seed_pk);
-
-    // Upper(P3) -> pack into cpk
-    let mut p3_upper = vec![0u64; param_p3_limbs];
-    m_upper(m_vec_limbs, &p3, &mut p3_upper, param_o);
-    pack_m_vecs(
-        &p3_upper,
-        &mut cpk[param_pk_seed_bytes..],
-        param_p3_limbs / m_vec_limbs,
-        param_m,
-    );
+///
+/// Although the output is public, the derivation works through secret
+/// material: the oil space `O` decoded from `SHAKE256(seed_sk)`, and
+/// `P1 O + P2` on the way to P3. Every buffer holding it is `Zeroizing`, so
+/// it is wiped when this returns, on success or error. Key generation does
+/// the same after drawing the seed.
+pub(crate) fn derive_cpk_from_csk<P: MayoParameter, X: Xof>(
+    csk: &[u8],
+    cpk: &mut [u8],
+) -> Result<()> {
+    let mut components = expand_secret_components::<P, X>(csk)?;
+    pack_public_key::<P>(&mut components, cpk);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mayo1;
+
+    #[test]
+    fn regenerate_public_key_repairs_corruption() {
+        let mut keypair = KeyPair::<Mayo1>::generate(&mut rand::rng()).expect("keygen failed");
+        let expected = keypair.verifying_key.clone();
+        let msg = b"repair me";
+        let sig = keypair
+            .signing_key()
+            .sign_with_rng(&mut rand::rng(), msg)
+            .expect("signing failed");
+
+        keypair.verifying_key.bytes[Mayo1::PK_SEED_BYTES] ^= 1;
+        keypair.signing_key.cpk[0] ^= 1;
+        assert!(keypair.verifying_key().verify(msg, &sig).is_err());
+
+        keypair.regenerate_public_key().expect("regenerate");
+        assert_eq!(keypair.verifying_key, expected);
+        assert_eq!(keypair.signing_key.cpk, expected.bytes);
+        assert!(keypair.verifying_key().verify(msg, &sig).is_ok());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "does not match its seed")]
+    fn from_signing_key_catches_corrupted_public_key() {
+        let keypair = KeyPair::<Mayo1>::generate(&mut rand::rng()).expect("keygen failed");
+        let mut signing_key = keypair.into_signing_key();
+        signing_key.cpk[Mayo1::PK_SEED_BYTES] ^= 1;
+        let _ = KeyPair::from_signing_key(signing_key);
+    }
 }
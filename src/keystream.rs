@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Keystream used to expand P1 and P2 from the public seed.
+//!
+//! [`expand_p1_p2_with`](crate::keygen::expand_p1_p2_with) is generic over
+//! [`Keystream`] so the unpacking of the matrices can be tested against a
+//! fixed keystream, independent of the AES implementation. [`Aes128Ctr`] is
+//! the only implementation the public API uses.
+
+use aes::Aes128;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+
+/// A stream of pseudorandom bytes keyed by a 16-byte seed.
+pub(crate) trait Keystream {
+    /// Start the stream for `key`.
+    fn new(key: &[u8; 16]) -> Self;
+
+    /// Overwrite `buf` with the next bytes of the stream.
+    fn fill(&mut self, buf: &mut [u8]);
+}
+
+/// AES-128 in counter mode with a zero IV and a 32-bit big-endian counter.
+pub(crate) struct Aes128Ctr(ctr::Ctr32BE<Aes128>);
+
+impl Keystream for Aes128Ctr {
+    fn new(key: &[u8; 16]) -> Self {
+        Self(ctr::Ctr32BE::<Aes128>::new(
+            &(*key).into(),
+            &[0u8; 16].into(),
+        ))
+    }
+
+    fn fill(&mut self, buf: &mut [u8]) {
+        buf.fill(0);
+        self.0.apply_keystream(buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aes128_ctr_known_keystream() {
+        // AES-128 under the all-zero key of the counter blocks 0, 1 and 2.
+        let expected = hex::decode(concat!(
+            "66e94bd4ef8a2c3b884cfa59ca342b2e",
+            "58e2fccefa7e3061367f1d57a4e7455a",
+            "0388dace60b6a392f328c2b971b2fe78",
+        ))
+        .expect("valid hex");
+
+        let mut stream = Aes128Ctr::new(&[0u8; 16]);
+        let mut got = [0xffu8; 48];
+        // The stream continues across calls of any length.
+        stream.fill(&mut got[..5]);
+        stream.fill(&mut got[5..]);
+        assert_eq!(got.as_slice(), expected.as_slice());
+    }
+}
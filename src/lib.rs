@@ -17,6 +17,9 @@
 //! | [`Mayo3`] | 3 | 681 B | 2,986 B | 32 B |
 //! | [`Mayo5`] | 5 | 964 B | 5,554 B | 40 B |
 //!
+//! [`MayoParameter::describe`] returns the same figures as a [`ParamInfo`],
+//! for tools that print them for any parameter set.
+//!
 //! All parameter sets implement the [`MayoParameter`] trait and can be used
 //! interchangeably as the generic parameter on [`KeyPair`], [`SigningKey`],
 //! [`ExpandedSigningKey`], [`VerifyingKey`], [`ExpandedVerifyingKey`],
@@ -26,19 +29,17 @@
 //!
 //! ```
 //! use pq_mayo::{KeyPair, Mayo1};
-//! use signature::{Signer, Verifier};
 //!
 //! let mut rng = rand::rng();
 //! let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen");
 //! let msg = b"hello world";
 //!
-//! let sig = keypair.signing_key().try_sign(msg).expect("sign");
+//! let sig = keypair.signing_key().sign_with_rng(&mut rng, msg).expect("sign");
 //! keypair.verifying_key().verify(msg, &sig).expect("verify");
 //! ```
 //!
 //! ```
 //! use pq_mayo::{KeyPair, Mayo1, Mayo2, Mayo3, Mayo5};
-//! use signature::{Signer, Verifier};
 //!
 //! let mut rng = rand::rng();
 //!
@@ -47,7 +48,7 @@
 //!
 //! // NIST security level 1 -- tweaked after the wedge attack analysis
 //! let kp2 = KeyPair::<Mayo2>::generate(&mut rng).expect("keygen");
-//! let sig = kp2.signing_key().try_sign(b"message").expect("sign");
+//! let sig = kp2.signing_key().sign_with_rng(&mut rng, b"message").expect("sign");
 //! kp2.verifying_key().verify(b"message", &sig).expect("verify");
 //!
 //! // NIST security level 3
@@ -57,6 +58,30 @@
 //! let kp5 = KeyPair::<Mayo5>::generate(&mut rng).expect("keygen");
 //! ```
 //!
+//! # The `signature` Traits
+//!
+//! With the default `signature-traits` feature, the signing keys implement
+//! `signature::Signer` and `signature::RandomizedSigner`, the verifying keys
+//! implement `signature::Verifier`, [`Signature`] implements
+//! `signature::SignatureEncoding` and [`KeyPair`] implements
+//! `signature::KeypairRef`, so they plug into code generic over those traits.
+//! `Signer::try_sign` draws the salt from the thread-local RNG.
+//! [`SigningKey`] and [`VerifyingKey`] also implement
+//! `signature::DigestSigner` and `signature::DigestVerifier` for an
+//! extendable-output hash the caller feeds, whose first `DIGEST_BYTES` bytes
+//! replace the message digest. With `shake::Shake256` the signatures are the
+//! ordinary ones.
+//!
+//! Disable default features to build without the `signature` crate. The
+//! inherent [`SigningKey::sign_with_rng`] and [`VerifyingKey::verify`] are
+//! always available. The `cose` and `hybrid` features need the traits and
+//! turn this one back on.
+//!
+//! ```toml
+//! [dependencies]
+//! pq-mayo = { version = "0.5", default-features = false }
+//! ```
+//!
 //! # Key Serialization
 //!
 //! Keys and signatures implement [`AsRef<[u8]>`] for exporting raw bytes
@@ -65,7 +90,6 @@
 //!
 //! ```
 //! use pq_mayo::{KeyPair, Mayo1, SigningKey, VerifyingKey, Signature};
-//! use signature::{Signer, Verifier};
 //!
 //! let mut rng = rand::rng();
 //! let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen");
@@ -79,7 +103,7 @@
 //! let vk = VerifyingKey::<Mayo1>::try_from(vk_bytes).expect("valid verifying key");
 //!
 //! // Sign with reconstructed key, verify with reconstructed key
-//! let sig = sk.try_sign(b"hello").expect("sign");
+//! let sig = sk.sign_with_rng(&mut rng, b"hello").expect("sign");
 //! vk.verify(b"hello", &sig).expect("verify");
 //!
 //! // Signatures can also be round-tripped through bytes
@@ -88,6 +112,24 @@
 //! vk.verify(b"hello", &sig2).expect("verify restored sig");
 //! ```
 //!
+//! [`VerifyingKey`] and [`Signature`] also implement `Display` and
+//! `FromStr` as lowercase hex, for command lines and text files. The
+//! signing key has no text form; write its bytes instead. The
+//! `examples/mayo_cli.rs` example drives keygen, signing and verification
+//! this way.
+//!
+//! ```
+//! use pq_mayo::{KeyPair, Mayo1, Signature, VerifyingKey};
+//!
+//! let mut rng = rand::rng();
+//! let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen");
+//! let sig = keypair.signing_key().sign_with_rng(&mut rng, b"hello").expect("sign");
+//!
+//! let vk: VerifyingKey<Mayo1> = keypair.verifying_key().to_string().parse().expect("hex");
+//! let sig: Signature<Mayo1> = sig.to_string().parse().expect("hex");
+//! vk.verify(b"hello", &sig).expect("verify");
+//! ```
+//!
 //! # Deriving a Verifying Key from a Signing Key
 //!
 //! A [`VerifyingKey`] can be derived from a [`SigningKey`] without
@@ -96,7 +138,6 @@
 //!
 //! ```
 //! use pq_mayo::{KeyPair, Mayo1, VerifyingKey};
-//! use signature::{Signer, Verifier};
 //!
 //! let mut rng = rand::rng();
 //! let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen");
@@ -104,7 +145,7 @@
 //! let vk = VerifyingKey::<Mayo1>::from(keypair.signing_key());
 //! assert_eq!(&vk, keypair.verifying_key());
 //!
-//! let sig = keypair.signing_key().try_sign(b"test").expect("sign");
+//! let sig = keypair.signing_key().sign_with_rng(&mut rng, b"test").expect("sign");
 //! vk.verify(b"test", &sig).expect("verify");
 //! ```
 //!
@@ -128,15 +169,30 @@
 //! );
 //! ```
 //!
+//! # Parallel Key Generation
+//!
+//! Enable the `rayon` feature to generate many keypairs across threads with
+//! [`KeyPair::generate_batch`]. Seeds are drawn from the RNG in order, so the
+//! output matches sequential [`KeyPair::generate`] calls with the same RNG.
+//! The same feature adds `verify_many`, which checks a slice of independent
+//! `(key, message, signature)` triples in parallel and returns the results in
+//! input order.
+//!
+//! ```toml
+//! [dependencies]
+//! pq-mayo = { version = "0.5", features = ["rayon"] }
+//! ```
+//!
 //! # Signing with a Caller-Provided RNG
 //!
 //! The [`SigningKey::sign_with_rng`] method allows passing a custom
 //! [`CryptoRng`](rand::CryptoRng) for salt generation. This is useful
 //! for reproducible testing or when a specific entropy source is required.
+//! [`SigningKey`] also implements `signature::RandomizedSigner` for generic
+//! code that passes the RNG through the `signature` traits.
 //!
 //! ```
 //! use pq_mayo::{KeyPair, Mayo1};
-//! use signature::Verifier;
 //!
 //! let mut rng = rand::rng();
 //! let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen");
@@ -145,6 +201,9 @@
 //! keypair.verifying_key().verify(b"data", &sig).expect("verify");
 //! ```
 //!
+//! [`SigningKey::sign_deterministic`] instead uses an all-zero randomizer, so
+//! the same key and message always produce the same signature.
+//!
 //! # Faster Repeated Signing
 //!
 //! [`ExpandedSigningKey`] caches secret-derived signing material for repeated
@@ -153,16 +212,26 @@
 //!
 //! ```
 //! use pq_mayo::{ExpandedSigningKey, KeyPair, Mayo1};
-//! use signature::{Signer, Verifier};
 //!
 //! let mut rng = rand::rng();
 //! let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen");
 //! let expanded = ExpandedSigningKey::<Mayo1>::from(keypair.signing_key());
 //!
-//! let sig = expanded.try_sign(b"message").expect("sign");
+//! let sig = expanded.sign_with_rng(&mut rng, b"message").expect("sign");
 //! keypair.verifying_key().verify(b"message", &sig).expect("verify");
 //! ```
 //!
+//! For high-throughput signing, a [`SignScratch`] additionally reuses the
+//! signing working buffers across calls via
+//! [`ExpandedSigningKey::sign_with_scratch`]. It is zeroized after every
+//! signature; keep one per thread. [`SigningKey::sign_iter`] combines the two
+//! to sign a stream of messages lazily.
+//!
+//! To sign one message under many keys, hash it once with
+//! [`message_digest`] and pass the digest to
+//! [`SigningKey::sign_with_precomputed_digest`]; the signatures verify
+//! against the message as usual.
+//!
 //! # Faster Repeated Verification
 //!
 //! [`ExpandedVerifyingKey`] caches expanded public key material for repeated
@@ -171,12 +240,11 @@
 //!
 //! ```
 //! use pq_mayo::{ExpandedVerifyingKey, KeyPair, Mayo1, VerificationContext};
-//! use signature::{Signer, Verifier};
 //!
 //! let mut rng = rand::rng();
 //! let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen");
 //! let msg = b"message";
-//! let sig = keypair.signing_key().try_sign(msg).expect("sign");
+//! let sig = keypair.signing_key().sign_with_rng(&mut rng, msg).expect("sign");
 //!
 //! let expanded = ExpandedVerifyingKey::<Mayo1>::from(keypair.verifying_key());
 //! expanded.verify(msg, &sig).expect("verify");
@@ -185,6 +253,68 @@
 //! context.verify(msg, &sig).expect("verify with cached scratch");
 //! ```
 //!
+//! # Context Strings
+//!
+//! [`SigningKey::sign_with_context`] binds a signature to a context string of
+//! up to 255 bytes by signing `len(ctx) || ctx || msg`; check it with
+//! [`VerifyingKey::verify_with_context`]. This is the plain scheme applied to
+//! that concatenation, so the MAYO reference implementation verifies it when
+//! given the same bytes as its message.
+//!
+//! [`SigningKey::sign_with_aad`] binds associated data, such as a protocol
+//! header, by signing `len(aad) || aad || msg` with an 8-byte length; check
+//! it with [`VerifyingKey::verify_with_aad`]. With both, use
+//! [`SigningKey::sign_with_context_and_aad`]. Each of these modes frames its
+//! input under a domain tag of its own, so a signature from one never
+//! verifies in another, nor as a context signature. The two tags are
+//! reserved, and using one as a context returns [`Error::ReservedContext`].
+//!
+//! # Streaming Large Messages
+//!
+//! With the `std` feature, `SigningKey::sign_reader` and
+//! `VerifyingKey::verify_reader` take the message as a `std::io::Read`
+//! and hash it in fixed-size chunks, so a large file is
+//! never loaded whole. The signatures are the ordinary ones: a file signed
+//! with `sign_reader` verifies with [`VerifyingKey::verify`] over its
+//! contents, and the other way round.
+//!
+//! ```toml
+//! [dependencies]
+//! pq-mayo = { version = "0.5", features = ["std"] }
+//! ```
+//!
+//! Message length is unbounded in every API: the message is only absorbed
+//! into SHAKE256, and nothing is sized from its length. The one exception is
+//! context signing, which copies `len(ctx) || ctx || msg` once, so it needs
+//! memory for a second copy of the message.
+//!
+//! # Constant-Time Verification Results
+//!
+//! With the `subtle` feature, `VerifyingKey::verify_ct` returns a
+//! `subtle::Choice` instead of a `Result`, so a protocol that checks several
+//! conditions can combine them with `&` and branch once on the total.
+//!
+//! ```toml
+//! [dependencies]
+//! pq-mayo = { version = "0.5", features = ["subtle"] }
+//! ```
+//!
+//! # Signature Envelopes
+//!
+//! [`envelope::seal`] wraps a detached signature in a short header naming its
+//! parameter set, and [`envelope::open`] checks that header before verifying,
+//! so a signature file made with one parameter set cannot be fed to a verifier
+//! for another.
+//!
+//! ```
+//! use pq_mayo::{KeyPair, Mayo1, envelope};
+//!
+//! let mut rng = rand::rng();
+//! let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen");
+//! let sealed = envelope::seal(keypair.signing_key(), &mut rng, b"file contents").expect("seal");
+//! envelope::open(keypair.verifying_key(), &sealed, b"file contents").expect("open");
+//! ```
+//!
 //! # Error Handling
 //!
 //! All fallible operations return [`error::Result<T>`](error::Result), which
@@ -234,6 +364,99 @@
 //! assert_eq!(keypair, restored);
 //! ```
 //!
+//! A `KeyPair` serializes as its secret seed, the same as its `SigningKey`;
+//! the verifying key is derived again on deserialization rather than stored.
+//!
+//! A plain `SigningKey` serializes as its seed alone, and Mayo1 and Mayo2
+//! seeds have the same length. Wrap the key in `TaggedSigningKey` to record
+//! the parameter set name alongside the seed, so that loading it as the wrong
+//! parameter set fails.
+//!
+//! # Hybrid Signatures
+//!
+//! Enable the `hybrid` feature for [`HybridSigningKey`], which pairs a MAYO key
+//! with an Ed25519 key. A [`HybridSignature`] verifies only if both components
+//! verify; its docs describe the exact wire format.
+//!
+//! ```toml
+//! [dependencies]
+//! pq-mayo = { version = "0.5", features = ["hybrid"] }
+//! ```
+//!
+//! # COSE
+//!
+//! Enable the `cose` feature (which implies `pkcs8`) to encode verifying keys
+//! as COSE_Key with [`VerifyingKey::to_cose_key`] and to produce and check
+//! COSE_Sign1 messages with [`SigningKey::sign_cose_sign1`] and
+//! [`VerifyingKey::verify_cose_sign1`]. The `alg` value is the text form of
+//! the parameter set's experimental OID.
+//!
+//! ```toml
+//! [dependencies]
+//! pq-mayo = { version = "0.5", features = ["cose"] }
+//! ```
+//!
+//! # JWK
+//!
+//! Enable the `jwk` feature to exchange verifying keys as JSON Web Keys with
+//! [`VerifyingKey::to_jwk`] and [`VerifyingKey::from_jwk`]. Keys use the `AKP`
+//! key type with `alg` set to the parameter set name, e.g. `"MAYO-1"`.
+//!
+//! ```toml
+//! [dependencies]
+//! pq-mayo = { version = "0.5", features = ["jwk"] }
+//! ```
+//!
+//! # GF(16) Internals
+//!
+//! Enable the `gf16-internals` feature to expose the [`gf16`] module: scalar
+//! GF(16) arithmetic, a small dense [`gf16::Matrix`] type and the constant-time
+//! `gf16::row_echelon_form`, for tooling such as KAT generators and
+//! cross-checkers. These are low-level building blocks
+//! and are **not** covered by SemVer while the feature is experimental.
+//!
+//! ```toml
+//! [dependencies]
+//! pq-mayo = { version = "0.5", features = ["gf16-internals"] }
+//! ```
+//!
+//! # Internals
+//!
+//! Enable the `internals` feature to expose the `internals` module, which
+//! returns intermediate values such as the expanded P1 and P2 matrices for
+//! byte-comparison against the reference implementation, and signs and
+//! verifies with a message digest of the caller's choosing through its
+//! `Digester` trait. Like the GF(16) internals it is exempt from SemVer.
+//!
+//! ```toml
+//! [dependencies]
+//! pq-mayo = { version = "0.5", features = ["internals"] }
+//! ```
+//!
+//! # Known Answer Tests
+//!
+//! Enable the `kat` feature to expose the `kat` module, which parses NIST
+//! `.rsp` response files and replays their vectors, for checking this crate
+//! against vendored KAT files:
+//!
+//! ```toml
+//! [dependencies]
+//! pq-mayo = { version = "0.5", features = ["kat"] }
+//! ```
+//!
+//! # Test Utilities
+//!
+//! Enable the `test-utils` feature in `[dev-dependencies]` to get the
+//! `mayo_test_suite!` macro, which generates sign/verify, tampering, serde and
+//! length-validation tests for a parameter set, so crates built on MAYO can
+//! run the same conformance checks as this one. The checks themselves are in
+//! the `test_utils` module.
+//!
+//! ```toml
+//! [dev-dependencies]
+//! pq-mayo = { version = "0.5", features = ["test-utils"] }
+//! ```
+//!
 //! # WebAssembly Support
 //!
 //! This crate compiles to `wasm32-unknown-unknown` using pure Rust
@@ -245,6 +468,10 @@
 //! pq-mayo = { version = "0.5", features = ["js"] }
 //! ```
 //!
+//! Without `js`, the `Signer` impls have no randomness source. Use
+//! [`SigningKey::sign_with_entropy`] with `SALT_BYTES` of host-supplied
+//! randomness instead, or [`SigningKey::sign_with_rng`] with your own RNG.
+//!
 //! # Security Considerations
 //!
 //! - All operations are implemented in **constant time** to resist
@@ -254,6 +481,24 @@
 //!   crate to prevent secret material from lingering in memory.
 //! - The [`Debug`](core::fmt::Debug) implementation for [`SigningKey`] redacts
 //!   the key bytes, printing `**FILTERED**` instead.
+//! - The number of sampling attempts a signature takes depends on secret
+//!   values, and signing stops at the first success, so its running time
+//!   varies. This is inherent to MAYO and shared with the reference
+//!   implementation. [`SigningKey::sign_fixed_time`] always runs every
+//!   attempt, for deployments that need uniform timing at a large cost in
+//!   throughput.
+//! - As a fault-attack countermeasure, signing verifies every signature
+//!   against the public map recomputed from the expanded secret key before
+//!   releasing it. [`SigningKey::sign_with_verify`] also checks it against a
+//!   public key derived from the seed in a separate pass, which additionally
+//!   catches faults in the secret key expansion itself.
+//! - P1 and P2 are expanded from the public seed with AES-128, using the
+//!   [`aes`](https://docs.rs/aes) crate. It picks AES-NI or ARMv8 AES when the
+//!   CPU has them and otherwise falls back to a bitsliced, constant-time
+//!   software implementation. Build with `RUSTFLAGS='--cfg aes_backend="soft"'`
+//!   to always use the software backend, at a noticeable cost in key
+//!   generation, expansion and verification speed. Only public data passes
+//!   through AES, so this is a matter of policy rather than of key secrecy.
 //!
 //! # PKCS#8 and SPKI Support
 //!
@@ -261,6 +506,9 @@
 //! with X.509 and PKCS#8 standards. This implements the [`EncodePrivateKey`],
 //! [`DecodePrivateKey`], [`EncodePublicKey`], and [`DecodePublicKey`] traits
 //! from the [`pkcs8`](https://docs.rs/pkcs8) ecosystem.
+//! `KeyPair::to_pkcs8_der_with_public_key` also stores the public key in the
+//! PKCS#8 v2 `publicKey` field, for consumers that read it directly. On
+//! loading, it must match the key derived from the seed.
 //!
 //! Since MAYO has not yet been standardized by NIST, experimental OIDs
 //! from the [Open Quantum Safe](https://openquantumsafe.org/) project are
@@ -275,42 +523,150 @@
 //! [`DecodePrivateKey`]: https://docs.rs/pkcs8/latest/pkcs8/trait.DecodePrivateKey.html
 //! [`EncodePublicKey`]: https://docs.rs/spki/latest/spki/trait.EncodePublicKey.html
 //! [`DecodePublicKey`]: https://docs.rs/spki/latest/spki/trait.DecodePublicKey.html
+//!
+// Links to feature-gated items: intra-doc links when the feature is on,
+// docs.rs otherwise.
+#![cfg_attr(
+    feature = "rayon",
+    doc = "[`KeyPair::generate_batch`]: KeyPair::generate_batch"
+)]
+#![cfg_attr(
+    not(feature = "rayon"),
+    doc = "[`KeyPair::generate_batch`]: https://docs.rs/pq-mayo/latest/pq_mayo/struct.KeyPair.html#method.generate_batch"
+)]
+#![cfg_attr(feature = "hybrid", doc = "[`HybridSigningKey`]: HybridSigningKey")]
+#![cfg_attr(
+    not(feature = "hybrid"),
+    doc = "[`HybridSigningKey`]: https://docs.rs/pq-mayo/latest/pq_mayo/struct.HybridSigningKey.html"
+)]
+#![cfg_attr(feature = "hybrid", doc = "[`HybridSignature`]: HybridSignature")]
+#![cfg_attr(
+    not(feature = "hybrid"),
+    doc = "[`HybridSignature`]: https://docs.rs/pq-mayo/latest/pq_mayo/struct.HybridSignature.html"
+)]
+#![cfg_attr(
+    feature = "cose",
+    doc = "[`VerifyingKey::to_cose_key`]: VerifyingKey::to_cose_key"
+)]
+#![cfg_attr(
+    not(feature = "cose"),
+    doc = "[`VerifyingKey::to_cose_key`]: https://docs.rs/pq-mayo/latest/pq_mayo/struct.VerifyingKey.html#method.to_cose_key"
+)]
+#![cfg_attr(
+    feature = "cose",
+    doc = "[`SigningKey::sign_cose_sign1`]: SigningKey::sign_cose_sign1"
+)]
+#![cfg_attr(
+    not(feature = "cose"),
+    doc = "[`SigningKey::sign_cose_sign1`]: https://docs.rs/pq-mayo/latest/pq_mayo/struct.SigningKey.html#method.sign_cose_sign1"
+)]
+#![cfg_attr(
+    feature = "cose",
+    doc = "[`VerifyingKey::verify_cose_sign1`]: VerifyingKey::verify_cose_sign1"
+)]
+#![cfg_attr(
+    not(feature = "cose"),
+    doc = "[`VerifyingKey::verify_cose_sign1`]: https://docs.rs/pq-mayo/latest/pq_mayo/struct.VerifyingKey.html#method.verify_cose_sign1"
+)]
+#![cfg_attr(
+    feature = "jwk",
+    doc = "[`VerifyingKey::to_jwk`]: VerifyingKey::to_jwk"
+)]
+#![cfg_attr(
+    not(feature = "jwk"),
+    doc = "[`VerifyingKey::to_jwk`]: https://docs.rs/pq-mayo/latest/pq_mayo/struct.VerifyingKey.html#method.to_jwk"
+)]
+#![cfg_attr(
+    feature = "jwk",
+    doc = "[`VerifyingKey::from_jwk`]: VerifyingKey::from_jwk"
+)]
+#![cfg_attr(
+    not(feature = "jwk"),
+    doc = "[`VerifyingKey::from_jwk`]: https://docs.rs/pq-mayo/latest/pq_mayo/struct.VerifyingKey.html#method.from_jwk"
+)]
+#![cfg_attr(feature = "gf16-internals", doc = "[`gf16`]: gf16")]
+#![cfg_attr(
+    not(feature = "gf16-internals"),
+    doc = "[`gf16`]: https://docs.rs/pq-mayo/latest/pq_mayo/gf16/index.html"
+)]
+#![cfg_attr(feature = "gf16-internals", doc = "[`gf16::Matrix`]: gf16::Matrix")]
+#![cfg_attr(
+    not(feature = "gf16-internals"),
+    doc = "[`gf16::Matrix`]: https://docs.rs/pq-mayo/latest/pq_mayo/gf16/struct.Matrix.html"
+)]
 
+mod context;
+pub mod envelope;
 mod error;
+#[cfg(feature = "hybrid")]
+mod hybrid;
+#[cfg(feature = "internals")]
+pub mod internals;
+#[cfg(feature = "jwk")]
+mod jwk;
+#[cfg(feature = "kat")]
+pub mod kat;
 mod keypair;
 mod mayo_signature;
 mod params;
 #[cfg(feature = "pkcs8")]
 mod pkcs8;
+mod seed;
 mod signing_key;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
 mod verifying_key;
+mod workspace;
 
 mod bitsliced;
 mod codec;
+#[cfg(feature = "cose")]
+mod cose;
+mod digester;
 mod echelon;
+#[cfg(feature = "gf16-internals")]
+pub mod gf16;
+#[cfg(not(feature = "gf16-internals"))]
 mod gf16;
 mod keygen;
+mod keystream;
 mod matrix_ops;
 mod sample;
 mod sign;
 mod verify;
+mod xof;
 
-pub use error::{Error, Result};
+pub use digester::{MessageDigest, message_digest};
+pub use error::{Error, Result, VerifyDetail};
+#[cfg(feature = "hybrid")]
+pub use hybrid::{HybridSignature, HybridSigningKey, HybridVerifyingKey};
 pub use keypair::KeyPair;
-pub use mayo_signature::Signature;
-pub use params::{Mayo1, Mayo2, Mayo3, Mayo5, MayoParameter};
-pub use signing_key::{ExpandedSigningKey, SigningKey};
-pub use verifying_key::{ExpandedVerifyingKey, VerificationContext, VerifyingKey};
+pub use mayo_signature::{InlineSignature, Signature};
+pub use params::{MAX_SIG_BYTES, Mayo1, Mayo2, Mayo3, Mayo5, MayoParameter, ParamInfo};
+pub use seed::Seed;
+pub use sign::SignScratch;
+#[cfg(feature = "serde")]
+pub use signing_key::TaggedSigningKey;
+pub use signing_key::{ExpandedSigningKey, SigningKey, SolveStats};
+#[cfg(feature = "rayon")]
+pub use verifying_key::verify_many;
+pub use verifying_key::{
+    ExpandedVerifyingKey, VerificationContext, VerifyingKey, verify_any, verify_any_expanded,
+    verify_raw, verify_with_expanded,
+};
+pub use workspace::{Mayo1Workspace, Mayo2Workspace, Mayo3Workspace, Mayo5Workspace, Workspace};
 
 #[cfg(feature = "pkcs8")]
-pub use crate::pkcs8::{MAYO1_OID, MAYO2_OID, MAYO3_OID, MAYO5_OID};
+pub use crate::pkcs8::{
+    MAYO1_OID, MAYO2_OID, MAYO3_OID, MAYO5_OID, cpk_bytes_for_oid, csk_bytes_for_oid,
+    sig_bytes_for_oid,
+};
 
 #[cfg(feature = "serde")]
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde::de::DeserializeOwned;
-    use signature::Signer;
 
     #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
     #[serde(bound(
@@ -375,10 +731,80 @@ mod tests {
         let mut rng = rand::rng();
         let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen");
         let msg = b"hello world";
-        let sig = keypair.signing_key().try_sign(msg).expect("sign");
+        let sig = keypair
+            .signing_key()
+            .sign_with_rng(&mut rand::rng(), msg)
+            .expect("sign");
         assert_serde_formats(sig);
     }
 
+    fn tagged_signing_key_serde<P: MayoParameter>() {
+        let mut rng = rand::rng();
+        let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen");
+        assert_serde_formats(TaggedSigningKey::from(keypair.signing_key().clone()));
+    }
+
+    fn keypair_serializes_as_seed<P: MayoParameter>() {
+        let mut rng = rand::rng();
+        let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen");
+
+        let json = serde_json::to_string(&keypair).expect("serialize");
+        assert_eq!(
+            json,
+            serde_json::to_string(keypair.signing_key()).expect("serialize")
+        );
+        let restored: KeyPair<P> = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(restored, keypair);
+        assert_eq!(restored.verifying_key(), keypair.verifying_key());
+
+        // Both keys side by side, as a struct of the two would serialize.
+        let both = (keypair.signing_key(), keypair.verifying_key());
+        let compact = postcard::to_stdvec(&keypair).expect("serialize");
+        let full = postcard::to_stdvec(&both).expect("serialize");
+        assert_eq!(compact.len(), 1 + P::CSK_BYTES);
+        assert!(compact.len() < full.len() - P::CPK_BYTES);
+        let restored: KeyPair<P> = postcard::from_bytes(&compact).expect("deserialize");
+        assert_eq!(restored, keypair);
+    }
+
+    #[test]
+    fn keypair_serializes_as_seed_all_params() {
+        keypair_serializes_as_seed::<Mayo1>();
+        keypair_serializes_as_seed::<Mayo2>();
+        keypair_serializes_as_seed::<Mayo3>();
+        keypair_serializes_as_seed::<Mayo5>();
+    }
+
+    #[test]
+    fn tagged_signing_key_serde_all_params() {
+        tagged_signing_key_serde::<Mayo1>();
+        tagged_signing_key_serde::<Mayo2>();
+        tagged_signing_key_serde::<Mayo3>();
+        tagged_signing_key_serde::<Mayo5>();
+    }
+
+    #[test]
+    fn tagged_signing_key_rejects_other_parameter_set() {
+        let mut rng = rand::rng();
+        let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen");
+
+        // The untagged seed is accepted as a Mayo2 key since the lengths match.
+        let plain = serde_json::to_string(keypair.signing_key()).expect("serialize");
+        assert!(serde_json::from_str::<SigningKey<Mayo2>>(&plain).is_ok());
+
+        let tagged = TaggedSigningKey::from(keypair.signing_key().clone());
+        let json = serde_json::to_string(&tagged).expect("serialize");
+        let err = serde_json::from_str::<TaggedSigningKey<Mayo2>>(&json)
+            .expect_err("Mayo1 key loaded as Mayo2");
+        assert!(err.to_string().contains("parameter set mismatch"));
+        assert!(serde_json::from_str::<TaggedSigningKey<Mayo3>>(&json).is_err());
+
+        let bytes = postcard::to_stdvec(&tagged).expect("serialize");
+        assert!(postcard::from_bytes::<TaggedSigningKey<Mayo2>>(&bytes).is_err());
+        let restored: TaggedSigningKey<Mayo1> = postcard::from_bytes(&bytes).expect("deserialize");
+        assert_eq!(restored.into_signing_key(), *keypair.signing_key());
+    }
+
     #[test]
     fn keypair_serde_mayo1() {
         keypair_serde::<Mayo1>();
@@ -3,27 +3,23 @@
 //! Matrix operations on bitsliced m-vectors for MAYO.
 
 use crate::bitsliced::{m_vec_add, m_vec_multiply_bins};
-use crate::params::{MAX_M_VEC_LIMBS, MayoParameter};
+use crate::codec::unpack_m_vecs;
+use crate::gf16::mul_f;
+use crate::params::{F_TAIL_LEN, MAX_M_VEC_LIMBS, MAX_ROW_VECS, MayoParameter};
 
-/// Scratch space for [`m_calculate_ps_sps_with_scratch`].
-pub(crate) struct PsSpsScratch {
-    ps: Vec<u64>,
-    accumulator: Vec<u64>,
-    sps_accumulator: Vec<u64>,
-}
-
-impl PsSpsScratch {
-    pub(crate) fn new<P: MayoParameter>() -> Self {
-        let m_vec_limbs = P::M_VEC_LIMBS;
-        let k = P::K;
-        let n = P::N;
+/// Bins for one output row: 16 per m-vector, at most [`MAX_ROW_VECS`]
+/// m-vectors per row.
+const MAX_ROW_BINS: usize = 16 * MAX_M_VEC_LIMBS * MAX_ROW_VECS;
 
-        Self {
-            ps: vec![0u64; n * k * m_vec_limbs],
-            accumulator: vec![0u64; 16 * m_vec_limbs * k * n],
-            sps_accumulator: vec![0u64; 16 * m_vec_limbs * k * k],
-        }
-    }
+/// The upper-triangular P3 block consumed by [`m_calculate_ps_sps`].
+#[derive(Clone, Copy)]
+pub(crate) enum P3Input<'a> {
+    /// Already unpacked into bitsliced m-vectors.
+    Unpacked(&'a [u64]),
+    /// Still in packed public-key form; each m-vector is unpacked on the fly
+    /// as it is folded into the accumulator, so a one-shot verify never
+    /// materializes the whole of P3.
+    Packed(&'a [u8]),
 }
 
 /// Bin-accumulator multiply: `mat` (`mat_rows x mat_cols` plain GF(16) bytes)
@@ -34,7 +30,7 @@ impl PsSpsScratch {
 /// m-vector is XOR-accumulated into the bin selected by its scalar; a single
 /// `m_vec_multiply_bins` per output then folds the 16 bins. This trades the
 /// per-element multiply for a cheap XOR plus one deferred fold per output — the
-/// same structure used in verification's `m_calculate_ps_sps_with_scratch`.
+/// same structure used in verification's `m_calculate_ps_sps`.
 fn bins_mat_x_m_mat<P: MayoParameter>(
     mat: &[u8],
     bs_mat: &[u64],
@@ -44,26 +40,22 @@ fn bins_mat_x_m_mat<P: MayoParameter>(
     bs_mat_cols: usize,
 ) {
     // `mvl` is a compile-time constant per monomorphization, so the inner
-    // m_vec_add / m_vec_multiply_bins loops fully unroll. A fresh `vec![0; …]`
-    // per call is intentional: `alloc_zeroed` yields lazily-zeroed OS pages,
-    // which benchmarked faster than reusing a buffer with an explicit `fill(0)`.
+    // m_vec_add / m_vec_multiply_bins loops fully unroll.
     let mvl = P::M_VEC_LIMBS;
-    let mut bins = vec![0u64; mat_rows * bs_mat_cols * 16 * mvl];
+    let mut bins = [0u64; MAX_ROW_BINS];
+    let bins = &mut bins[..bs_mat_cols * 16 * mvl];
     for r in 0..mat_rows {
-        let bins_row = r * bs_mat_cols * 16 * mvl;
+        bins.fill(0);
         let mat_row = &mat[r * mat_cols..(r + 1) * mat_cols];
         for (c, &scalar) in mat_row.iter().enumerate() {
             let src_row = c * bs_mat_cols * mvl;
             for k in 0..bs_mat_cols {
-                let bin_idx = bins_row + (k * 16 + usize::from(scalar)) * mvl;
+                let bin_idx = (k * 16 + usize::from(scalar)) * mvl;
                 let src = &bs_mat[src_row + k * mvl..src_row + (k + 1) * mvl];
                 m_vec_add(src, &mut bins[bin_idx..bin_idx + mvl], mvl);
             }
         }
-    }
-    for i in 0..mat_rows * bs_mat_cols {
-        let base = i * 16 * mvl;
-        m_vec_multiply_bins(&mut bins[base..], &mut acc[i * mvl..(i + 1) * mvl], mvl);
+        fold_row_bins(bins, &mut acc[r * bs_mat_cols * mvl..], bs_mat_cols, mvl);
     }
 }
 
@@ -80,23 +72,21 @@ fn bins_upper_tri_mat_x_mat_trans<P: MayoParameter>(
 ) {
     let mvl = P::M_VEC_LIMBS;
     let cols = rows;
-    let mut bins = vec![0u64; rows * mat_rows * 16 * mvl];
+    let mut bins = [0u64; MAX_ROW_BINS];
+    let bins = &mut bins[..mat_rows * 16 * mvl];
     let mut used = 0;
     for r in 0..rows {
-        let bins_row = r * mat_rows * 16 * mvl;
+        bins.fill(0);
         for c in r..cols {
             let src = &bs_mat[used * mvl..(used + 1) * mvl];
             for k in 0..mat_rows {
                 let scalar = mat[k * cols + c];
-                let bin_idx = bins_row + (k * 16 + usize::from(scalar)) * mvl;
+                let bin_idx = (k * 16 + usize::from(scalar)) * mvl;
                 m_vec_add(src, &mut bins[bin_idx..bin_idx + mvl], mvl);
             }
             used += 1;
         }
-    }
-    for i in 0..rows * mat_rows {
-        let base = i * 16 * mvl;
-        m_vec_multiply_bins(&mut bins[base..], &mut acc[i * mvl..(i + 1) * mvl], mvl);
+        fold_row_bins(bins, &mut acc[r * mat_rows * mvl..], mat_rows, mvl);
     }
 }
 
@@ -111,22 +101,20 @@ pub(crate) fn mul_add_mat_trans_x_m_mat<P: MayoParameter>(
     bs_mat_cols: usize,
 ) {
     let mvl = P::M_VEC_LIMBS;
-    let mut bins = vec![0u64; mat_cols * bs_mat_cols * 16 * mvl];
+    let mut bins = [0u64; MAX_ROW_BINS];
+    let bins = &mut bins[..bs_mat_cols * 16 * mvl];
     for r in 0..mat_cols {
-        let bins_row = r * bs_mat_cols * 16 * mvl;
+        bins.fill(0);
         for c in 0..mat_rows {
             let scalar = mat[c * mat_cols + r];
             let src_row = c * bs_mat_cols * mvl;
             for k in 0..bs_mat_cols {
-                let bin = bins_row + (k * 16 + usize::from(scalar)) * mvl;
+                let bin = (k * 16 + usize::from(scalar)) * mvl;
                 let src = &bs_mat[src_row + k * mvl..src_row + (k + 1) * mvl];
                 m_vec_add(src, &mut bins[bin..bin + mvl], mvl);
             }
         }
-    }
-    for i in 0..mat_cols * bs_mat_cols {
-        let base = i * 16 * mvl;
-        m_vec_multiply_bins(&mut bins[base..], &mut acc[i * mvl..(i + 1) * mvl], mvl);
+        fold_row_bins(bins, &mut acc[r * bs_mat_cols * mvl..], bs_mat_cols, mvl);
     }
 }
 
@@ -140,28 +128,20 @@ pub(crate) fn p1_times_o<P: MayoParameter>(p1: &[u64], o: &[u8], acc: &mut [u64]
     let param_v = P::V;
     let param_o = P::O;
 
-    let mut bins = vec![0u64; param_v * param_o * 16 * m_vec_limbs];
+    let mut bins = [0u64; MAX_ROW_BINS];
+    let bins = &mut bins[..param_o * 16 * m_vec_limbs];
     let mut used = 0;
     for r in 0..param_v {
+        bins.fill(0);
         for c in r..param_v {
             let src = &p1[used * m_vec_limbs..(used + 1) * m_vec_limbs];
-            let o_c_offset = c * param_o;
-            for k in 0..param_o {
-                let bin = (r * param_o + k) * 16 * m_vec_limbs
-                    + usize::from(o[o_c_offset + k]) * m_vec_limbs;
-                m_vec_add(src, &mut bins[bin..bin + m_vec_limbs], m_vec_limbs);
-            }
+            add_to_bins(bins, src, &o[c * param_o..(c + 1) * param_o], m_vec_limbs);
             used += 1;
         }
-    }
-
-    let mut tmp = [0u64; MAX_M_VEC_LIMBS];
-    for i in 0..param_v * param_o {
-        let base = i * 16 * m_vec_limbs;
-        m_vec_multiply_bins(&mut bins[base..], &mut tmp[..m_vec_limbs], m_vec_limbs);
-        m_vec_add(
-            &tmp[..m_vec_limbs],
-            &mut acc[i * m_vec_limbs..(i + 1) * m_vec_limbs],
+        fold_row_bins_add(
+            bins,
+            &mut acc[r * param_o * m_vec_limbs..],
+            param_o,
             m_vec_limbs,
         );
     }
@@ -169,52 +149,71 @@ pub(crate) fn p1_times_o<P: MayoParameter>(p1: &[u64], o: &[u8], acc: &mut [u64]
 
 /// Compute (P1 + P1^t) * O and add to acc (which already contains P2).
 ///
-/// This computes L = (P1 + P1^t) * O + P2 by skipping diagonal entries
-/// and adding both (r,c) and (c,r) contributions.
+/// This computes L = (P1 + P1^t) * O + P2 one output row at a time: row `r`
+/// collects `P1[c, r]` from the rows above and `P1[r, c]` from its own row,
+/// skipping the diagonal, which cancels in `P1 + P1^t`.
 pub(crate) fn p1p1t_times_o<P: MayoParameter>(p1: &[u64], o: &[u8], acc: &mut [u64]) {
     let m_vec_limbs = P::M_VEC_LIMBS;
     let param_o = P::O;
     let param_v = P::V;
 
-    let mut bins = vec![0u64; param_v * param_o * 16 * m_vec_limbs];
-    let mut bs_mat_entries_used = 0;
+    let mut bins = [0u64; MAX_ROW_BINS];
+    let bins = &mut bins[..param_o * 16 * m_vec_limbs];
     for r in 0..param_v {
-        for c in r..param_v {
-            if c == r {
-                bs_mat_entries_used += 1;
-                continue;
-            }
-            let src_offset = m_vec_limbs * bs_mat_entries_used;
-            let src = &p1[src_offset..src_offset + m_vec_limbs];
-            let o_c_offset = c * param_o;
-            let o_r_offset = r * param_o;
-            for k in 0..param_o {
-                // P1[r,c] * O[c,k] -> (r,k)
-                let b1 = (r * param_o + k) * 16 * m_vec_limbs
-                    + usize::from(o[o_c_offset + k]) * m_vec_limbs;
-                m_vec_add(src, &mut bins[b1..b1 + m_vec_limbs], m_vec_limbs);
-                // P1[r,c] * O[r,k] -> (c,k) (transpose contribution)
-                let b2 = (c * param_o + k) * 16 * m_vec_limbs
-                    + usize::from(o[o_r_offset + k]) * m_vec_limbs;
-                m_vec_add(src, &mut bins[b2..b2 + m_vec_limbs], m_vec_limbs);
-            }
-            bs_mat_entries_used += 1;
+        bins.fill(0);
+        // `diag` walks the index of P1[c, c]; P1[c, r] sits `r - c` after it.
+        let mut diag = 0;
+        for c in 0..r {
+            let src = &p1[(diag + r - c) * m_vec_limbs..(diag + r - c + 1) * m_vec_limbs];
+            add_to_bins(bins, src, &o[c * param_o..(c + 1) * param_o], m_vec_limbs);
+            diag += param_v - c;
+        }
+        for c in r + 1..param_v {
+            let src = &p1[(diag + c - r) * m_vec_limbs..(diag + c - r + 1) * m_vec_limbs];
+            add_to_bins(bins, src, &o[c * param_o..(c + 1) * param_o], m_vec_limbs);
         }
+        fold_row_bins_add(
+            bins,
+            &mut acc[r * param_o * m_vec_limbs..],
+            param_o,
+            m_vec_limbs,
+        );
     }
+}
 
-    // Fold each output's 16 bins and add onto acc, which already holds P2.
-    let mut tmp = [0u64; MAX_M_VEC_LIMBS];
-    for i in 0..param_v * param_o {
-        let base = i * 16 * m_vec_limbs;
-        m_vec_multiply_bins(&mut bins[base..], &mut tmp[..m_vec_limbs], m_vec_limbs);
-        m_vec_add(
-            &tmp[..m_vec_limbs],
-            &mut acc[i * m_vec_limbs..(i + 1) * m_vec_limbs],
-            m_vec_limbs,
+/// Add `src` into bin `row[k]` of output `k` for every `k`, i.e. accumulate
+/// `src * row` one bin group per entry of `row`.
+#[inline(always)]
+fn add_to_bins(bins: &mut [u64], src: &[u64], row: &[u8], mvl: usize) {
+    for (k, &scalar) in row.iter().enumerate() {
+        let bin = (k * 16 + usize::from(scalar)) * mvl;
+        m_vec_add(src, &mut bins[bin..bin + mvl], mvl);
+    }
+}
+
+/// Fold the first `vecs` groups of 16 bins into `vecs` m-vectors at the
+/// start of `out`, overwriting them.
+#[inline]
+fn fold_row_bins(bins: &mut [u64], out: &mut [u64], vecs: usize, mvl: usize) {
+    for i in 0..vecs {
+        m_vec_multiply_bins(
+            &mut bins[i * 16 * mvl..],
+            &mut out[i * mvl..(i + 1) * mvl],
+            mvl,
         );
     }
 }
 
+/// [`fold_row_bins`], adding onto `out` instead of overwriting it.
+#[inline]
+fn fold_row_bins_add(bins: &mut [u64], out: &mut [u64], vecs: usize, mvl: usize) {
+    let mut tmp = [0u64; MAX_M_VEC_LIMBS];
+    for i in 0..vecs {
+        m_vec_multiply_bins(&mut bins[i * 16 * mvl..], &mut tmp[..mvl], mvl);
+        m_vec_add(&tmp[..mvl], &mut out[i * mvl..(i + 1) * mvl], mvl);
+    }
+}
+
 /// Compute M matrices (V^t * L) and v^t * P1 * v (VPV).
 ///
 /// `pv` is a caller-provided scratch buffer of length `V * K * M_VEC_LIMBS`.
@@ -276,13 +275,20 @@ pub(crate) fn m_upper(m_vec_limbs: usize, input: &[u64], output: &mut [u64], siz
 }
 
 /// Compute P * S^t and then S * P * S^t (the SPS matrix for verification).
-pub(crate) fn m_calculate_ps_sps_with_scratch<P: MayoParameter>(
+///
+/// `ps` is working space for the `n x k` m-vectors of `P * S^t`.
+///
+/// Zero entries of `s` still add into bin 0, which [`m_vec_multiply_bins`]
+/// never reads. Skipping them would be sound here, since `s` is the public
+/// signature, but the data-dependent branch mispredicts about once in sixteen
+/// adds and made verify slower rather than faster, so every add is kept.
+pub(crate) fn m_calculate_ps_sps<P: MayoParameter>(
     p1: &[u64],
     p2: &[u64],
-    p3: &[u64],
+    p3: P3Input<'_>,
     s: &[u8],
     sps: &mut [u64],
-    scratch: &mut PsSpsScratch,
+    ps: &mut [u64],
 ) {
     let v = P::V;
     let o = P::O;
@@ -290,35 +296,21 @@ pub(crate) fn m_calculate_ps_sps_with_scratch<P: MayoParameter>(
     let n = P::N;
     let m_vec_limbs = P::M_VEC_LIMBS;
 
-    // Compute PS using bins accumulator
+    // Compute PS one row at a time using a bins accumulator
     let ps_len = n * k * m_vec_limbs;
-    let acc_len = 16 * m_vec_limbs * k * n;
-    let sps_acc_len = 16 * m_vec_limbs * k * k;
-    debug_assert!(scratch.ps.len() >= ps_len);
-    debug_assert!(scratch.accumulator.len() >= acc_len);
-    debug_assert!(scratch.sps_accumulator.len() >= sps_acc_len);
-
-    let ps = &mut scratch.ps[..ps_len];
-    let accumulator = &mut scratch.accumulator[..acc_len];
-    let sps_accumulator = &mut scratch.sps_accumulator[..sps_acc_len];
-
-    ps.fill(0);
-    accumulator.fill(0);
-    sps_accumulator.fill(0);
+    debug_assert!(ps.len() >= ps_len);
+    let ps = &mut ps[..ps_len];
+    let mut bins = [0u64; MAX_ROW_BINS];
+    let bins = &mut bins[..k * 16 * m_vec_limbs];
 
     let mut p1_used = 0;
     for row in 0..v {
-        let acc_row_offset = row * k * 16 * m_vec_limbs;
+        bins.fill(0);
         for j in row..v {
             let src = &p1[p1_used * m_vec_limbs..(p1_used + 1) * m_vec_limbs];
             for col in 0..k {
-                let bin_idx =
-                    acc_row_offset + (col * 16 + usize::from(s[col * n + j])) * m_vec_limbs;
-                m_vec_add(
-                    src,
-                    &mut accumulator[bin_idx..bin_idx + m_vec_limbs],
-                    m_vec_limbs,
-                );
+                let bin_idx = (col * 16 + usize::from(s[col * n + j])) * m_vec_limbs;
+                m_vec_add(src, &mut bins[bin_idx..bin_idx + m_vec_limbs], m_vec_limbs);
             }
             p1_used += 1;
         }
@@ -326,72 +318,211 @@ pub(crate) fn m_calculate_ps_sps_with_scratch<P: MayoParameter>(
         for j in 0..o {
             let src = &p2[(row * o + j) * m_vec_limbs..(row * o + j + 1) * m_vec_limbs];
             for col in 0..k {
-                let bin_idx =
-                    acc_row_offset + (col * 16 + usize::from(s[col * n + j + v])) * m_vec_limbs;
-                m_vec_add(
-                    src,
-                    &mut accumulator[bin_idx..bin_idx + m_vec_limbs],
-                    m_vec_limbs,
-                );
+                let bin_idx = (col * 16 + usize::from(s[col * n + j + v])) * m_vec_limbs;
+                m_vec_add(src, &mut bins[bin_idx..bin_idx + m_vec_limbs], m_vec_limbs);
             }
         }
+        fold_row_bins(bins, &mut ps[row * k * m_vec_limbs..], k, m_vec_limbs);
     }
 
+    let packed_size = P::M / 2;
+    let mut p3_vec = [0u64; MAX_M_VEC_LIMBS];
     let mut p3_used = 0;
     for row in v..n {
-        let acc_row_offset = row * k * 16 * m_vec_limbs;
+        bins.fill(0);
         for j in row..n {
-            let src = &p3[p3_used * m_vec_limbs..(p3_used + 1) * m_vec_limbs];
+            let src = match p3 {
+                P3Input::Unpacked(p3) => &p3[p3_used * m_vec_limbs..(p3_used + 1) * m_vec_limbs],
+                P3Input::Packed(p3) => {
+                    unpack_m_vecs(&p3[p3_used * packed_size..], &mut p3_vec, 1, P::M);
+                    &p3_vec[..m_vec_limbs]
+                }
+            };
             for col in 0..k {
-                let bin_idx =
-                    acc_row_offset + (col * 16 + usize::from(s[col * n + j])) * m_vec_limbs;
-                m_vec_add(
-                    src,
-                    &mut accumulator[bin_idx..bin_idx + m_vec_limbs],
-                    m_vec_limbs,
-                );
+                let bin_idx = (col * 16 + usize::from(s[col * n + j])) * m_vec_limbs;
+                m_vec_add(src, &mut bins[bin_idx..bin_idx + m_vec_limbs], m_vec_limbs);
             }
             p3_used += 1;
         }
+        fold_row_bins(bins, &mut ps[row * k * m_vec_limbs..], k, m_vec_limbs);
     }
 
-    // Multiply bins
-    let mut idx = 0;
-    while idx < n * k {
-        m_vec_multiply_bins(
-            &mut accumulator[idx * 16 * m_vec_limbs..],
-            &mut ps[idx * m_vec_limbs..(idx + 1) * m_vec_limbs],
-            m_vec_limbs,
-        );
-        idx += 1;
-    }
-
-    // Compute SPS = S * PS
+    // Compute SPS = S * PS, again one row at a time
     for row in 0..k {
+        bins.fill(0);
         let s_row = &s[row * n..(row + 1) * n];
-        let sps_acc_row_offset = row * k * 16 * m_vec_limbs;
         for (j, &s_j) in s_row.iter().enumerate() {
             let bin = usize::from(s_j);
             let ps_row_offset = j * k * m_vec_limbs;
             for col in 0..k {
-                let bin_idx = sps_acc_row_offset + (col * 16 + bin) * m_vec_limbs;
+                let bin_idx = (col * 16 + bin) * m_vec_limbs;
                 let ps_idx = ps_row_offset + col * m_vec_limbs;
                 m_vec_add(
                     &ps[ps_idx..ps_idx + m_vec_limbs],
-                    &mut sps_accumulator[bin_idx..bin_idx + m_vec_limbs],
+                    &mut bins[bin_idx..bin_idx + m_vec_limbs],
                     m_vec_limbs,
                 );
             }
         }
+        fold_row_bins(bins, &mut sps[row * k * m_vec_limbs..], k, m_vec_limbs);
     }
+}
 
-    idx = 0;
-    while idx < k * k {
-        m_vec_multiply_bins(
-            &mut sps_accumulator[idx * 16 * m_vec_limbs..],
-            &mut sps[idx * m_vec_limbs..(idx + 1) * m_vec_limbs],
-            m_vec_limbs,
-        );
-        idx += 1;
+/// Compute the right-hand side: y = t XOR reduce(vPv).
+///
+/// Signing calls this with the target `t`; verification calls it with
+/// `t = 0` to reduce `sPs` into the public map's output.
+pub(crate) fn compute_rhs<P: MayoParameter>(vpv: &mut [u64], t: &[u8], y: &mut [u8]) {
+    let m_vec_limbs = P::M_VEC_LIMBS;
+    let param_m = P::M;
+    let param_k = P::K;
+    let f_tail = P::F_TAIL;
+
+    let top_pos = ((param_m - 1) % 16) * 4;
+
+    // Zero out tails of m_vecs if necessary
+    if param_m % 16 != 0 {
+        let mut mask: u64 = 1;
+        mask <<= (param_m % 16) * 4;
+        mask -= 1;
+        for i in 0..(param_k * param_k) {
+            vpv[i * m_vec_limbs + m_vec_limbs - 1] &= mask;
+        }
+    }
+
+    let mut temp = [0u64; MAX_M_VEC_LIMBS];
+
+    for i in (0..param_k).rev() {
+        for j in i..param_k {
+            // Multiply by X (shift up 4 bits)
+            let top = ((temp[m_vec_limbs - 1] >> top_pos) % 16) as u8;
+            temp[m_vec_limbs - 1] <<= 4;
+            for k in (0..m_vec_limbs - 1).rev() {
+                temp[k + 1] ^= temp[k] >> 60;
+                temp[k] <<= 4;
+            }
+
+            // Reduce mod f(X)
+            for (jj, &f_coeff) in f_tail.iter().enumerate().take(F_TAIL_LEN) {
+                let product = mul_f(top, f_coeff);
+                if jj % 2 == 0 {
+                    // XOR into the low nibble of byte jj/2
+                    let limb_idx = (jj / 2) / 8;
+                    let byte_idx = (jj / 2) % 8;
+                    temp[limb_idx] ^= u64::from(product) << (byte_idx * 8);
+                } else {
+                    // XOR into the high nibble of byte jj/2
+                    let limb_idx = (jj / 2) / 8;
+                    let byte_idx = (jj / 2) % 8;
+                    temp[limb_idx] ^= u64::from(product) << (byte_idx * 8 + 4);
+                }
+            }
+
+            // Extract from vPv and add
+            let idx_ij = (i * param_k + j) * m_vec_limbs;
+            let idx_ji = (j * param_k + i) * m_vec_limbs;
+            for k in 0..m_vec_limbs {
+                let sym = if i != j { vpv[idx_ji + k] } else { 0 };
+                temp[k] ^= vpv[idx_ij + k] ^ sym;
+            }
+        }
+    }
+
+    // Compute y = t XOR temp (unpacked)
+    for i in (0..param_m).step_by(2) {
+        let limb_idx = (i / 2) / 8;
+        let byte_idx = (i / 2) % 8;
+        let byte_val = ((temp[limb_idx] >> (byte_idx * 8)) & 0xFF) as u8;
+        y[i] = t[i] ^ (byte_val & 0xF);
+        if i + 1 < param_m {
+            y[i + 1] = t[i + 1] ^ (byte_val >> 4);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic xorshift stream.
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn nibble(&mut self) -> u8 {
+            u8::try_from(self.next() & 0xf).expect("below 16")
+        }
+    }
+
+    fn get_nibble(m_vec: &[u64], t: usize) -> u8 {
+        u8::try_from((m_vec[t / 16] >> (4 * (t % 16))) & 0xf).expect("below 16")
+    }
+
+    /// Dense reference: `L[i][k] = P2[i][k] + sum_j (P1 + P1^t)[i][j] * O[j][k]`,
+    /// one nibble of each m-vector at a time. `P1` is upper triangular,
+    /// stored row by row from the diagonal, so the diagonal is counted twice
+    /// and cancels.
+    fn p1p1t_times_o_naive<P: MayoParameter>(p1: &[u64], o: &[u8], p2: &[u64]) -> Vec<u64> {
+        let limbs = P::M_VEC_LIMBS;
+        let (v, param_o) = (P::V, P::O);
+        let p1_at = |r: usize, c: usize| {
+            // Index of (r, c), c >= r, in the packed upper triangle.
+            let idx = r * v - r * (r + 1) / 2 + c;
+            &p1[idx * limbs..(idx + 1) * limbs]
+        };
+
+        let mut l = p2.to_vec();
+        for i in 0..v {
+            for k in 0..param_o {
+                let out = &mut l[(i * param_o + k) * limbs..(i * param_o + k + 1) * limbs];
+                for t in 0..16 * limbs {
+                    let mut acc = get_nibble(out, t);
+                    for j in 0..v {
+                        let mut a = 0;
+                        if j >= i {
+                            a ^= get_nibble(p1_at(i, j), t);
+                        }
+                        if j <= i {
+                            a ^= get_nibble(p1_at(j, i), t);
+                        }
+                        acc ^= mul_f(a, o[j * param_o + k]);
+                    }
+                    out[t / 16] &= !(0xf << (4 * (t % 16)));
+                    out[t / 16] |= u64::from(acc) << (4 * (t % 16));
+                }
+            }
+        }
+        l
+    }
+
+    fn p1p1t_times_o_matches_naive<P: MayoParameter>() {
+        for seed in 1..=2u64 {
+            let mut rng = XorShift(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+            let p1: Vec<u64> = (0..P::P1_LIMBS).map(|_| rng.next()).collect();
+            let p2: Vec<u64> = (0..P::P2_LIMBS).map(|_| rng.next()).collect();
+            let o: Vec<u8> = (0..P::V * P::O).map(|_| rng.nibble()).collect();
+
+            let mut l = p2.clone();
+            p1p1t_times_o::<P>(&p1, &o, &mut l);
+            assert!(
+                l == p1p1t_times_o_naive::<P>(&p1, &o, &p2),
+                "{} seed {seed}",
+                P::NAME
+            );
+        }
+    }
+
+    #[test]
+    fn p1p1t_times_o_matches_naive_all_params() {
+        p1p1t_times_o_matches_naive::<crate::Mayo1>();
+        p1p1t_times_o_matches_naive::<crate::Mayo2>();
+        p1p1t_times_o_matches_naive::<crate::Mayo3>();
+        p1p1t_times_o_matches_naive::<crate::Mayo5>();
     }
 }
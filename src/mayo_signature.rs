@@ -2,17 +2,152 @@
 
 //! MAYO signature type.
 
+use crate::codec::has_zero_padding;
+use crate::envelope::param_id;
 use crate::error::Error;
-use crate::params::MayoParameter;
+use crate::params::{MAX_SIG_BYTES, MayoParameter};
+use crate::xof::{Shake256Xof, Xof};
 use core::marker::PhantomData;
+use zeroize::Zeroize;
 
 /// A MAYO signature.
+///
+/// The encoding is the packed solution `s` followed by the salt:
+///
+/// | Offset | Length | Field |
+/// |--------|--------|-------|
+/// | 0 | `SIG_BYTES - SALT_BYTES` | `s`, `k * n` nibbles, low nibble first |
+/// | `SIG_BYTES - SALT_BYTES` | `SALT_BYTES` | salt |
 #[derive(Clone)]
 pub struct Signature<P: MayoParameter> {
     bytes: Vec<u8>,
     _marker: PhantomData<P>,
 }
 
+impl<P: MayoParameter> Signature<P> {
+    /// Copy the encoded signature into a new `Vec`, leaving `self` intact.
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.bytes.clone()
+    }
+
+    /// The salt, the last `P::SALT_BYTES` bytes of the encoding.
+    ///
+    /// Verification hashes this together with the message digest to derive
+    /// the target vector `t`.
+    pub fn salt(&self) -> &[u8] {
+        &self.bytes[P::SIG_BYTES - P::SALT_BYTES..]
+    }
+
+    /// The packed solution `s`, everything before the salt.
+    pub fn encoded_solution(&self) -> &[u8] {
+        &self.bytes[..P::SIG_BYTES - P::SALT_BYTES]
+    }
+
+    /// Both regions at once, `(encoded_solution, salt)`, split at
+    /// `P::SIG_BYTES - P::SALT_BYTES` without copying.
+    pub fn split(&self) -> (&[u8], &[u8]) {
+        self.bytes.split_at(P::SIG_BYTES - P::SALT_BYTES)
+    }
+
+    /// Encode with a parameter set tag and length prefix:
+    ///
+    /// | Offset | Length | Field |
+    /// |--------|--------|-------|
+    /// | 0 | 1 | parameter set id, see [`param_id`](crate::envelope::param_id), or 0 |
+    /// | 1 | 2 | `SIG_BYTES`, big-endian |
+    /// | 3 | `SIG_BYTES` | the signature |
+    ///
+    /// Suits stores that mix signatures of several parameter sets. A
+    /// parameter set defined outside this crate has no id and is tagged 0,
+    /// which [`from_tagged_bytes`](Self::from_tagged_bytes) never accepts.
+    pub fn to_tagged_bytes(&self) -> Vec<u8> {
+        const {
+            assert!(
+                P::SIG_BYTES <= u16::MAX as usize,
+                "SIG_BYTES fits in two bytes"
+            )
+        };
+        let [.., hi, lo] = P::SIG_BYTES.to_be_bytes();
+        let mut out = Vec::with_capacity(3 + P::SIG_BYTES);
+        out.push(param_id::<P>().unwrap_or(0));
+        out.extend_from_slice(&[hi, lo]);
+        out.extend_from_slice(&self.bytes);
+        out
+    }
+
+    /// Decode the output of [`to_tagged_bytes`](Self::to_tagged_bytes).
+    ///
+    /// Returns [`Error::ParameterSetMismatch`] if the tag names another
+    /// parameter set or is 0, and [`Error::InvalidSignatureLength`] if the input is
+    /// truncated, or if the declared length or the bytes that follow it are
+    /// not `P::SIG_BYTES` long.
+    pub fn from_tagged_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let Some((&[tag, hi, lo], sig)) = bytes.split_first_chunk::<3>() else {
+            return Err(Error::InvalidSignatureLength {
+                expected: 3 + P::SIG_BYTES,
+                got: bytes.len(),
+            });
+        };
+        let expected = param_id::<P>();
+        if expected != Some(tag) {
+            return Err(Error::ParameterSetMismatch {
+                expected: expected.unwrap_or(0),
+                got: tag,
+            });
+        }
+        let declared = usize::from(u16::from_be_bytes([hi, lo]));
+        if declared != P::SIG_BYTES {
+            return Err(Error::InvalidSignatureLength {
+                expected: P::SIG_BYTES,
+                got: declared,
+            });
+        }
+        Self::try_from(sig)
+    }
+
+    /// A 32-byte identifier for this signature: SHAKE256 of its encoding,
+    /// truncated.
+    ///
+    /// Like [`VerifyingKey::commitment`](crate::VerifyingKey::commitment), it
+    /// is computed from public bytes and is not secret. Useful to deduplicate
+    /// or index signatures without storing them whole.
+    pub fn commitment(&self) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        Shake256Xof::hash(&[&self.bytes], &mut out);
+        out
+    }
+
+    /// Cheap structural check, without verifying anything.
+    ///
+    /// The encoding must be exactly `P::SIG_BYTES` long and, when `k * n` is
+    /// odd, the padding nibble after `s` must be zero. Verification rejects
+    /// a signature that fails either check before doing any work, so this
+    /// lets a gateway drop such signatures early. Every built-in parameter
+    /// set has an even `k * n`, so for them only the length matters.
+    pub fn is_well_formed(&self) -> bool {
+        self.bytes.len() == P::SIG_BYTES && has_zero_padding(&self.bytes, P::K * P::N)
+    }
+
+    /// The encoded length in bytes, always `P::SIG_BYTES`.
+    // A signature is never empty, so an `is_empty` would always be `false`.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        P::SIG_BYTES
+    }
+}
+
+impl<P: MayoParameter> Zeroize for Signature<P> {
+    /// Overwrite the encoding with zeros in place.
+    ///
+    /// A signature is not secret; this exists so it can be handled like
+    /// other credential types that must implement `Zeroize`. The length is
+    /// kept, so the accessors stay valid, and dropping a signature does not
+    /// wipe it.
+    fn zeroize(&mut self) {
+        self.bytes.as_mut_slice().zeroize();
+    }
+}
+
 impl<P: MayoParameter> PartialEq for Signature<P> {
     fn eq(&self, other: &Self) -> bool {
         self.bytes == other.bytes
@@ -21,6 +156,24 @@ impl<P: MayoParameter> PartialEq for Signature<P> {
 
 impl<P: MayoParameter> Eq for Signature<P> {}
 
+impl<P: MayoParameter> PartialOrd for Signature<P> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P: MayoParameter> Ord for Signature<P> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.bytes.cmp(&other.bytes)
+    }
+}
+
+impl<P: MayoParameter> core::hash::Hash for Signature<P> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.bytes.hash(state);
+    }
+}
+
 impl<P: MayoParameter> AsRef<[u8]> for Signature<P> {
     fn as_ref(&self) -> &[u8] {
         &self.bytes
@@ -36,6 +189,25 @@ impl<P: MayoParameter> core::fmt::Debug for Signature<P> {
     }
 }
 
+impl<P: MayoParameter> core::fmt::Display for Signature<P> {
+    /// The signature as lowercase hex.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&hex::encode(&self.bytes))
+    }
+}
+
+impl<P: MayoParameter> core::str::FromStr for Signature<P> {
+    type Err = Error;
+
+    /// Parse the hex form written by [`Display`](core::fmt::Display), in
+    /// either case. Returns [`Error::InvalidHex`] or
+    /// [`Error::InvalidSignatureLength`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s).map_err(|_| Error::InvalidHex)?;
+        Self::try_from(bytes)
+    }
+}
+
 impl<P: MayoParameter> TryFrom<&[u8]> for Signature<P> {
     type Error = Error;
 
@@ -92,6 +264,7 @@ impl<P: MayoParameter> From<Signature<P>> for Vec<u8> {
     }
 }
 
+#[cfg(feature = "signature-traits")]
 impl<P: MayoParameter> signature::SignatureEncoding for Signature<P> {
     type Repr = Vec<u8>;
 }
@@ -116,3 +289,98 @@ impl<'de, P: MayoParameter> serde::Deserialize<'de> for Signature<P> {
         Self::try_from(bytes).map_err(serde::de::Error::custom)
     }
 }
+
+/// A MAYO signature held inline in a fixed-size array instead of a `Vec`.
+///
+/// Every parameter set's signature fits in [`MAX_SIG_BYTES`], so this type
+/// is `Copy` and can be embedded in other structs or a `static`. Parsing,
+/// copying and comparing one does not allocate. Produce one with
+/// [`SigningKey::sign_inline`](crate::SigningKey::sign_inline) and check it
+/// with [`VerifyingKey::verify_from_slice`](crate::VerifyingKey::verify_from_slice),
+/// which verifies the bytes in place.
+///
+/// This is about where the signature is stored, not about the operations:
+/// signing and verification allocate their working memory as usual, which
+/// is dominated by the expanded P1 and P2 (over 100 KB for every parameter
+/// set). A parameter set defined outside this crate with `SIG_BYTES` above
+/// [`MAX_SIG_BYTES`] cannot be stored and returns
+/// [`Error::InvalidSignatureLength`].
+#[derive(Clone, Copy)]
+pub struct InlineSignature<P: MayoParameter> {
+    bytes: [u8; MAX_SIG_BYTES],
+    _marker: PhantomData<P>,
+}
+
+impl<P: MayoParameter> InlineSignature<P> {
+    /// Run `fill` on a zeroed `P::SIG_BYTES` buffer and keep the result.
+    pub(crate) fn build(fill: impl FnOnce(&mut [u8]) -> Result<(), Error>) -> Result<Self, Error> {
+        let mut bytes = [0u8; MAX_SIG_BYTES];
+        let sig = bytes
+            .get_mut(..P::SIG_BYTES)
+            .ok_or(Error::InvalidSignatureLength {
+                expected: P::SIG_BYTES,
+                got: MAX_SIG_BYTES,
+            })?;
+        fill(sig)?;
+        Ok(Self {
+            bytes,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Copy into a heap-backed [`Signature`].
+    pub fn to_signature(&self) -> Signature<P> {
+        Signature {
+            bytes: self.as_ref().to_vec(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<P: MayoParameter> AsRef<[u8]> for InlineSignature<P> {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes[..P::SIG_BYTES]
+    }
+}
+
+impl<P: MayoParameter> PartialEq for InlineSignature<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl<P: MayoParameter> Eq for InlineSignature<P> {}
+
+impl<P: MayoParameter> core::fmt::Debug for InlineSignature<P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InlineSignature")
+            .field("variant", &P::NAME)
+            .field("bytes", &hex::encode(self.as_ref()))
+            .finish()
+    }
+}
+
+impl<P: MayoParameter> TryFrom<&[u8]> for InlineSignature<P> {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() != P::SIG_BYTES {
+            return Err(Error::InvalidSignatureLength {
+                expected: P::SIG_BYTES,
+                got: bytes.len(),
+            });
+        }
+        Self::build(|sig| {
+            sig.copy_from_slice(bytes);
+            Ok(())
+        })
+    }
+}
+
+impl<P: MayoParameter> TryFrom<&Signature<P>> for InlineSignature<P> {
+    type Error = Error;
+
+    fn try_from(signature: &Signature<P>) -> Result<Self, Self::Error> {
+        Self::try_from(signature.as_ref())
+    }
+}
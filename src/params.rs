@@ -16,12 +16,25 @@ pub(crate) const MAX_M_VEC_LIMBS: usize = 9;
 /// Maximum M across all parameter sets (Mayo5 = 142).
 pub(crate) const MAX_M: usize = 142;
 
+/// Maximum A_COLS = k*o + 1 across all parameter sets (Mayo5 = 145).
+pub(crate) const MAX_A_COLS: usize = 145;
+
+/// Maximum of O and K across all parameter sets (Mayo2's o = 16), the
+/// widest row of m-vectors the bin accumulators fold at once.
+pub(crate) const MAX_ROW_VECS: usize = 16;
+
+/// Largest `SIG_BYTES` of the built-in parameter sets (Mayo5 = 964), the
+/// capacity of a [`InlineSignature`](crate::InlineSignature).
+pub const MAX_SIG_BYTES: usize = 964;
+
 /// Trait defining all constants for a MAYO parameter set.
 pub trait MayoParameter: Clone + Copy + Send + Sync + 'static {
     /// Typenum type for the compact secret key byte length.
     type CskSize: ArraySize;
     /// Human-readable name of this parameter set.
     const NAME: &'static str;
+    /// NIST security category (1, 3 or 5). MAYO-2 is category 1, like MAYO-1.
+    const SECURITY_LEVEL: u8;
     /// Total number of variables (n).
     const N: usize;
     /// Number of equations (m).
@@ -42,7 +55,13 @@ pub trait MayoParameter: Clone + Copy + Send + Sync + 'static {
     const O_BYTES: usize;
     /// Byte length of a vinegar vector packed as nibbles.
     const V_BYTES: usize;
-    /// Byte length of a random vector r packed as nibbles.
+    /// Byte length of a random vector r packed as nibbles,
+    /// `(K * O).div_ceil(2)`.
+    ///
+    /// Signing squeezes `K * V_BYTES + R_BYTES` bytes per attempt: the `K`
+    /// vinegar vectors, then `r`. Decoding the `K * O` nibbles of `r` reads
+    /// exactly to the end of that buffer, into the first `K * O` of the
+    /// `A_COLS` entries of the solution; the last entry stays zero.
     const R_BYTES: usize;
     /// Byte length of P1 matrix packed.
     const P1_BYTES: usize;
@@ -54,6 +73,8 @@ pub trait MayoParameter: Clone + Copy + Send + Sync + 'static {
     const CSK_BYTES: usize;
     /// Byte length of compact public key.
     const CPK_BYTES: usize;
+    /// Byte length of the expanded secret key (`seed_sk || O || P1 || L`).
+    const ESK_BYTES: usize;
     /// Byte length of signature.
     const SIG_BYTES: usize;
     /// Byte length of salt.
@@ -72,11 +93,68 @@ pub trait MayoParameter: Clone + Copy + Send + Sync + 'static {
     const P2_LIMBS: usize;
     /// Number of u64 limbs for P3 in bitsliced form.
     const P3_LIMBS: usize;
+    /// Experimental object identifier for this parameter set (OQS
+    /// `1.3.9999.8` arc), as used for PKCS#8 and SPKI encoding.
+    ///
+    /// `None` for a parameter set without one, which is the default so that
+    /// enabling `pkcs8` never breaks a parameter set defined elsewhere.
+    #[cfg(feature = "pkcs8")]
+    const OID: Option<pkcs8::ObjectIdentifier> = None;
+
+    /// The name, security level and encoded sizes of this parameter set.
+    fn describe() -> ParamInfo {
+        ParamInfo {
+            name: Self::NAME,
+            security_level: Self::SECURITY_LEVEL,
+            sig_bytes: Self::SIG_BYTES,
+            pk_bytes: Self::CPK_BYTES,
+            sk_bytes: Self::CSK_BYTES,
+        }
+    }
+
+    /// Expected number of `(v, r)` sampling attempts per signature.
+    ///
+    /// An attempt succeeds when its `m x ko` linear system has full rank `m`.
+    /// For a uniformly random matrix over GF(16) that happens with
+    /// probability
+    ///
+    /// `p = (1 - 16^-(ko-m+1)) * (1 - 16^-(ko-m+2)) * ... * (1 - 16^-ko)`,
+    ///
+    /// so the number of attempts is geometric with mean `1 / p`. MAYO_1,
+    /// MAYO_3 and MAYO_5 have `ko - m = 2` and expect under 1.0003 attempts;
+    /// MAYO_2 has `ko = m` and expects about 1.071. Multiplying by the cost
+    /// of one attempt gives a latency budget.
+    fn expected_solve_attempts() -> f64 {
+        // 16^-(ko-m+1), the failure weight of the first factor.
+        let mut q_pow = (0..=Self::K * Self::O - Self::M).fold(1.0, |x, _| x / 16.0);
+        let mut p = 1.0;
+        for _ in 0..Self::M {
+            p *= 1.0 - q_pow;
+            q_pow /= 16.0;
+        }
+        1.0 / p
+    }
+}
+
+/// Summary of a parameter set, from [`MayoParameter::describe`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ParamInfo {
+    /// Human-readable name, e.g. `"MAYO_1"`.
+    pub name: &'static str,
+    /// NIST security category.
+    pub security_level: u8,
+    /// Signature size in bytes.
+    pub sig_bytes: usize,
+    /// Compact public key size in bytes.
+    pub pk_bytes: usize,
+    /// Compact secret key size in bytes.
+    pub sk_bytes: usize,
 }
 
 macro_rules! define_mayo_parameter {
     (
-        $name:ident, $display:expr,
+        $name:ident, $display:expr, security_level = $level:expr,
         n = $n:expr, m = $m:expr, o = $o:expr, k = $k:expr,
         m_vec_limbs = $mvl:expr,
         m_bytes = $mb:expr, O_bytes = $ob:expr, v_bytes = $vb:expr, r_bytes = $rb:expr,
@@ -85,7 +163,7 @@ macro_rules! define_mayo_parameter {
         cpk_bytes = $cpkb:expr, sig_bytes = $sigb:expr,
         salt_bytes = $saltb:expr, digest_bytes = $db:expr,
         pk_seed_bytes = $pksb:expr, sk_seed_bytes = $sksb:expr,
-        f_tail = $ft:expr
+        f_tail = $ft:expr, oid = $oid:expr
     ) => {
         #[doc = concat!("MAYO parameter set ", $display, ".")]
         #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -94,6 +172,7 @@ macro_rules! define_mayo_parameter {
         impl MayoParameter for $name {
             type CskSize = $cskt;
             const NAME: &'static str = $display;
+            const SECURITY_LEVEL: u8 = $level;
             const N: usize = $n;
             const M: usize = $m;
             const O: usize = $o;
@@ -110,6 +189,7 @@ macro_rules! define_mayo_parameter {
             const P3_BYTES: usize = $p3b;
             const CSK_BYTES: usize = $cskb;
             const CPK_BYTES: usize = $cpkb;
+            const ESK_BYTES: usize = $sksb + $ob + $p1b + $p2b;
             const SIG_BYTES: usize = $sigb;
             const SALT_BYTES: usize = $saltb;
             const DIGEST_BYTES: usize = $db;
@@ -119,6 +199,9 @@ macro_rules! define_mayo_parameter {
             const P1_LIMBS: usize = ($n - $o) * (($n - $o) + 1) / 2 * $mvl;
             const P2_LIMBS: usize = ($n - $o) * $o * $mvl;
             const P3_LIMBS: usize = $o * ($o + 1) / 2 * $mvl;
+            #[cfg(feature = "pkcs8")]
+            const OID: Option<pkcs8::ObjectIdentifier> =
+                Some(pkcs8::ObjectIdentifier::new_unwrap($oid));
         }
     };
 }
@@ -126,6 +209,7 @@ macro_rules! define_mayo_parameter {
 define_mayo_parameter!(
     Mayo1,
     "MAYO_1",
+    security_level = 1,
     n = 86,
     m = 78,
     o = 8,
@@ -146,12 +230,14 @@ define_mayo_parameter!(
     digest_bytes = 32,
     pk_seed_bytes = 16,
     sk_seed_bytes = 24,
-    f_tail = [8, 1, 1, 0]
+    f_tail = [8, 1, 1, 0],
+    oid = "1.3.9999.8.1.3"
 );
 
 define_mayo_parameter!(
     Mayo2,
     "MAYO_2",
+    security_level = 1,
     n = 96,
     m = 64,
     o = 16,
@@ -172,12 +258,14 @@ define_mayo_parameter!(
     digest_bytes = 32,
     pk_seed_bytes = 16,
     sk_seed_bytes = 24,
-    f_tail = [8, 0, 2, 8]
+    f_tail = [8, 0, 2, 8],
+    oid = "1.3.9999.8.2.3"
 );
 
 define_mayo_parameter!(
     Mayo3,
     "MAYO_3",
+    security_level = 3,
     n = 118,
     m = 108,
     o = 10,
@@ -198,12 +286,14 @@ define_mayo_parameter!(
     digest_bytes = 48,
     pk_seed_bytes = 16,
     sk_seed_bytes = 32,
-    f_tail = [8, 0, 1, 7]
+    f_tail = [8, 0, 1, 7],
+    oid = "1.3.9999.8.3.3"
 );
 
 define_mayo_parameter!(
     Mayo5,
     "MAYO_5",
+    security_level = 5,
     n = 154,
     m = 142,
     o = 12,
@@ -224,5 +314,6 @@ define_mayo_parameter!(
     digest_bytes = 64,
     pk_seed_bytes = 16,
     sk_seed_bytes = 40,
-    f_tail = [4, 0, 8, 1]
+    f_tail = [4, 0, 8, 1],
+    oid = "1.3.9999.8.5.3"
 );
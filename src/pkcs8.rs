@@ -46,16 +46,52 @@ type SeedString<'a> = ContextSpecific<&'a OctetStringRef>;
 // ============================================================================
 
 /// Experimental OID for MAYO-1 (`1.3.9999.8.1.3`).
-pub const MAYO1_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.9999.8.1.3");
+pub const MAYO1_OID: ObjectIdentifier = Mayo1::OID.expect("MAYO_1 has an OID");
 
 /// Experimental OID for MAYO-2 (`1.3.9999.8.2.3`).
-pub const MAYO2_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.9999.8.2.3");
+pub const MAYO2_OID: ObjectIdentifier = Mayo2::OID.expect("MAYO_2 has an OID");
 
 /// Experimental OID for MAYO-3 (`1.3.9999.8.3.3`).
-pub const MAYO3_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.9999.8.3.3");
+pub const MAYO3_OID: ObjectIdentifier = Mayo3::OID.expect("MAYO_3 has an OID");
 
 /// Experimental OID for MAYO-5 (`1.3.9999.8.5.3`).
-pub const MAYO5_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.9999.8.5.3");
+pub const MAYO5_OID: ObjectIdentifier = Mayo5::OID.expect("MAYO_5 has an OID");
+
+// ============================================================================
+// Size lookup by OID
+// ============================================================================
+
+/// `(SIG_BYTES, CPK_BYTES, CSK_BYTES)` of the parameter set named by `oid`.
+fn sizes_for_oid(oid: &ObjectIdentifier) -> Option<(usize, usize, usize)> {
+    fn sizes<P: MayoParameter>() -> (usize, usize, usize) {
+        (P::SIG_BYTES, P::CPK_BYTES, P::CSK_BYTES)
+    }
+    match *oid {
+        MAYO1_OID => Some(sizes::<Mayo1>()),
+        MAYO2_OID => Some(sizes::<Mayo2>()),
+        MAYO3_OID => Some(sizes::<Mayo3>()),
+        MAYO5_OID => Some(sizes::<Mayo5>()),
+        _ => None,
+    }
+}
+
+/// Signature length in bytes for the MAYO parameter set identified by `oid`,
+/// or `None` if `oid` is not one of the MAYO OIDs.
+pub fn sig_bytes_for_oid(oid: &ObjectIdentifier) -> Option<usize> {
+    sizes_for_oid(oid).map(|(sig, _, _)| sig)
+}
+
+/// Compact public key length in bytes for the MAYO parameter set identified
+/// by `oid`, or `None` if `oid` is not one of the MAYO OIDs.
+pub fn cpk_bytes_for_oid(oid: &ObjectIdentifier) -> Option<usize> {
+    sizes_for_oid(oid).map(|(_, cpk, _)| cpk)
+}
+
+/// Compact secret key length in bytes for the MAYO parameter set identified
+/// by `oid`, or `None` if `oid` is not one of the MAYO OIDs.
+pub fn csk_bytes_for_oid(oid: &ObjectIdentifier) -> Option<usize> {
+    sizes_for_oid(oid).map(|(_, _, csk)| csk)
+}
 
 // ============================================================================
 // AssociatedAlgorithmIdentifier for parameter set types
@@ -157,8 +193,16 @@ where
         let seed = seed_string.value.as_bytes();
         reader.finish()?;
 
-        KeyPair::from_seed(seed)
-            .map_err(|_| ::pkcs8::Error::KeyMalformed(::pkcs8::KeyError::Invalid))
+        let keypair = match private_key_info.public_key {
+            Some(public_key) => {
+                let cpk = public_key
+                    .as_bytes()
+                    .ok_or(::pkcs8::Error::KeyMalformed(::pkcs8::KeyError::Invalid))?;
+                KeyPair::from_seed_and_public_key(seed, cpk)
+            }
+            None => KeyPair::from_seed(seed),
+        };
+        keypair.map_err(|_| ::pkcs8::Error::KeyMalformed(::pkcs8::KeyError::Invalid))
     }
 }
 
@@ -181,6 +225,34 @@ where
     }
 }
 
+impl<P> KeyPair<P>
+where
+    P: MayoParameter + AssociatedAlgorithmIdentifier<Params = AnyRef<'static>>,
+{
+    /// Encode as PKCS#8 v2 (`OneAsymmetricKey`), with the public key in its
+    /// optional `publicKey` field next to the seed.
+    ///
+    /// Decoding such a document with
+    /// [`DecodePrivateKey`](::pkcs8::DecodePrivateKey) still derives the
+    /// public key from the seed, and rejects the document if the stored
+    /// field differs from it in any byte. Documents without the field, such
+    /// as those from [`to_pkcs8_der`](EncodePrivateKey::to_pkcs8_der), load
+    /// the same way minus that check.
+    pub fn to_pkcs8_der_with_public_key(&self) -> ::pkcs8::Result<der::SecretDocument> {
+        let seed_der = SeedString {
+            tag_mode: TagMode::Implicit,
+            tag_number: SEED_TAG_NUMBER,
+            value: OctetStringRef::new(self.signing_key().as_ref())?,
+        }
+        .to_der()?;
+
+        let private_key = OctetStringRef::new(&seed_der)?;
+        let mut private_key_info = PrivateKeyInfoRef::new(P::ALGORITHM_IDENTIFIER, private_key);
+        private_key_info.public_key = Some(BitStringRef::new(0, self.verifying_key().as_ref())?);
+        ::pkcs8::SecretDocument::encode_msg(&private_key_info).map_err(::pkcs8::Error::Asn1)
+    }
+}
+
 // ============================================================================
 // SigningKey: TryFrom<PrivateKeyInfoRef> (DecodePrivateKey via blanket impl)
 // ============================================================================
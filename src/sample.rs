@@ -2,8 +2,9 @@
 
 //! Constant-time solution sampling for the linearized MAYO system.
 
-use crate::echelon::{ct_compare_8, ef};
+use crate::echelon::{ct_compare_8, ct_select_u8, ef};
 use crate::gf16::{mat_mul, mul_fx8, sub_f};
+use crate::params::MAX_M;
 
 /// Sample a solution x to Ax = y, with r used as randomness.
 ///
@@ -34,14 +35,17 @@ pub(crate) fn sample_solution(args: SampleSolutionArgs<'_>) -> bool {
     } = args;
 
     let ko = k * o;
+    debug_assert!(m >= 1 && a_cols == ko + 1);
+    debug_assert!(a.len() >= m * a_cols && x.len() >= a_cols && y.len() >= m);
 
     // Compute Ar
-    let mut ar = vec![0u8; m];
+    let mut ar = [0u8; MAX_M];
+    let ar = &mut ar[..m];
     // Clear last column of A
     for i in 0..m {
         a[ko + i * a_cols] = 0;
     }
-    mat_mul(a, x, &mut ar, a_cols, m, 1);
+    mat_mul(a, x, ar, a_cols, m, 1);
 
     // Move y - Ar to last column of matrix A
     for i in 0..m {
@@ -61,15 +65,29 @@ pub(crate) fn sample_solution(args: SampleSolutionArgs<'_>) -> bool {
 
     // Back substitution — runs unconditionally regardless of rank to prevent
     // timing leaks; caller retries when we return false.
+    //
+    // The pivot of `row` is searched for in `row..=col_upper_bound`, as in the
+    // reference implementation. `m - row >= 1`, so the division is defined;
+    // `32 / (m - row)` is 32 for the last row and 0 once `m - row > 32`. The
+    // bound depends only on the dimensions and is clamped to `ko = a_cols - 1`,
+    // so `row * a_cols + col` stays inside row `row` of `a` and `col` inside
+    // `x`. `col == ko` is the right-hand side: with full rank every row's pivot
+    // lies before it (at most `row + ko - m`, and `ko - m <= 2` for every
+    // parameter set, well inside the window of the last 16 rows), so `u` is 0
+    // there. Only a row with no pivot, in an attempt that is discarded, can
+    // select it.
     for row in (0..m).rev() {
         let mut finished: u8 = 0;
         let col_upper_bound = (row + 32 / (m - row)).min(ko);
+        debug_assert!(row <= col_upper_bound && col_upper_bound < a_cols);
 
         for col in row..=col_upper_bound {
             // Constant-time check if this is the pivot column
             let correct_column = ct_compare_8(a[row * a_cols + col], 0) & !finished;
 
-            let u = correct_column & a[row * a_cols + a_cols - 1];
+            // Select the right-hand side only for the first nonzero column;
+            // every other column contributes zero.
+            let u = ct_select_u8(correct_column, a[row * a_cols + a_cols - 1], 0);
             x[col] ^= u;
 
             // Update rows above
@@ -94,3 +112,202 @@ pub(crate) fn sample_solution(args: SampleSolutionArgs<'_>) -> bool {
 
     full_rank != 0
 }
+
+/// How sampling a solution ended, from
+/// [`SignStages::sample_solution_detailed`](crate::internals::SignStages::sample_solution_detailed).
+#[cfg(any(test, feature = "internals"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SampleOutcome {
+    /// `A` had full rank `m`; `x` holds a solution.
+    Solved,
+    /// The last row of `A` was all zero after echelon form, so the attempt
+    /// was discarded.
+    RankDeficient {
+        /// Number of nonzero rows of the echelon form, below `m`.
+        rank: usize,
+    },
+}
+
+/// [`sample_solution`], reporting the rank of `A` when it is deficient.
+///
+/// The rank is counted only for a failed attempt, whose values are thrown
+/// away, and outside the constant-time part, so this is for diagnostics;
+/// signing keeps to the bool.
+#[cfg(any(test, feature = "internals"))]
+pub(crate) fn sample_solution_detailed(args: SampleSolutionArgs<'_>) -> SampleOutcome {
+    let SampleSolutionArgs {
+        a,
+        y,
+        x,
+        k,
+        o,
+        m,
+        a_cols,
+    } = args;
+    let solved = sample_solution(SampleSolutionArgs {
+        a: &mut *a,
+        y,
+        x,
+        k,
+        o,
+        m,
+        a_cols,
+    });
+    if solved {
+        return SampleOutcome::Solved;
+    }
+    // Echelon form moves the zero rows to the bottom.
+    let rank = a
+        .chunks_exact(a_cols)
+        .take(m)
+        .take_while(|row| row[..a_cols - 1].iter().any(|&e| e != 0))
+        .count();
+    SampleOutcome::RankDeficient { rank }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const K: usize = 2;
+    const O: usize = 3;
+    const M: usize = 4;
+    const A_COLS: usize = K * O + 1;
+
+    /// An `M x A_COLS` matrix whose first `rank` rows are `e_i`, the rest
+    /// zero.
+    fn diagonal(rank: usize) -> Vec<u8> {
+        let mut a = vec![0u8; M * A_COLS];
+        for i in 0..rank {
+            a[i * A_COLS + i] = 1;
+        }
+        a
+    }
+
+    fn sample(a: &mut [u8], y: &[u8], x: &mut [u8]) -> SampleOutcome {
+        sample_solution_detailed(SampleSolutionArgs {
+            a,
+            y,
+            x,
+            k: K,
+            o: O,
+            m: M,
+            a_cols: A_COLS,
+        })
+    }
+
+    #[test]
+    fn full_rank_is_solved() {
+        let y = [3u8, 5, 7, 9];
+        let mut x = [0u8; A_COLS];
+        assert_eq!(sample(&mut diagonal(M), &y, &mut x), SampleOutcome::Solved);
+        assert_eq!(x[..M], y);
+    }
+
+    #[test]
+    fn rank_deficiency_is_reported() {
+        for rank in 0..M {
+            let mut x = [0u8; A_COLS];
+            assert_eq!(
+                sample(&mut diagonal(rank), &[1u8; M], &mut x),
+                SampleOutcome::RankDeficient { rank }
+            );
+        }
+    }
+
+    /// Solve with the real dimensions of `P` for a system whose last two
+    /// pivots sit as far right as full rank allows, at `ko - 2` and `ko - 1`,
+    /// so the last row's window reaches the right-hand side column.
+    fn last_rows_use_full_window<P: crate::MayoParameter>() {
+        let (m, ko, a_cols) = (P::M, P::K * P::O, P::A_COLS);
+        let pivots: Vec<usize> = (0..m - 2).chain([ko - 2, ko - 1]).collect();
+        let mut a = vec![0u8; m * a_cols];
+        for (row, &col) in pivots.iter().enumerate() {
+            a[row * a_cols + col] = 1;
+            // Something to eliminate in the rows above.
+            if row > 0 {
+                a[(row - 1) * a_cols + col] = 5;
+            }
+        }
+        let original = a.clone();
+        let y: Vec<u8> = (0..m)
+            .map(|i| u8::try_from(i % 15 + 1).expect("below 16"))
+            .collect();
+        let mut x: Vec<u8> = (0..a_cols)
+            .map(|i| u8::try_from(i % 7).expect("below 16"))
+            .collect();
+        x[ko] = 0;
+
+        assert!(sample_solution(SampleSolutionArgs {
+            a: &mut a,
+            y: &y,
+            x: &mut x,
+            k: P::K,
+            o: P::O,
+            m,
+            a_cols,
+        }));
+        assert_eq!(x[ko], 0, "{}: sentinel changed", P::NAME);
+        let mut ax = vec![0u8; m];
+        mat_mul(&original, &x, &mut ax, a_cols, m, 1);
+        assert_eq!(ax, y, "{}", P::NAME);
+    }
+
+    #[test]
+    fn last_rows_use_full_window_all_params() {
+        last_rows_use_full_window::<crate::Mayo1>();
+        last_rows_use_full_window::<crate::Mayo3>();
+        last_rows_use_full_window::<crate::Mayo5>();
+    }
+
+    /// MAYO_2 has `ko = m`, so every pivot is on the diagonal and the last
+    /// row's window is clamped to `ko`.
+    #[test]
+    fn square_system_last_row() {
+        use crate::MayoParameter;
+        type P = crate::Mayo2;
+        let (m, a_cols) = (P::M, P::A_COLS);
+        let mut a = vec![0u8; m * a_cols];
+        for row in 0..m {
+            a[row * a_cols + row] = 1;
+            a[row * a_cols + (row + 1) % m] ^= 3;
+        }
+        let original = a.clone();
+        let y = vec![9u8; m];
+        let mut x = vec![0u8; a_cols];
+        let solved = sample_solution(SampleSolutionArgs {
+            a: &mut a,
+            y: &y,
+            x: &mut x,
+            k: P::K,
+            o: P::O,
+            m,
+            a_cols,
+        });
+        assert!(solved);
+        let mut ax = vec![0u8; m];
+        mat_mul(&original, &x, &mut ax, a_cols, m, 1);
+        assert_eq!(ax, y);
+    }
+
+    #[test]
+    fn detailed_agrees_with_bool() {
+        let y = [1u8, 2, 3, 4];
+        for rank in 0..=M {
+            let (mut a1, mut x1) = (diagonal(rank), [0u8; A_COLS]);
+            let (mut a2, mut x2) = (diagonal(rank), [0u8; A_COLS]);
+            let solved = sample_solution(SampleSolutionArgs {
+                a: &mut a1,
+                y: &y,
+                x: &mut x1,
+                k: K,
+                o: O,
+                m: M,
+                a_cols: A_COLS,
+            });
+            let outcome = sample(&mut a2, &y, &mut x2);
+            assert_eq!(solved, outcome == SampleOutcome::Solved);
+            assert_eq!((a1, x1), (a2, x2));
+        }
+    }
+}
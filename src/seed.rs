@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Typed MAYO secret seed.
+
+use crate::error::Error;
+use crate::params::MayoParameter;
+use hybrid_array::Array;
+use rand::CryptoRng;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// A secret seed of exactly `P::SK_SEED_BYTES` bytes.
+///
+/// Converting into a [`KeyPair`](crate::KeyPair) cannot fail, unlike
+/// [`KeyPair::from_seed`](crate::KeyPair::from_seed). The length is part of
+/// the type, so a seed of the wrong size is rejected when the `Seed` is built
+/// rather than when the key is derived. The bytes are zeroized on drop.
+#[derive(Clone)]
+pub struct Seed<P: MayoParameter> {
+    pub(crate) bytes: Array<u8, P::CskSize>,
+}
+
+impl<P: MayoParameter> Seed<P> {
+    /// Draw a fresh seed from `rng`.
+    pub fn generate(rng: &mut impl CryptoRng) -> Self {
+        let mut bytes = Array::<u8, P::CskSize>::default();
+        rng.fill_bytes(&mut bytes);
+        Self { bytes }
+    }
+}
+
+impl<P: MayoParameter> From<Array<u8, P::CskSize>> for Seed<P> {
+    fn from(bytes: Array<u8, P::CskSize>) -> Self {
+        Self { bytes }
+    }
+}
+
+impl<P: MayoParameter> TryFrom<&[u8]> for Seed<P> {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let bytes =
+            Array::<u8, P::CskSize>::try_from(bytes).map_err(|_e| Error::InvalidSeedLength {
+                expected: P::SK_SEED_BYTES,
+                got: bytes.len(),
+            })?;
+        Ok(Self { bytes })
+    }
+}
+
+impl<P: MayoParameter> AsRef<[u8]> for Seed<P> {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<P: MayoParameter> PartialEq for Seed<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl<P: MayoParameter> Eq for Seed<P> {}
+
+impl<P: MayoParameter> Zeroize for Seed<P> {
+    fn zeroize(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl<P: MayoParameter> Drop for Seed<P> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<P: MayoParameter> ZeroizeOnDrop for Seed<P> {}
+
+impl<P: MayoParameter> core::fmt::Debug for Seed<P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Seed")
+            .field("variant", &P::NAME)
+            .field("bytes", &"**FILTERED**")
+            .finish()
+    }
+}
@@ -2,20 +2,25 @@
 
 //! MAYO signature generation.
 
-use crate::codec::{decode, encode};
+use crate::codec::{decode, encode, pack_m_vecs};
+use crate::digester::{Digester, XofDigester};
 use crate::error::{Error, Result};
 use crate::gf16::{add_f, mul_f};
-use crate::keygen::expand_p1_p2;
-use crate::matrix_ops::{compute_m_and_vpv, compute_p3, m_upper, p1p1t_times_o};
-use crate::params::{F_TAIL_LEN, MAX_M_VEC_LIMBS, MayoParameter};
+use crate::keygen::expand_secret_components_into;
+use crate::matrix_ops::{
+    P3Input, compute_m_and_vpv, compute_p3, compute_rhs, m_upper, p1p1t_times_o,
+};
+use crate::params::{F_TAIL_LEN, MayoParameter};
 use crate::sample::{SampleSolutionArgs, sample_solution};
-use crate::verify::mayo_verify_with_split_pk;
-use rand::CryptoRng;
-use shake::Shake256;
-use shake::digest::{ExtendableOutput, Update, XofReader};
-use zeroize::Zeroizing;
+use crate::verify::{VerifyBuffers, mayo_verify_split_with_scratch};
+use crate::workspace::Carve;
+use crate::xof::Xof;
+use core::marker::PhantomData;
+use subtle::{Choice, ConditionallySelectable};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
 /// Expanded secret-key material produced by [`expand_sk`].
+#[derive(Clone)]
 pub(crate) struct ExpandedSecretKey {
     /// `P1 ‖ L`, where `L = (P1 + P1^t)*O + P2`, in bitsliced `u64` limbs.
     /// Secret: `L` encodes the oil subspace `O`.
@@ -28,45 +33,165 @@ pub(crate) struct ExpandedSecretKey {
 }
 
 /// Expand a compact secret key into P1, L (=(P1+P1^t)*O + P2), P2, and O.
-pub(crate) fn expand_sk<P: MayoParameter>(csk: &[u8]) -> ExpandedSecretKey {
-    let param_o = P::O;
-    let param_v = P::V;
-    let param_o_bytes = P::O_BYTES;
-    let param_pk_seed_bytes = P::PK_SEED_BYTES;
-    let param_sk_seed_bytes = P::SK_SEED_BYTES;
-
-    let seed_sk = &csk[..param_sk_seed_bytes];
-
-    // S = SHAKE256(seed_sk) -> pk_seed || O_bytes
-    let mut s = Zeroizing::new(vec![0u8; param_pk_seed_bytes + param_o_bytes]);
-    let mut hasher = Shake256::default();
-    hasher.update(seed_sk);
-    let mut reader = hasher.finalize_xof();
-    reader.read(&mut s);
-
-    // Decode O
-    let mut o = Zeroizing::new(vec![0u8; param_v * param_o]);
-    decode(&s[param_pk_seed_bytes..], &mut o, param_v * param_o);
+pub(crate) fn expand_sk<P: MayoParameter, X: Xof>(csk: &[u8]) -> Result<ExpandedSecretKey> {
+    // P1 || L is Zeroizing because L encodes secret O
+    let mut p1_l = Zeroizing::new(vec![0u64; P::P1_LIMBS + P::P2_LIMBS]);
+    let mut p2 = vec![0u64; P::P2_LIMBS];
+    let mut o = Zeroizing::new(vec![0u8; P::V * P::O]);
+    let mut s = Zeroizing::new(vec![0u8; P::PK_SEED_BYTES + P::O_BYTES]);
+    expand_sk_into::<P, X>(csk, &mut p1_l, &mut p2, &mut o, &mut s)?;
+    Ok(ExpandedSecretKey { p1_l, p2, o })
+}
 
-    // Expand P1 and P2; wrap in Zeroizing because L encodes secret O
-    let mut p = Zeroizing::new(expand_p1_p2::<P>(&s[..param_pk_seed_bytes]));
+/// [`expand_sk`] into caller-provided buffers of exactly `P1_LIMBS +
+/// P2_LIMBS`, `P2_LIMBS` and `V * O` entries. `s` is working space for
+/// `seed_pk || O_bytes` and is left holding it.
+pub(crate) fn expand_sk_into<P: MayoParameter, X: Xof>(
+    csk: &[u8],
+    p1_l: &mut [u64],
+    p2: &mut [u64],
+    o: &mut [u8],
+    s: &mut [u8],
+) -> Result<()> {
+    expand_secret_components_into::<P, X>(csk, s, o, p1_l)?;
 
     // Save the public P2 before it is overwritten by L. The verify-after-sign
     // fault check reuses it to recompute P3, avoiding a second AES expansion.
-    let p2 = p[P::P1_LIMBS..].to_vec();
+    p2.copy_from_slice(&p1_l[P::P1_LIMBS..]);
 
     // Compute L = (P1 + P1^t)*O + P2
     // L replaces P2 in memory
-    {
-        let (p1, l) = p.split_at_mut(P::P1_LIMBS);
-        p1p1t_times_o::<P>(p1, &o, l);
+    let (p1, l) = p1_l.split_at_mut(P::P1_LIMBS);
+    p1p1t_times_o::<P>(p1, o, l);
+    Ok(())
+}
+
+impl ExpandedSecretKey {
+    /// Borrow the expanded material for signing.
+    pub(crate) fn parts(&self) -> SecretKeyParts<'_> {
+        SecretKeyParts {
+            p1_l: &self.p1_l,
+            p2: &self.p2,
+            o: &self.o,
+        }
     }
+}
+
+/// Borrowed expanded secret-key material, laid out as in
+/// [`ExpandedSecretKey`] but living in an owned key or a workspace.
+#[derive(Clone, Copy)]
+pub(crate) struct SecretKeyParts<'a> {
+    pub(crate) p1_l: &'a [u64],
+    pub(crate) p2: &'a [u64],
+    pub(crate) o: &'a [u8],
+}
+
+impl Zeroize for ExpandedSecretKey {
+    fn zeroize(&mut self) {
+        self.p1_l.zeroize();
+        self.p2.zeroize();
+        self.o.zeroize();
+    }
+}
 
-    ExpandedSecretKey { p1_l: p, p2, o }
+/// Pack expanded secret-key material as `seed_sk || O || P1 || L`.
+///
+/// This is the reference implementation's expanded secret key layout: `O` is
+/// nibble-encoded and `P1`/`L` are packed m-vectors. `out` must be
+/// `ESK_BYTES` long.
+pub(crate) fn pack_expanded_sk<P: MayoParameter>(
+    csk: &[u8],
+    p1_l: &[u64],
+    o: &[u8],
+    out: &mut [u8],
+) {
+    let m_vec_limbs = P::M_VEC_LIMBS;
+    let param_sk_seed_bytes = P::SK_SEED_BYTES;
+    let o_end = param_sk_seed_bytes + P::O_BYTES;
+    let p1_end = o_end + P::P1_BYTES;
+
+    out[..param_sk_seed_bytes].copy_from_slice(&csk[..param_sk_seed_bytes]);
+    encode(o, &mut out[param_sk_seed_bytes..o_end], P::V * P::O);
+    pack_m_vecs(
+        &p1_l[..P::P1_LIMBS],
+        &mut out[o_end..p1_end],
+        P::P1_LIMBS / m_vec_limbs,
+        P::M,
+    );
+    pack_m_vecs(
+        &p1_l[P::P1_LIMBS..],
+        &mut out[p1_end..],
+        P::P2_LIMBS / m_vec_limbs,
+        P::M,
+    );
 }
 
 /// Transpose a 16x16 matrix of nibbles packed in 16 u64 values.
+#[cfg(not(target_arch = "aarch64"))]
+#[inline]
+fn transpose_16x16_nibbles(m: &mut [u64]) {
+    transpose_16x16_nibbles_scalar(m);
+}
+
+/// Transpose a 16x16 matrix of nibbles packed in 16 u64 values (NEON).
+///
+/// Same four swap stages as the scalar path, two limbs per 128-bit register.
+/// The nibble stage pairs adjacent limbs, so `vld2q_u64` de-interleaves even
+/// and odd limbs into separate registers; the later stages pair limbs that are
+/// 2, 4, and 8 apart, which are plain contiguous loads.
+#[cfg(target_arch = "aarch64")]
+#[inline]
 fn transpose_16x16_nibbles(m: &mut [u64]) {
+    use std::arch::aarch64::*;
+
+    let m = &mut m[..16];
+    // SAFETY: NEON is baseline on aarch64; the reslice above guarantees 16
+    // limbs, and every load/store below touches limbs 0..16 only.
+    unsafe {
+        let ptr = m.as_mut_ptr();
+
+        let mask = vdupq_n_u64(0x0f0f0f0f0f0f0f0f);
+        for i in (0..16).step_by(4) {
+            let v = vld2q_u64(ptr.add(i));
+            let t = vandq_u64(veorq_u64(vshrq_n_u64::<4>(v.0), v.1), mask);
+            let a = veorq_u64(v.0, vshlq_n_u64::<4>(t));
+            let b = veorq_u64(v.1, t);
+            vst2q_u64(ptr.add(i), uint64x2x2_t(a, b));
+        }
+
+        let mask = vdupq_n_u64(0x00ff00ff00ff00ff);
+        for i in (0..16).step_by(4) {
+            let a = vld1q_u64(ptr.add(i));
+            let b = vld1q_u64(ptr.add(i + 2));
+            let t = vandq_u64(veorq_u64(vshrq_n_u64::<8>(a), b), mask);
+            vst1q_u64(ptr.add(i), veorq_u64(a, vshlq_n_u64::<8>(t)));
+            vst1q_u64(ptr.add(i + 2), veorq_u64(b, t));
+        }
+
+        let mask = vdupq_n_u64(0x0000ffff0000ffff);
+        for i in [0, 2, 8, 10] {
+            let a = vld1q_u64(ptr.add(i));
+            let b = vld1q_u64(ptr.add(i + 4));
+            let t = vandq_u64(veorq_u64(vshrq_n_u64::<16>(a), b), mask);
+            vst1q_u64(ptr.add(i), veorq_u64(a, vshlq_n_u64::<16>(t)));
+            vst1q_u64(ptr.add(i + 4), veorq_u64(b, t));
+        }
+
+        let mask = vdupq_n_u64(0x00000000ffffffff);
+        for i in (0..8).step_by(2) {
+            let a = vld1q_u64(ptr.add(i));
+            let b = vld1q_u64(ptr.add(i + 8));
+            let t = vandq_u64(veorq_u64(vshrq_n_u64::<32>(a), b), mask);
+            vst1q_u64(ptr.add(i), veorq_u64(a, vshlq_n_u64::<32>(t)));
+            vst1q_u64(ptr.add(i + 8), veorq_u64(b, t));
+        }
+    }
+}
+
+/// Scalar 16x16 nibble transpose: the portable path, and the reference the
+/// NEON path is tested against.
+#[cfg(any(test, not(target_arch = "aarch64")))]
+fn transpose_16x16_nibbles_scalar(m: &mut [u64]) {
     let even_nibbles: u64 = 0x0f0f0f0f0f0f0f0f;
     let even_bytes: u64 = 0x00ff00ff00ff00ff;
     let even_2bytes: u64 = 0x0000ffff0000ffff;
@@ -107,77 +232,8 @@ fn transpose_16x16_nibbles(m: &mut [u64]) {
     }
 }
 
-/// Compute the right-hand side: y = t XOR reduce(vPv).
-pub(crate) fn compute_rhs<P: MayoParameter>(vpv: &mut [u64], t: &[u8], y: &mut [u8]) {
-    let m_vec_limbs = P::M_VEC_LIMBS;
-    let param_m = P::M;
-    let param_k = P::K;
-    let f_tail = P::F_TAIL;
-
-    let top_pos = ((param_m - 1) % 16) * 4;
-
-    // Zero out tails of m_vecs if necessary
-    if param_m % 16 != 0 {
-        let mut mask: u64 = 1;
-        mask <<= (param_m % 16) * 4;
-        mask -= 1;
-        for i in 0..(param_k * param_k) {
-            vpv[i * m_vec_limbs + m_vec_limbs - 1] &= mask;
-        }
-    }
-
-    let mut temp = [0u64; MAX_M_VEC_LIMBS];
-
-    for i in (0..param_k).rev() {
-        for j in i..param_k {
-            // Multiply by X (shift up 4 bits)
-            let top = ((temp[m_vec_limbs - 1] >> top_pos) % 16) as u8;
-            temp[m_vec_limbs - 1] <<= 4;
-            for k in (0..m_vec_limbs - 1).rev() {
-                temp[k + 1] ^= temp[k] >> 60;
-                temp[k] <<= 4;
-            }
-
-            // Reduce mod f(X)
-            for (jj, &f_coeff) in f_tail.iter().enumerate().take(F_TAIL_LEN) {
-                let product = mul_f(top, f_coeff);
-                if jj % 2 == 0 {
-                    // XOR into the low nibble of byte jj/2
-                    let limb_idx = (jj / 2) / 8;
-                    let byte_idx = (jj / 2) % 8;
-                    temp[limb_idx] ^= u64::from(product) << (byte_idx * 8);
-                } else {
-                    // XOR into the high nibble of byte jj/2
-                    let limb_idx = (jj / 2) / 8;
-                    let byte_idx = (jj / 2) % 8;
-                    temp[limb_idx] ^= u64::from(product) << (byte_idx * 8 + 4);
-                }
-            }
-
-            // Extract from vPv and add
-            let idx_ij = (i * param_k + j) * m_vec_limbs;
-            let idx_ji = (j * param_k + i) * m_vec_limbs;
-            for k in 0..m_vec_limbs {
-                let sym = if i != j { vpv[idx_ji + k] } else { 0 };
-                temp[k] ^= vpv[idx_ij + k] ^ sym;
-            }
-        }
-    }
-
-    // Compute y = t XOR temp (unpacked)
-    for i in (0..param_m).step_by(2) {
-        let limb_idx = (i / 2) / 8;
-        let byte_idx = (i / 2) % 8;
-        let byte_val = ((temp[limb_idx] >> (byte_idx * 8)) & 0xFF) as u8;
-        y[i] = t[i] ^ (byte_val & 0xF);
-        if i + 1 < param_m {
-            y[i + 1] = t[i + 1] ^ (byte_val >> 4);
-        }
-    }
-}
-
 /// Compute the linearized system matrix A from the M matrices (VtL).
-fn compute_a<P: MayoParameter>(vtl: &mut [u64], a: &mut [u64], a_out: &mut [u8]) {
+pub(crate) fn compute_a<P: MayoParameter>(vtl: &mut [u64], a: &mut [u64], a_out: &mut [u8]) {
     let m_vec_limbs = P::M_VEC_LIMBS;
     let param_m = P::M;
     let param_o = P::O;
@@ -188,6 +244,15 @@ fn compute_a<P: MayoParameter>(vtl: &mut [u64], a: &mut [u64], a_out: &mut [u8])
 
     let a_width = (param_o * param_k).div_ceil(16) * 16;
 
+    // The carry out of the top limb after the largest shift still lands in
+    // a word row of A, so every carry below is written, never dropped.
+    const {
+        assert!(
+            P::M_VEC_LIMBS + (P::K * (P::K + 1) / 2 - 1) * 4 / 64 < P::M.div_ceil(8),
+            "A has a word row for every carry"
+        )
+    };
+
     let mut bits_to_shift: usize = 0;
     let mut words_to_shift: usize = 0;
 
@@ -217,11 +282,7 @@ fn compute_a<P: MayoParameter>(vtl: &mut [u64], a: &mut [u64], a_out: &mut [u8])
                     debug_assert!(dst_idx < a_total);
                     a[dst_idx] ^= src << bits_to_shift;
                     if bits_to_shift > 0 {
-                        // Carry into next row; may legitimately overflow at boundary.
-                        let dst_idx2 = param_o * i + c + (k + words_to_shift + 1) * a_width;
-                        if dst_idx2 < a_total {
-                            a[dst_idx2] ^= src >> (64 - bits_to_shift);
-                        }
+                        a[dst_idx + a_width] ^= src >> (64 - bits_to_shift);
                     }
                 }
             }
@@ -235,11 +296,7 @@ fn compute_a<P: MayoParameter>(vtl: &mut [u64], a: &mut [u64], a_out: &mut [u8])
                         debug_assert!(dst_idx < a_total);
                         a[dst_idx] ^= src << bits_to_shift;
                         if bits_to_shift > 0 {
-                            // Carry into next row; may legitimately overflow at boundary.
-                            let dst_idx2 = param_o * j + c + (k + words_to_shift + 1) * a_width;
-                            if dst_idx2 < a_total {
-                                a[dst_idx2] ^= src >> (64 - bits_to_shift);
-                            }
+                            a[dst_idx + a_width] ^= src >> (64 - bits_to_shift);
                         }
                     }
                 }
@@ -331,112 +388,381 @@ fn decode_packed_nibbles(input: &[u8], output: &mut [u8], len: usize) {
     }
 }
 
+/// Reusable working buffers for repeated signing.
+///
+/// Each signature needs a dozen or so buffers sized by the parameter set.
+/// Passing a `SignScratch` to [`SigningKey::sign_with_scratch`] or
+/// [`ExpandedSigningKey::sign_with_scratch`] reuses them instead of allocating
+/// fresh ones per call. Every buffer is zeroized after each signature and
+/// again on drop, so no secret-derived state carries over between messages.
+///
+/// Signing borrows the scratch mutably; use one scratch per thread.
+///
+/// [`SigningKey::sign_with_scratch`]: crate::SigningKey::sign_with_scratch
+/// [`ExpandedSigningKey::sign_with_scratch`]: crate::ExpandedSigningKey::sign_with_scratch
+pub struct SignScratch<P: MayoParameter> {
+    limbs: Vec<u64>,
+    bytes: Vec<u8>,
+    _marker: PhantomData<P>,
+}
+
+impl<P: MayoParameter> SignScratch<P> {
+    /// Allocate zeroed buffers sized for parameter set `P`.
+    pub fn new() -> Self {
+        Self {
+            limbs: vec![0u64; SignBuffers::limbs::<P>()],
+            bytes: vec![0u8; SignBuffers::bytes::<P>()],
+            _marker: PhantomData,
+        }
+    }
+
+    fn buffers(&mut self) -> SignBuffers<'_> {
+        SignBuffers::carve::<P>(
+            &mut Carve::new(&mut self.limbs),
+            &mut Carve::new(&mut self.bytes),
+        )
+    }
+}
+
+impl<P: MayoParameter> Default for SignScratch<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: MayoParameter> Zeroize for SignScratch<P> {
+    fn zeroize(&mut self) {
+        // Clear contents but keep the allocations for the next signature.
+        self.limbs.as_mut_slice().zeroize();
+        self.bytes.as_mut_slice().zeroize();
+    }
+}
+
+impl<P: MayoParameter> Drop for SignScratch<P> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<P: MayoParameter> ZeroizeOnDrop for SignScratch<P> {}
+
+impl<P: MayoParameter> core::fmt::Debug for SignScratch<P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SignScratch")
+            .field("variant", &P::NAME)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The working buffers of one signature, each sized for `P`.
+pub(crate) struct SignBuffers<'a> {
+    tmp: &'a mut [u8],
+    salt: &'a mut [u8],
+    tenc: &'a mut [u8],
+    t: &'a mut [u8],
+    x: &'a mut [u8],
+    s: &'a mut [u8],
+    vdec: &'a mut [u8],
+    x_first: &'a mut [u8],
+    vdec_first: &'a mut [u8],
+    v_and_r: &'a mut [u8],
+    mtmp: &'a mut [u64],
+    vpv: &'a mut [u64],
+    pv: &'a mut [u64],
+    y: &'a mut [u8],
+    a_matrix: &'a mut [u8],
+    a_scratch: &'a mut [u64],
+    p2_work: &'a mut [u64],
+    p3: &'a mut [u64],
+    p3_upper: &'a mut [u64],
+    verify: VerifyBuffers<'a>,
+}
+
+impl<'a> SignBuffers<'a> {
+    /// Number of `u64` limbs [`carve`](Self::carve) takes.
+    pub(crate) const fn limbs<P: MayoParameter>() -> usize {
+        let m_vec_limbs = P::M_VEC_LIMBS;
+        let a_width = (P::O * P::K).div_ceil(16) * 16;
+        P::K * P::O * m_vec_limbs
+            + P::K * P::K * m_vec_limbs
+            + P::V * P::K * m_vec_limbs
+            + a_width * P::M.div_ceil(8)
+            + P::P2_LIMBS
+            + P::O * P::O * m_vec_limbs
+            + P::P3_LIMBS
+            + VerifyBuffers::limbs::<P>()
+    }
+
+    /// Number of bytes [`carve`](Self::carve) takes.
+    pub(crate) const fn bytes<P: MayoParameter>() -> usize {
+        P::DIGEST_BYTES
+            + 2 * P::SALT_BYTES
+            + P::M_BYTES
+            + P::M
+            + 2 * P::A_COLS
+            + P::K * P::N
+            + 2 * P::V * P::K
+            + P::K * P::V_BYTES
+            + P::R_BYTES
+            + P::M
+            + P::M.div_ceil(8) * 8 * P::A_COLS
+            + VerifyBuffers::bytes::<P>()
+    }
+
+    /// Take the buffers from the front of `limbs` and `bytes`.
+    pub(crate) fn carve<P: MayoParameter>(
+        limbs: &mut Carve<'a, u64>,
+        bytes: &mut Carve<'a, u8>,
+    ) -> Self {
+        let m_vec_limbs = P::M_VEC_LIMBS;
+        let a_width = (P::O * P::K).div_ceil(16) * 16;
+        Self {
+            tmp: bytes.take(P::DIGEST_BYTES + P::SALT_BYTES),
+            salt: bytes.take(P::SALT_BYTES),
+            tenc: bytes.take(P::M_BYTES),
+            t: bytes.take(P::M),
+            x: bytes.take(P::A_COLS),
+            s: bytes.take(P::K * P::N),
+            vdec: bytes.take(P::V * P::K),
+            x_first: bytes.take(P::A_COLS),
+            vdec_first: bytes.take(P::V * P::K),
+            v_and_r: bytes.take(P::K * P::V_BYTES + P::R_BYTES),
+            mtmp: limbs.take(P::K * P::O * m_vec_limbs),
+            vpv: limbs.take(P::K * P::K * m_vec_limbs),
+            pv: limbs.take(P::V * P::K * m_vec_limbs),
+            y: bytes.take(P::M),
+            a_matrix: bytes.take(P::M.div_ceil(8) * 8 * P::A_COLS),
+            a_scratch: limbs.take(a_width * P::M.div_ceil(8)),
+            p2_work: limbs.take(P::P2_LIMBS),
+            p3: limbs.take(P::O * P::O * m_vec_limbs),
+            p3_upper: limbs.take(P::P3_LIMBS),
+            verify: VerifyBuffers::carve::<P>(limbs, bytes),
+        }
+    }
+}
+
+/// Limbs [`mayo_sign_signature_in`] works in: the expanded key, the copy of
+/// P2 and the [`SignBuffers`].
+pub(crate) const fn sign_in_limbs<P: MayoParameter>() -> usize {
+    P::P1_LIMBS + 2 * P::P2_LIMBS + SignBuffers::limbs::<P>()
+}
+
+/// Bytes [`mayo_sign_signature_in`] works in: `O`, the `seed_pk || O_bytes`
+/// expansion and the [`SignBuffers`].
+pub(crate) const fn sign_in_bytes<P: MayoParameter>() -> usize {
+    P::V * P::O + P::PK_SEED_BYTES + P::O_BYTES + SignBuffers::bytes::<P>()
+}
+
 /// Generate a MAYO signature for a message.
 ///
-/// Returns the signature length on success.
-pub(crate) fn mayo_sign_signature<P: MayoParameter>(
+/// `randomizer` fills the `SALT_BYTES`-long randomizer that is hashed into the
+/// salt, e.g. from an RNG or with zeros for deterministic signing.
+///
+/// Returns the counter value whose `(v, r)` yielded a solution, i.e. the
+/// number of sampling attempts minus one.
+///
+/// That count is not constant: whether a candidate `(v, r)` gives a solvable
+/// system depends on the secret-derived vinegar values, and signing stops at
+/// the first one that does. Each attempt costs the same, so the running time
+/// reveals how many attempts were needed, which is an accepted property of
+/// MAYO (the reference implementation behaves the same). Use
+/// [`mayo_sign_signature_fixed_time`] where uniform timing matters more than
+/// throughput.
+pub(crate) fn mayo_sign_signature<P: MayoParameter, X: Xof>(
+    sig: &mut [u8],
+    msg: &[u8],
+    csk: &[u8],
+    randomizer: impl FnOnce(&mut [u8]),
+    scratch: &mut SignScratch<P>,
+) -> Result<u8> {
+    check_sig_len::<P>(sig)?;
+    let esk = expand_sk::<P, X>(csk)?;
+    mayo_sign_signature_with_expanded_sk::<P, X, XofDigester<X>>(
+        sig, msg, csk, &esk, randomizer, scratch,
+    )
+}
+
+pub(crate) fn mayo_sign_signature_with_expanded_sk<P: MayoParameter, X: Xof, D: Digester>(
+    sig: &mut [u8],
+    msg: &[u8],
+    csk: &[u8],
+    esk: &ExpandedSecretKey,
+    randomizer: impl FnOnce(&mut [u8]),
+    scratch: &mut SignScratch<P>,
+) -> Result<u8> {
+    check_sig_len::<P>(sig)?;
+    let result = sign_with_buffers::<P, X, D, false>(
+        sig,
+        msg,
+        csk,
+        esk.parts(),
+        randomizer,
+        scratch.buffers(),
+    );
+    scratch.zeroize();
+    result
+}
+
+/// [`mayo_sign_signature`] working only in `limbs` and `bytes`, which must
+/// hold at least [`sign_in_limbs`] limbs and [`sign_in_bytes`] bytes. Both
+/// are zeroized before returning.
+pub(crate) fn mayo_sign_signature_in<P: MayoParameter, X: Xof>(
+    sig: &mut [u8],
+    msg: &[u8],
+    csk: &[u8],
+    randomizer: impl FnOnce(&mut [u8]),
+    limbs: &mut [u64],
+    bytes: &mut [u8],
+) -> Result<u8> {
+    check_sig_len::<P>(sig)?;
+    let result = sign_in::<P, X>(sig, msg, csk, randomizer, limbs, bytes);
+    limbs.zeroize();
+    bytes.zeroize();
+    result
+}
+
+fn sign_in<P: MayoParameter, X: Xof>(
+    sig: &mut [u8],
+    msg: &[u8],
+    csk: &[u8],
+    randomizer: impl FnOnce(&mut [u8]),
+    limbs: &mut [u64],
+    bytes: &mut [u8],
+) -> Result<u8> {
+    let mut limbs = Carve::new(limbs);
+    let mut bytes = Carve::new(bytes);
+    let p1_l = limbs.take(P::P1_LIMBS + P::P2_LIMBS);
+    let p2 = limbs.take(P::P2_LIMBS);
+    let o = bytes.take(P::V * P::O);
+    let seed = bytes.take(P::PK_SEED_BYTES + P::O_BYTES);
+    expand_sk_into::<P, X>(csk, p1_l, p2, o, seed)?;
+    let key = SecretKeyParts { p1_l, p2, o };
+    let buffers = SignBuffers::carve::<P>(&mut limbs, &mut bytes);
+    sign_with_buffers::<P, X, XofDigester<X>, false>(sig, msg, csk, key, randomizer, buffers)
+}
+
+/// [`mayo_sign_signature`], but always running all 256 sampling attempts.
+///
+/// The first solvable candidate is kept with constant-time selects instead of
+/// a break, so the loop does the same work whichever attempt succeeds. The
+/// signature is identical to [`mayo_sign_signature`]'s for the same
+/// randomizer, at up to 256 times the cost.
+pub(crate) fn mayo_sign_signature_fixed_time<P: MayoParameter, X: Xof>(
     sig: &mut [u8],
     msg: &[u8],
     csk: &[u8],
-    rng: &mut impl CryptoRng,
-) -> Result<usize> {
-    let esk = expand_sk::<P>(csk);
-    mayo_sign_signature_with_expanded_sk::<P>(sig, msg, csk, &esk.p1_l, &esk.p2, &esk.o, rng)
+    randomizer: impl FnOnce(&mut [u8]),
+    scratch: &mut SignScratch<P>,
+) -> Result<u8> {
+    check_sig_len::<P>(sig)?;
+    let esk = expand_sk::<P, X>(csk)?;
+    let result = sign_with_buffers::<P, X, XofDigester<X>, true>(
+        sig,
+        msg,
+        csk,
+        esk.parts(),
+        randomizer,
+        scratch.buffers(),
+    );
+    scratch.zeroize();
+    result
 }
 
-pub(crate) fn mayo_sign_signature_with_expanded_sk<P: MayoParameter>(
+/// Reject a signature buffer that is not exactly `SIG_BYTES` long, before
+/// any work is done, instead of panicking on the final writes.
+fn check_sig_len<P: MayoParameter>(sig: &[u8]) -> Result<()> {
+    if sig.len() != P::SIG_BYTES {
+        return Err(Error::InvalidSignatureLength {
+            expected: P::SIG_BYTES,
+            got: sig.len(),
+        });
+    }
+    Ok(())
+}
+
+fn sign_with_buffers<P: MayoParameter, X: Xof, D: Digester, const FIXED_TIME: bool>(
     sig: &mut [u8],
     msg: &[u8],
     csk: &[u8],
-    p: &[u64],
-    p2: &[u64],
-    o_mat: &[u8],
-    rng: &mut impl CryptoRng,
-) -> Result<usize> {
+    esk: SecretKeyParts<'_>,
+    randomizer: impl FnOnce(&mut [u8]),
+    scratch: SignBuffers<'_>,
+) -> Result<u8> {
     let param_m = P::M;
     let param_n = P::N;
     let param_o = P::O;
     let param_k = P::K;
     let param_v = P::V;
-    let param_m_bytes = P::M_BYTES;
     let param_v_bytes = P::V_BYTES;
-    let param_r_bytes = P::R_BYTES;
     let param_sig_bytes = P::SIG_BYTES;
     let param_a_cols = P::A_COLS;
     let param_digest_bytes = P::DIGEST_BYTES;
     let param_sk_seed_bytes = P::SK_SEED_BYTES;
     let param_salt_bytes = P::SALT_BYTES;
+    let SignBuffers {
+        tmp,
+        salt,
+        tenc,
+        t,
+        x,
+        s,
+        vdec,
+        x_first,
+        vdec_first,
+        v_and_r,
+        mtmp,
+        vpv,
+        pv,
+        y,
+        a_matrix,
+        a_scratch,
+        p2_work,
+        p3,
+        p3_upper,
+        mut verify,
+    } = scratch;
 
     let seed_sk = &csk[..param_sk_seed_bytes];
+    let o_mat: &[u8] = esk.o;
 
-    let p1 = &p[..P::P1_LIMBS];
-    let l = &p[P::P1_LIMBS..];
+    let p1 = &esk.p1_l[..P::P1_LIMBS];
+    let l = &esk.p1_l[P::P1_LIMBS..];
 
     // Hash message
-    let mut tmp = Zeroizing::new(vec![0u8; param_digest_bytes + param_salt_bytes]);
-    {
-        let mut hasher = Shake256::default();
-        hasher.update(msg);
-        let mut reader = hasher.finalize_xof();
-        reader.read(&mut tmp[..param_digest_bytes]);
-    }
+    D::digest(msg, &mut tmp[..param_digest_bytes]);
 
     // Choose randomizer
-    rng.fill_bytes(&mut tmp[param_digest_bytes..param_digest_bytes + param_salt_bytes]);
+    randomizer(&mut tmp[param_digest_bytes..param_digest_bytes + param_salt_bytes]);
 
     // Compute salt = SHAKE256(digest || random || seed_sk)
     // Absorb seed_sk directly from its source instead of copying into a shared
     // buffer, preventing fault attacks that skip the copy (Section 9.2,
     // "MAYO Key Recovery by Fixing Vinegar Seeds", Jendral & Dubrova 2024).
-    let mut salt = Zeroizing::new(vec![0u8; param_salt_bytes]);
-    {
-        let mut hasher = Shake256::default();
-        hasher.update(&tmp[..param_digest_bytes + param_salt_bytes]);
-        hasher.update(seed_sk);
-        let mut reader = hasher.finalize_xof();
-        reader.read(&mut salt);
-    }
+    X::hash(
+        &[&tmp[..param_digest_bytes + param_salt_bytes], seed_sk],
+        salt,
+    );
 
     // Compute t = SHAKE256(digest || salt)
-    let mut tenc = vec![0u8; param_m_bytes];
-    let mut t = vec![0u8; param_m];
-    tmp[param_digest_bytes..param_digest_bytes + param_salt_bytes].copy_from_slice(&salt);
-    {
-        let mut hasher = Shake256::default();
-        hasher.update(&tmp[..param_digest_bytes + param_salt_bytes]);
-        let mut reader = hasher.finalize_xof();
-        reader.read(&mut tenc);
-    }
-    decode(&tenc, &mut t, param_m);
-
-    let mut x = Zeroizing::new(vec![0u8; param_a_cols]);
-    let mut s = vec![0u8; param_k * param_n];
-    let mut vdec = Zeroizing::new(vec![0u8; param_v * param_k]);
-
-    // Pre-allocate buffers reused across retry iterations
-    let m_vec_limbs = P::M_VEC_LIMBS;
-    let mut v_and_r = Zeroizing::new(vec![0u8; param_k * param_v_bytes + param_r_bytes]);
-    let mut mtmp = Zeroizing::new(vec![0u64; param_k * param_o * m_vec_limbs]);
-    let mut vpv = Zeroizing::new(vec![0u64; param_k * param_k * m_vec_limbs]);
-    let mut pv = Zeroizing::new(vec![0u64; param_v * param_k * m_vec_limbs]);
-    let mut y = vec![0u8; param_m];
-    let a_row_size = param_m.div_ceil(8) * 8;
-    let mut a_matrix = Zeroizing::new(vec![0u8; a_row_size * param_a_cols]);
-    let a_width = (param_o * param_k).div_ceil(16) * 16;
-    let mut a_scratch = vec![0u64; a_width * param_m.div_ceil(8)];
+    tmp[param_digest_bytes..param_digest_bytes + param_salt_bytes].copy_from_slice(salt);
+    X::hash(&[&tmp[..param_digest_bytes + param_salt_bytes]], tenc);
+    decode(tenc, t, param_m);
 
+    let mut solved_ctr = None;
+    let mut found = Choice::from(0);
+    let mut first_ctr = 0u8;
     for ctr in 0..=255u8 {
         // Generate V and r using incremental hashing.
         // Absorb seed_sk directly from its source to prevent fault attacks
         // on SHAKE256 (Sections 6.1-6.3, Jendral & Dubrova 2024).
-        {
-            let mut hasher = Shake256::default();
-            hasher.update(&tmp[..param_digest_bytes + param_salt_bytes]);
-            hasher.update(seed_sk);
-            hasher.update(&[ctr]);
-            let mut reader = hasher.finalize_xof();
-            reader.read(&mut v_and_r);
-        }
+        X::hash(
+            &[
+                &tmp[..param_digest_bytes + param_salt_bytes],
+                seed_sk,
+                &[ctr],
+            ],
+            v_and_r,
+        );
 
         // Decode the v_i vectors
         for i in 0..param_k {
@@ -450,15 +776,15 @@ pub(crate) fn mayo_sign_signature_with_expanded_sk<P: MayoParameter>(
         // Compute M matrices and vPv
         mtmp.fill(0);
         vpv.fill(0);
-        compute_m_and_vpv::<P>(&vdec, l, p1, &mut mtmp, &mut vpv, &mut pv);
+        compute_m_and_vpv::<P>(vdec, l, p1, mtmp, vpv, pv);
 
         // Compute y = t XOR reduce(vPv)
         y.fill(0);
-        compute_rhs::<P>(&mut vpv, &t, &mut y);
+        compute_rhs::<P>(vpv, t, y);
 
         // Compute the linearized system A
         a_matrix.fill(0);
-        compute_a::<P>(&mut mtmp, &mut a_scratch, &mut a_matrix);
+        compute_a::<P>(mtmp, a_scratch, a_matrix);
 
         // Clear last column
         for i in 0..param_m {
@@ -466,25 +792,46 @@ pub(crate) fn mayo_sign_signature_with_expanded_sk<P: MayoParameter>(
         }
 
         // Decode r directly into x; sample_solution updates it in place.
+        // r is the last R_BYTES of v_and_r and fills all but the last
+        // entry of x.
+        debug_assert_eq!(v_and_r.len(), param_k * param_v_bytes + P::R_BYTES);
+        debug_assert_eq!(P::R_BYTES, (param_k * param_o).div_ceil(2));
+        debug_assert_eq!(x.len(), param_k * param_o + 1);
         x.fill(0);
-        decode(
-            &v_and_r[param_k * param_v_bytes..],
-            &mut x,
-            param_k * param_o,
-        );
+        decode(&v_and_r[param_k * param_v_bytes..], x, param_k * param_o);
 
-        if sample_solution(SampleSolutionArgs {
-            a: &mut a_matrix,
-            y: &y,
-            x: &mut x,
+        let solved = sample_solution(SampleSolutionArgs {
+            a: a_matrix,
+            y,
+            x,
             k: param_k,
             o: param_o,
             m: param_m,
             a_cols: param_a_cols,
-        }) {
+        });
+        if FIXED_TIME {
+            // Keep the first solution without branching on which attempt it
+            // came from; later attempts run in full and are discarded.
+            let take = Choice::from(u8::from(solved)) & !found;
+            for (dst, src) in x_first.iter_mut().zip(x.iter()) {
+                dst.conditional_assign(src, take);
+            }
+            for (dst, src) in vdec_first.iter_mut().zip(vdec.iter()) {
+                dst.conditional_assign(src, take);
+            }
+            first_ctr.conditional_assign(&ctr, take);
+            found |= take;
+        } else if solved {
+            solved_ctr = Some(ctr);
             break;
         }
     }
+    if FIXED_TIME {
+        x.copy_from_slice(x_first);
+        vdec.copy_from_slice(vdec_first);
+        solved_ctr = bool::from(found).then_some(first_ctr);
+    }
+    let ctr = solved_ctr.ok_or(Error::Signing)?;
 
     // Compute s[i] = v[i] + O*x[i]
     for i in 0..param_k {
@@ -501,8 +848,8 @@ pub(crate) fn mayo_sign_signature_with_expanded_sk<P: MayoParameter>(
         si[param_v..param_n].copy_from_slice(xi);
     }
 
-    encode(&s, sig, param_n * param_k);
-    sig[param_sig_bytes - param_salt_bytes..param_sig_bytes].copy_from_slice(&salt);
+    encode(s, sig, param_n * param_k);
+    sig[param_sig_bytes - param_salt_bytes..param_sig_bytes].copy_from_slice(salt);
 
     // Fault-attack countermeasure: verify the signature we just produced against
     // an independently recomputed public map before releasing it, catching faults
@@ -513,15 +860,245 @@ pub(crate) fn mayo_sign_signature_with_expanded_sk<P: MayoParameter>(
     // from the seed. This removes a second AES-CTR expansion (~1/3 of signing
     // time) while still re-deriving the secret-dependent material and running a
     // full public-map verification, so signing faults remain caught.
-    let p1 = &p[..P::P1_LIMBS];
-    let mut p2_work = p2.to_vec();
-    let mut p3 = vec![0u64; param_o * param_o * m_vec_limbs];
-    compute_p3::<P>(p1, &mut p2_work, o_mat, &mut p3);
-    let mut p3_upper = vec![0u64; P::P3_LIMBS];
-    m_upper(m_vec_limbs, &p3, &mut p3_upper, param_o);
-    if mayo_verify_with_split_pk::<P>(msg, sig, p1, p2, &p3_upper).is_err() {
+    p2_work.copy_from_slice(esk.p2);
+    p3.fill(0);
+    compute_p3::<P>(p1, p2_work, o_mat, p3);
+    m_upper(P::M_VEC_LIMBS, p3, p3_upper, param_o);
+    if mayo_verify_split_with_scratch::<P, X, D>(
+        msg,
+        sig,
+        p1,
+        esk.p2,
+        P3Input::Unpacked(p3_upper),
+        &mut verify,
+    )
+    .is_err()
+    {
         return Err(Error::Signing);
     }
 
-    Ok(param_sig_bytes)
+    Ok(ctr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic xorshift fill of `n` u64s.
+    fn fill(seed: u64, n: usize) -> Vec<u64> {
+        let mut s = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15).wrapping_add(1);
+        (0..n)
+            .map(|_| {
+                s ^= s << 13;
+                s ^= s >> 7;
+                s ^= s << 17;
+                s
+            })
+            .collect()
+    }
+
+    fn nibble(m: &[u64], row: usize, col: usize) -> u64 {
+        (m[row] >> (col * 4)) & 0xf
+    }
+
+    /// The scalar path is an actual transpose: nibble (r, c) moves to (c, r).
+    #[test]
+    fn transpose_scalar_is_transpose() {
+        for seed in 0..64u64 {
+            let input = fill(0x7A5 ^ seed, 16);
+            let mut out = input.clone();
+            transpose_16x16_nibbles_scalar(&mut out);
+            for r in 0..16 {
+                for c in 0..16 {
+                    assert_eq!(nibble(&input, r, c), nibble(&out, c, r));
+                }
+            }
+        }
+    }
+
+    /// The dispatched transpose (NEON on aarch64) must match the scalar path
+    /// bit-for-bit on random 16x16 nibble blocks.
+    #[test]
+    fn transpose_matches_scalar() {
+        for seed in 0..256u64 {
+            let input = fill(0x16 ^ seed, 16);
+            let mut expected = input.clone();
+            transpose_16x16_nibbles_scalar(&mut expected);
+            let mut got = input;
+            transpose_16x16_nibbles(&mut got);
+            assert_eq!(expected, got, "transpose != scalar (seed={seed})");
+        }
+    }
+
+    /// A built entry by entry: `sum_{i <= j} z^l (E_ij M_j + E_ji M_i)` over
+    /// `m + k(k+1)/2` rows, the rows from `m` up folded back with
+    /// `z^m = f_tail(z)`. The last column, for `y`, stays zero.
+    fn dense_a<P: MayoParameter>(vtl: &[u64]) -> Vec<u8> {
+        let (m, o, mvl) = (P::M, P::O, P::M_VEC_LIMBS);
+        let rows = m + P::K * (P::K + 1) / 2;
+        let entry = |mat: usize, r: usize, c: usize| {
+            let limbs = &vtl[(mat * o + c) * mvl..];
+            u8::try_from(nibble(limbs, r / 16, r % 16)).expect("a nibble")
+        };
+
+        let mut big = vec![0u8; rows * P::A_COLS];
+        let mut l = 0;
+        for i in 0..P::K {
+            for j in (i..P::K).rev() {
+                for r in 0..m {
+                    for c in 0..o {
+                        big[(r + l) * P::A_COLS + i * o + c] ^= entry(j, r, c);
+                        if i != j {
+                            big[(r + l) * P::A_COLS + j * o + c] ^= entry(i, r, c);
+                        }
+                    }
+                }
+                l += 1;
+            }
+        }
+        for r in (m..rows).rev() {
+            for c in 0..P::A_COLS {
+                let v = big[r * P::A_COLS + c];
+                for (t, &f) in P::F_TAIL.iter().enumerate() {
+                    big[(r - m + t) * P::A_COLS + c] ^= mul_f(v, f);
+                }
+            }
+        }
+        big.truncate(m * P::A_COLS);
+        big
+    }
+
+    /// `compute_a` agrees with [`dense_a`] on random and saturated input,
+    /// the latter setting every bit any shift can carry.
+    fn compute_a_matches_dense<P: MayoParameter>() {
+        let a_width = (P::O * P::K).div_ceil(16) * 16;
+        let limbs = P::K * P::O * P::M_VEC_LIMBS;
+        for vtl in [fill(0xa, limbs), fill(0xb, limbs), vec![u64::MAX; limbs]] {
+            let mut vtl = vtl;
+            let mut a = vec![0u64; a_width * P::M.div_ceil(8)];
+            let mut a_out = vec![0u8; P::M.div_ceil(8) * 8 * P::A_COLS];
+            compute_a::<P>(&mut vtl, &mut a, &mut a_out);
+            // `compute_a` has masked the tails of `vtl` above `m`.
+            assert_eq!(
+                a_out[..P::M * P::A_COLS],
+                dense_a::<P>(&vtl)[..],
+                "{}",
+                P::NAME
+            );
+        }
+    }
+
+    /// The squeezed `v_and_r` buffer splits exactly into `k` vinegar vectors
+    /// and `r`, and `r` decodes into the solution vector without overrunning
+    /// either.
+    fn r_region_in_bounds<P: MayoParameter>() {
+        assert_eq!(P::V_BYTES, P::V.div_ceil(2), "{}", P::NAME);
+        assert_eq!(P::R_BYTES, (P::K * P::O).div_ceil(2), "{}", P::NAME);
+        assert_eq!(P::A_COLS, P::K * P::O + 1, "{}", P::NAME);
+
+        let mut scratch = SignScratch::<P>::new();
+        let buffers = scratch.buffers();
+        assert_eq!(buffers.v_and_r.len(), P::K * P::V_BYTES + P::R_BYTES);
+        assert_eq!(buffers.x.len(), P::A_COLS);
+
+        let v_and_r: Vec<u8> = (0..buffers.v_and_r.len())
+            .map(|i| u8::try_from(i % 256).expect("below 256"))
+            .collect();
+        let r = &v_and_r[P::K * P::V_BYTES..];
+        let mut x = vec![0xffu8; P::A_COLS];
+        decode(r, &mut x, P::K * P::O);
+        assert_eq!(x[P::A_COLS - 1], 0xff, "{}", P::NAME);
+        let last = r[r.len() - 1];
+        if (P::K * P::O) % 2 == 0 {
+            assert_eq!(x[P::K * P::O - 1], last >> 4, "{}", P::NAME);
+        } else {
+            assert_eq!(x[P::K * P::O - 1], last & 0xf, "{}", P::NAME);
+        }
+    }
+
+    #[test]
+    fn r_region_in_bounds_all_params() {
+        r_region_in_bounds::<crate::Mayo1>();
+        r_region_in_bounds::<crate::Mayo2>();
+        r_region_in_bounds::<crate::Mayo3>();
+        r_region_in_bounds::<crate::Mayo5>();
+    }
+
+    /// Running every attempt selects the same solution as stopping at the
+    /// first one.
+    #[test]
+    fn fixed_time_matches_early_exit() {
+        use crate::keygen::mayo_keypair_compact;
+        use crate::xof::Shake256Xof;
+        type P = crate::Mayo1;
+
+        let mut cpk = vec![0u8; P::CPK_BYTES];
+        let mut csk = vec![0u8; P::CSK_BYTES];
+        mayo_keypair_compact::<P, Shake256Xof>(&mut cpk, &mut csk, &mut rand::rng())
+            .expect("keygen");
+        let mut scratch = SignScratch::<P>::new();
+        for msg in [&b"first"[..], b"second", b"third"] {
+            let mut expected = vec![0u8; P::SIG_BYTES];
+            let ctr = mayo_sign_signature::<P, Shake256Xof>(
+                &mut expected,
+                msg,
+                &csk,
+                |r| r.fill(7),
+                &mut scratch,
+            )
+            .expect("sign");
+            let mut got = vec![0u8; P::SIG_BYTES];
+            let fixed_ctr = mayo_sign_signature_fixed_time::<P, Shake256Xof>(
+                &mut got,
+                msg,
+                &csk,
+                |r| r.fill(7),
+                &mut scratch,
+            )
+            .expect("fixed-time sign");
+            assert_eq!(got, expected);
+            assert_eq!(fixed_ctr, ctr);
+        }
+    }
+
+    #[test]
+    fn wrong_sized_signature_buffer_is_rejected() {
+        use crate::keygen::mayo_keypair_compact;
+        use crate::xof::Shake256Xof;
+        type P = crate::Mayo1;
+
+        let mut cpk = vec![0u8; P::CPK_BYTES];
+        let mut csk = vec![0u8; P::CSK_BYTES];
+        mayo_keypair_compact::<P, Shake256Xof>(&mut cpk, &mut csk, &mut rand::rng())
+            .expect("keygen");
+        let mut scratch = SignScratch::<P>::new();
+        for len in [0, P::SIG_BYTES - 1, P::SIG_BYTES + 1] {
+            let mut sig = vec![0u8; len];
+            let result =
+                mayo_sign_signature::<P, Shake256Xof>(&mut sig, b"msg", &csk, |_| {}, &mut scratch);
+            assert!(
+                matches!(
+                    result,
+                    Err(Error::InvalidSignatureLength { expected: P::SIG_BYTES, got }) if got == len
+                ),
+                "len = {len}"
+            );
+            let result = mayo_sign_signature_fixed_time::<P, Shake256Xof>(
+                &mut sig,
+                b"msg",
+                &csk,
+                |_| {},
+                &mut scratch,
+            );
+            assert!(result.is_err_and(|e| e.is_length_error()), "len = {len}");
+        }
+    }
+
+    #[test]
+    fn compute_a_matches_dense_all_params() {
+        compute_a_matches_dense::<crate::Mayo1>();
+        compute_a_matches_dense::<crate::Mayo2>();
+        compute_a_matches_dense::<crate::Mayo3>();
+        compute_a_matches_dense::<crate::Mayo5>();
+    }
 }
@@ -2,15 +2,89 @@
 
 //! MAYO signing key.
 
+use crate::context::{aad_preimage, context_preimage};
+use crate::digester::XofDigester;
+#[cfg(feature = "std")]
+use crate::digester::digest_reader;
+use crate::digester::{MessageDigest, Prehashed};
 use crate::error::Error;
 use crate::keypair::derive_cpk_from_csk;
-use crate::mayo_signature::Signature;
+use crate::mayo_signature::{InlineSignature, Signature};
 use crate::params::MayoParameter;
-use crate::sign::{expand_sk, mayo_sign_signature, mayo_sign_signature_with_expanded_sk};
+use crate::sign::{
+    ExpandedSecretKey, SignScratch, expand_sk, mayo_sign_signature, mayo_sign_signature_fixed_time,
+    mayo_sign_signature_in, mayo_sign_signature_with_expanded_sk, pack_expanded_sk,
+};
+use crate::verify::mayo_verify;
+use crate::workspace::Workspace;
+use crate::xof::Shake256Xof;
 use hybrid_array::Array;
+use subtle::ConstantTimeEq;
 use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
+fn check_entropy_len<P: MayoParameter>(entropy: &[u8]) -> crate::error::Result<()> {
+    if entropy.len() != P::SALT_BYTES {
+        return Err(Error::InvalidEntropyLength {
+            expected: P::SALT_BYTES,
+            got: entropy.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Sampling statistics for a batch of signatures, from
+/// [`SigningKey::sign_batch_with_stats`].
+///
+/// Signing retries with a fresh `(v, r)` until the linear system it yields is
+/// solvable, stepping a one-byte counter each time. Counter `c` means the
+/// signature took `c + 1` attempts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SolveStats {
+    total_attempts: u64,
+    max_attempts: u32,
+    histogram: [u64; 256],
+}
+
+impl Default for SolveStats {
+    fn default() -> Self {
+        Self {
+            total_attempts: 0,
+            max_attempts: 0,
+            histogram: [0; 256],
+        }
+    }
+}
+
+impl SolveStats {
+    /// Sampling attempts summed over every signature.
+    pub fn total_attempts(&self) -> u64 {
+        self.total_attempts
+    }
+
+    /// The most attempts any single signature needed.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Number of signatures that succeeded at each counter value.
+    pub fn histogram(&self) -> &[u64; 256] {
+        &self.histogram
+    }
+
+    fn record(&mut self, ctr: u8) {
+        let attempts = u32::from(ctr) + 1;
+        self.total_attempts += u64::from(attempts);
+        self.max_attempts = self.max_attempts.max(attempts);
+        self.histogram[usize::from(ctr)] += 1;
+    }
+}
+
 /// A MAYO signing key (compact secret key = seed).
+///
+/// The key is wiped when dropped. To wipe it earlier, e.g. once it has been
+/// copied into an HSM, call [`zeroize`](Zeroize::zeroize): the seed and the
+/// cached public key are overwritten with zeros in place. A zeroized key
+/// keeps its length but no longer corresponds to any usable key pair.
 #[derive(Clone)]
 pub struct SigningKey<P: MayoParameter> {
     pub(crate) bytes: Array<u8, P::CskSize>,
@@ -26,14 +100,14 @@ pub struct SigningKey<P: MayoParameter> {
 #[derive(Clone)]
 pub struct ExpandedSigningKey<P: MayoParameter> {
     bytes: Array<u8, P::CskSize>,
-    p: Zeroizing<Vec<u64>>,
-    p2: Vec<u64>,
-    o: Zeroizing<Vec<u8>>,
+    esk: ExpandedSecretKey,
 }
 
 impl<P: MayoParameter> Zeroize for SigningKey<P> {
+    /// Overwrite the seed and the cached public key with zeros.
     fn zeroize(&mut self) {
         self.bytes.zeroize();
+        self.cpk.as_mut_slice().zeroize();
     }
 }
 
@@ -48,9 +122,7 @@ impl<P: MayoParameter> ZeroizeOnDrop for SigningKey<P> {}
 impl<P: MayoParameter> Zeroize for ExpandedSigningKey<P> {
     fn zeroize(&mut self) {
         self.bytes.zeroize();
-        self.p.zeroize();
-        self.p2.zeroize();
-        self.o.zeroize();
+        self.esk.zeroize();
     }
 }
 
@@ -84,7 +156,7 @@ impl<P: MayoParameter> TryFrom<&[u8]> for SigningKey<P> {
                 got: bytes.len(),
             })?;
         let mut cpk = vec![0u8; P::CPK_BYTES];
-        derive_cpk_from_csk::<P>(bytes, &mut cpk);
+        derive_cpk_from_csk::<P, Shake256Xof>(bytes, &mut cpk)?;
         Ok(Self { bytes: csk, cpk })
     }
 }
@@ -114,16 +186,18 @@ impl<P: MayoParameter> TryFrom<Box<[u8]>> for SigningKey<P> {
 }
 
 impl<P: MayoParameter> PartialEq for SigningKey<P> {
+    /// Compares the secret seeds in constant time.
     fn eq(&self, other: &Self) -> bool {
-        self.bytes == other.bytes
+        self.bytes.as_slice().ct_eq(other.bytes.as_slice()).into()
     }
 }
 
 impl<P: MayoParameter> Eq for SigningKey<P> {}
 
 impl<P: MayoParameter> PartialEq for ExpandedSigningKey<P> {
+    /// Compares the secret seeds in constant time.
     fn eq(&self, other: &Self) -> bool {
-        self.bytes == other.bytes
+        self.bytes.as_slice().ct_eq(other.bytes.as_slice()).into()
     }
 }
 
@@ -149,6 +223,23 @@ impl<P: MayoParameter> core::fmt::Debug for ExpandedSigningKey<P> {
 }
 
 impl<P: MayoParameter> SigningKey<P> {
+    /// The secret seed `seed_sk`, the first `SK_SEED_BYTES` of the compact key.
+    ///
+    /// This is the entire secret: anyone holding it can re-derive the key
+    /// pair. The borrow is cleared with the key when it is zeroized or dropped;
+    /// copy it only into storage that is zeroized as well.
+    pub fn secret_seed(&self) -> &[u8] {
+        &self.bytes[..P::SK_SEED_BYTES]
+    }
+
+    /// The public seed `seed_pk` that P1 and P2 are expanded from.
+    ///
+    /// This is the first `PK_SEED_BYTES` of `SHAKE256(seed_sk)` and the prefix
+    /// of the matching compact public key.
+    pub fn public_seed(&self) -> &[u8] {
+        &self.cpk[..P::PK_SEED_BYTES]
+    }
+
     /// Expand this signing key for repeated signing.
     ///
     /// The returned key caches secret-derived signing material. Prefer the
@@ -164,71 +255,527 @@ impl<P: MayoParameter> SigningKey<P> {
         &self,
         rng: &mut impl rand::CryptoRng,
         msg: &[u8],
+    ) -> crate::error::Result<Signature<P>> {
+        self.sign_with_scratch(&mut SignScratch::new(), rng, msg)
+    }
+
+    /// Sign a message reusing the working buffers in `scratch`.
+    ///
+    /// Produces the same signature as [`sign_with_rng`](Self::sign_with_rng)
+    /// for the same RNG output, without allocating the signing buffers.
+    pub fn sign_with_scratch(
+        &self,
+        scratch: &mut SignScratch<P>,
+        rng: &mut impl rand::CryptoRng,
+        msg: &[u8],
+    ) -> crate::error::Result<Signature<P>> {
+        self.sign_randomized(scratch, |r| rng.fill_bytes(r), msg)
+    }
+
+    /// Sign a message into a caller-provided buffer, avoiding the signature
+    /// allocation.
+    ///
+    /// The signature is written to `out[..P::SIG_BYTES]` and its length is
+    /// returned; any bytes past that are left untouched. `out` shorter than
+    /// `P::SIG_BYTES` gives [`Error::InvalidSignatureLength`]. If signing
+    /// fails, the signature region is zeroed so a faulty signature is never
+    /// released.
+    pub fn sign_into(
+        &self,
+        rng: &mut impl rand::CryptoRng,
+        msg: &[u8],
+        out: &mut [u8],
+    ) -> crate::error::Result<usize> {
+        let Some(sig) = out.get_mut(..P::SIG_BYTES) else {
+            return Err(Error::InvalidSignatureLength {
+                expected: P::SIG_BYTES,
+                got: out.len(),
+            });
+        };
+        let result = mayo_sign_signature::<P, Shake256Xof>(
+            sig,
+            msg,
+            &self.bytes,
+            |r| rng.fill_bytes(r),
+            &mut SignScratch::new(),
+        );
+        match result {
+            Ok(_) => Ok(P::SIG_BYTES),
+            Err(e) => {
+                sig.zeroize();
+                Err(e)
+            }
+        }
+    }
+
+    /// Sign a message into an [`InlineSignature`].
+    ///
+    /// Signing allocates its working memory like
+    /// [`sign_with_rng`](Self::sign_with_rng); only the result is stored
+    /// inline rather than in a `Vec`. Use
+    /// [`sign_inline_with_workspace`](Self::sign_inline_with_workspace) to
+    /// avoid the heap altogether.
+    ///
+    /// Fails like [`sign_into`](Self::sign_into), and with
+    /// [`Error::InvalidSignatureLength`] if `P::SIG_BYTES` exceeds
+    /// [`MAX_SIG_BYTES`](crate::MAX_SIG_BYTES).
+    pub fn sign_inline(
+        &self,
+        rng: &mut impl rand::CryptoRng,
+        msg: &[u8],
+    ) -> crate::error::Result<InlineSignature<P>> {
+        InlineSignature::build(|sig| self.sign_into(rng, msg, sig).map(|_| ()))
+    }
+
+    /// Sign a message into an [`InlineSignature`] without allocating.
+    ///
+    /// The key is expanded into `workspace` and every intermediate value
+    /// lives there too, so signing makes no heap allocation (`rng` aside).
+    /// The workspace is zeroized before this returns. Fails like
+    /// [`sign_inline`](Self::sign_inline).
+    pub fn sign_inline_with_workspace<const LIMBS: usize, const BYTES: usize>(
+        &self,
+        rng: &mut impl rand::CryptoRng,
+        msg: &[u8],
+        workspace: &mut Workspace<P, LIMBS, BYTES>,
+    ) -> crate::error::Result<InlineSignature<P>> {
+        let (limbs, bytes) = workspace.buffers();
+        InlineSignature::build(|sig| {
+            let result = mayo_sign_signature_in::<P, Shake256Xof>(
+                sig,
+                msg,
+                &self.bytes,
+                |r| rng.fill_bytes(r),
+                limbs,
+                bytes,
+            );
+            match result {
+                Ok(_) => Ok(()),
+                Err(e) => {
+                    sig.zeroize();
+                    Err(e)
+                }
+            }
+        })
+    }
+
+    /// Sign `msg` bound to the context string `ctx`.
+    ///
+    /// This signs `len(ctx) || ctx || msg` with a one-byte length, so a
+    /// signature made under one context does not verify under another. The
+    /// layout matches what the MAYO reference implementation produces when
+    /// given that concatenation as its message. Verify with
+    /// [`VerifyingKey::verify_with_context`](crate::VerifyingKey::verify_with_context).
+    ///
+    /// Returns [`Error::InvalidContextLength`] if `ctx` is longer than 255
+    /// bytes and [`Error::ReservedContext`] for the two contexts the
+    /// associated-data methods use as domain tags.
+    pub fn sign_with_context(
+        &self,
+        rng: &mut impl rand::CryptoRng,
+        ctx: &[u8],
+        msg: &[u8],
+    ) -> crate::error::Result<Signature<P>> {
+        let preimage = context_preimage(ctx, msg)?;
+        self.sign_with_rng(rng, &preimage)
+    }
+
+    /// Sign `msg` together with associated data `aad`.
+    ///
+    /// This signs `len(aad) || aad || msg`, with an 8-byte little-endian
+    /// length, in a domain of its own: the signature does not verify as a
+    /// plain or context signature of `msg`, and changing `aad`, or moving
+    /// bytes between `aad` and `msg`, invalidates it. Verify with
+    /// [`VerifyingKey::verify_with_aad`](crate::VerifyingKey::verify_with_aad).
+    pub fn sign_with_aad(
+        &self,
+        rng: &mut impl rand::CryptoRng,
+        aad: &[u8],
+        msg: &[u8],
+    ) -> crate::error::Result<Signature<P>> {
+        let preimage = aad_preimage(None, aad, msg)?;
+        self.sign_with_rng(rng, &preimage)
+    }
+
+    /// Sign `msg` with associated data `aad` under the context string `ctx`.
+    ///
+    /// This signs `len(ctx) || ctx || len(aad) || aad || msg` in a domain
+    /// separate from both [`sign_with_context`](Self::sign_with_context) and
+    /// [`sign_with_aad`](Self::sign_with_aad). Verify with
+    /// [`VerifyingKey::verify_with_context_and_aad`].
+    ///
+    /// [`VerifyingKey::verify_with_context_and_aad`]: crate::VerifyingKey::verify_with_context_and_aad
+    ///
+    /// Returns [`Error::InvalidContextLength`] if `ctx` is longer than 255
+    /// bytes.
+    pub fn sign_with_context_and_aad(
+        &self,
+        rng: &mut impl rand::CryptoRng,
+        ctx: &[u8],
+        aad: &[u8],
+        msg: &[u8],
+    ) -> crate::error::Result<Signature<P>> {
+        let preimage = aad_preimage(Some(ctx), aad, msg)?;
+        self.sign_with_rng(rng, &preimage)
+    }
+
+    /// Sign everything `reader` yields, streaming it through SHAKE256 in
+    /// fixed-size chunks instead of holding it in memory.
+    ///
+    /// The signature is the same as [`sign_with_rng`](Self::sign_with_rng)
+    /// would produce over the whole contents, so it verifies with either
+    /// [`VerifyingKey::verify_reader`](crate::VerifyingKey::verify_reader) or
+    /// [`VerifyingKey::verify`](crate::VerifyingKey::verify). A read error
+    /// gives [`Error::Io`].
+    #[cfg(feature = "std")]
+    pub fn sign_reader(
+        &self,
+        rng: &mut impl rand::CryptoRng,
+        reader: impl std::io::Read,
+    ) -> crate::error::Result<Signature<P>> {
+        let mut digest = vec![0u8; P::DIGEST_BYTES];
+        digest_reader::<Shake256Xof>(reader, &mut digest)?;
+        self.sign_prehashed(rng, &digest)
+    }
+
+    /// Sign a message given as its digest from
+    /// [`message_digest`](crate::message_digest), skipping the SHAKE256
+    /// pass over the message.
+    ///
+    /// The signature is the one [`sign_with_rng`](Self::sign_with_rng)
+    /// would produce over the message itself for the same RNG output, and
+    /// verifies with [`VerifyingKey::verify`](crate::VerifyingKey::verify)
+    /// over the message. This is not hash-then-sign with a digest of the
+    /// caller's choosing: a [`MessageDigest`] can only be made by hashing a
+    /// message.
+    pub fn sign_with_precomputed_digest(
+        &self,
+        rng: &mut impl rand::CryptoRng,
+        digest: &MessageDigest<P>,
+    ) -> crate::error::Result<Signature<P>> {
+        self.sign_prehashed(rng, digest.as_ref())
+    }
+
+    /// Sign a message given as its `DIGEST_BYTES`-long digest.
+    fn sign_prehashed(
+        &self,
+        rng: &mut impl rand::CryptoRng,
+        digest: &[u8],
+    ) -> crate::error::Result<Signature<P>> {
+        let esk = expand_sk::<P, Shake256Xof>(&self.bytes)?;
+        let mut sig_bytes = vec![0u8; P::SIG_BYTES];
+        mayo_sign_signature_with_expanded_sk::<P, Shake256Xof, Prehashed>(
+            &mut sig_bytes,
+            digest,
+            &self.bytes,
+            &esk,
+            |r| rng.fill_bytes(r),
+            &mut SignScratch::new(),
+        )?;
+        Signature::try_from(sig_bytes)
+    }
+
+    /// Sign a message with running time independent of the number of
+    /// sampling attempts.
+    ///
+    /// Ordinary signing stops at the first `(v, r)` candidate whose linear
+    /// system is solvable, so its running time reveals how many attempts that
+    /// took. This always runs all 256 attempts and keeps the first solution
+    /// with constant-time selects. The signature is the one
+    /// [`sign_with_rng`](Self::sign_with_rng) would produce for the same RNG
+    /// output, but signing is up to 256 times slower; use it only where
+    /// uniform timing is a requirement.
+    pub fn sign_fixed_time(
+        &self,
+        rng: &mut impl rand::CryptoRng,
+        msg: &[u8],
+    ) -> crate::error::Result<Signature<P>> {
+        let mut sig_bytes = vec![0u8; P::SIG_BYTES];
+        mayo_sign_signature_fixed_time::<P, Shake256Xof>(
+            &mut sig_bytes,
+            msg,
+            &self.bytes,
+            |r| rng.fill_bytes(r),
+            &mut SignScratch::new(),
+        )?;
+        Signature::try_from(sig_bytes)
+    }
+
+    /// Sign a message, then verify the signature against an independently
+    /// derived public key before returning it.
+    ///
+    /// Every signing method already verifies its signature before releasing
+    /// it, which catches a fault in the signing arithmetic, e.g. a glitched
+    /// multiplication. That check reuses the P1, P2 and O that signing
+    /// expanded from the secret key, so a fault in that expansion or in
+    /// decoding O yields a signature and a check that agree with each other
+    /// but not with the real public key. This method additionally derives
+    /// the compact public key from the seed in a separate pass, rather than
+    /// trusting the cached copy, verifies against it, and returns
+    /// [`Error::Signing`] on failure. It costs about a key generation plus a
+    /// verification on top of [`sign_with_rng`](Self::sign_with_rng).
+    pub fn sign_with_verify(
+        &self,
+        rng: &mut impl rand::CryptoRng,
+        msg: &[u8],
+    ) -> crate::error::Result<Signature<P>> {
+        let signature = self.sign_with_rng(rng, msg)?;
+        let mut cpk = vec![0u8; P::CPK_BYTES];
+        derive_cpk_from_csk::<P, Shake256Xof>(&self.bytes, &mut cpk)?;
+        mayo_verify::<P, Shake256Xof>(msg, signature.as_ref(), &cpk).map_err(|_| Error::Signing)?;
+        Ok(signature)
+    }
+
+    /// Sign a message with caller-supplied entropy instead of an RNG.
+    ///
+    /// `entropy` must be exactly `P::SALT_BYTES` long; it fills the same
+    /// randomizer slot that [`sign_with_rng`](Self::sign_with_rng) draws from
+    /// its RNG, so the two agree for the same bytes. This suits hosts such as
+    /// `wasm32-unknown-unknown` without the `js` feature, where the caller
+    /// obtains randomness itself. Reusing entropy across messages is safe but
+    /// makes signing deterministic, like
+    /// [`sign_deterministic`](Self::sign_deterministic).
+    pub fn sign_with_entropy(
+        &self,
+        entropy: &[u8],
+        msg: &[u8],
+    ) -> crate::error::Result<Signature<P>> {
+        check_entropy_len::<P>(entropy)?;
+        self.sign_randomized(&mut SignScratch::new(), |r| r.copy_from_slice(entropy), msg)
+    }
+
+    /// Sign every message in `msgs` and report how many sampling attempts
+    /// signing took.
+    ///
+    /// The key is expanded once for the whole batch. Signatures are returned
+    /// in the order of `msgs`.
+    pub fn sign_batch_with_stats(
+        &self,
+        rng: &mut impl rand::CryptoRng,
+        msgs: &[&[u8]],
+    ) -> crate::error::Result<(Vec<Signature<P>>, SolveStats)> {
+        let expanded = self.expand();
+        let mut scratch = SignScratch::new();
+        let mut stats = SolveStats::default();
+        let mut signatures = Vec::with_capacity(msgs.len());
+        for msg in msgs {
+            let (signature, ctr) =
+                expanded.sign_counted(&mut scratch, |r| rng.fill_bytes(r), msg)?;
+            stats.record(ctr);
+            signatures.push(signature);
+        }
+        Ok((signatures, stats))
+    }
+
+    /// Lazily sign each message of `msgs` as the returned iterator is polled.
+    ///
+    /// The key is expanded once, up front, and the expanded key and working
+    /// buffers are reused for every message, as with
+    /// [`sign_batch_with_stats`](Self::sign_batch_with_stats). Each message is
+    /// signed independently, so an error does not end the iterator; collect
+    /// into a `Result<Vec<_>>` to stop at the first one.
+    pub fn sign_iter<'a, R, I>(
+        &self,
+        rng: &'a mut R,
+        msgs: I,
+    ) -> impl Iterator<Item = crate::error::Result<Signature<P>>> + use<'a, P, R, I>
+    where
+        R: rand::CryptoRng,
+        I: IntoIterator<Item = &'a [u8]>,
+        I::IntoIter: 'a,
+    {
+        let expanded = self.expand();
+        let mut scratch = SignScratch::new();
+        msgs.into_iter()
+            .map(move |msg| expanded.sign_with_scratch(&mut scratch, rng, msg))
+    }
+
+    /// Sign a message deterministically.
+    ///
+    /// The randomizer is all zeros, so the salt depends only on the message
+    /// digest and the secret seed, as in the MAYO specification's
+    /// deterministic variant. Signing the same message twice gives the same
+    /// signature.
+    pub fn sign_deterministic(&self, msg: &[u8]) -> crate::error::Result<Signature<P>> {
+        self.sign_randomized(&mut SignScratch::new(), |r| r.fill(0), msg)
+    }
+
+    fn sign_randomized(
+        &self,
+        scratch: &mut SignScratch<P>,
+        randomizer: impl FnOnce(&mut [u8]),
+        msg: &[u8],
     ) -> crate::error::Result<Signature<P>> {
         let mut sig_bytes = vec![0u8; P::SIG_BYTES];
-        mayo_sign_signature::<P>(&mut sig_bytes, msg, &self.bytes, rng)?;
+        mayo_sign_signature::<P, Shake256Xof>(
+            &mut sig_bytes,
+            msg,
+            &self.bytes,
+            randomizer,
+            scratch,
+        )?;
         Signature::try_from(sig_bytes)
     }
 }
 
 impl<P: MayoParameter> ExpandedSigningKey<P> {
+    /// A copy of the public `P1 || P2` limbs this key expanded, so a
+    /// verifying key for the same seed can skip its own AES-CTR expansion.
+    pub(crate) fn public_matrices(&self) -> Vec<u64> {
+        let mut pk = Vec::with_capacity(P::P1_LIMBS + P::P2_LIMBS);
+        pk.extend_from_slice(&self.esk.p1_l[..P::P1_LIMBS]);
+        pk.extend_from_slice(&self.esk.p2);
+        pk
+    }
+
+    /// Export the expanded secret key as `seed_sk || O || P1 || L`.
+    ///
+    /// This is the `ESK_BYTES`-long expanded secret key layout used by the MAYO
+    /// reference implementation, and is accepted by
+    /// [`KeyPair::from_expanded_secret_key`](crate::KeyPair::from_expanded_secret_key).
+    /// It contains the secret oil space and must be handled like the compact key.
+    pub fn to_expanded_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut esk = Zeroizing::new(vec![0u8; P::ESK_BYTES]);
+        pack_expanded_sk::<P>(&self.bytes, &self.esk.p1_l, &self.esk.o, &mut esk);
+        esk
+    }
+
     /// Sign a message using a caller-provided RNG for salt generation.
     pub fn sign_with_rng(
         &self,
         rng: &mut impl rand::CryptoRng,
         msg: &[u8],
     ) -> crate::error::Result<Signature<P>> {
+        self.sign_with_scratch(&mut SignScratch::new(), rng, msg)
+    }
+
+    /// Sign a message reusing the working buffers in `scratch`.
+    ///
+    /// Combined with the cached expansion, this is the allocation-light path
+    /// for signing many messages with one key.
+    pub fn sign_with_scratch(
+        &self,
+        scratch: &mut SignScratch<P>,
+        rng: &mut impl rand::CryptoRng,
+        msg: &[u8],
+    ) -> crate::error::Result<Signature<P>> {
+        self.sign_randomized(scratch, |r| rng.fill_bytes(r), msg)
+    }
+
+    /// Sign a message with caller-supplied entropy instead of an RNG.
+    ///
+    /// Matches [`SigningKey::sign_with_entropy`] for the same key.
+    pub fn sign_with_entropy(
+        &self,
+        entropy: &[u8],
+        msg: &[u8],
+    ) -> crate::error::Result<Signature<P>> {
+        check_entropy_len::<P>(entropy)?;
+        self.sign_randomized(&mut SignScratch::new(), |r| r.copy_from_slice(entropy), msg)
+    }
+
+    /// Sign a message deterministically.
+    ///
+    /// Matches [`SigningKey::sign_deterministic`] for the same key.
+    pub fn sign_deterministic(&self, msg: &[u8]) -> crate::error::Result<Signature<P>> {
+        self.sign_randomized(&mut SignScratch::new(), |r| r.fill(0), msg)
+    }
+
+    fn sign_randomized(
+        &self,
+        scratch: &mut SignScratch<P>,
+        randomizer: impl FnOnce(&mut [u8]),
+        msg: &[u8],
+    ) -> crate::error::Result<Signature<P>> {
+        self.sign_counted(scratch, randomizer, msg)
+            .map(|(signature, _)| signature)
+    }
+
+    /// Sign, also returning the sampling counter that produced the solution.
+    fn sign_counted(
+        &self,
+        scratch: &mut SignScratch<P>,
+        randomizer: impl FnOnce(&mut [u8]),
+        msg: &[u8],
+    ) -> crate::error::Result<(Signature<P>, u8)> {
         let mut sig_bytes = vec![0u8; P::SIG_BYTES];
-        mayo_sign_signature_with_expanded_sk::<P>(
+        let ctr = mayo_sign_signature_with_expanded_sk::<P, Shake256Xof, XofDigester<Shake256Xof>>(
             &mut sig_bytes,
             msg,
             &self.bytes,
-            &self.p,
-            &self.p2,
-            &self.o,
-            rng,
+            &self.esk,
+            randomizer,
+            scratch,
         )?;
-        Signature::try_from(sig_bytes)
+        Ok((Signature::try_from(sig_bytes)?, ctr))
     }
 }
 
 impl<P: MayoParameter> From<&SigningKey<P>> for ExpandedSigningKey<P> {
     fn from(signing_key: &SigningKey<P>) -> Self {
-        let esk = expand_sk::<P>(&signing_key.bytes);
         Self {
             bytes: signing_key.bytes.clone(),
-            p: esk.p1_l,
-            p2: esk.p2,
-            o: esk.o,
+            esk: expand_sk::<P, Shake256Xof>(&signing_key.bytes)
+                .expect("the public seed derived from a secret key is PK_SEED_BYTES long"),
         }
     }
 }
 
+#[cfg(feature = "signature-traits")]
 impl<P: MayoParameter> signature::Signer<Signature<P>> for SigningKey<P> {
     fn try_sign(&self, msg: &[u8]) -> Result<Signature<P>, signature::Error> {
-        let mut sig_bytes = vec![0u8; P::SIG_BYTES];
-        let mut rng = rand::rng();
-        mayo_sign_signature::<P>(&mut sig_bytes, msg, &self.bytes, &mut rng)
-            .map_err(|e| -> signature::Error { e.into() })?;
-        Signature::try_from(sig_bytes).map_err(|e| -> signature::Error { e.into() })
+        self.sign_with_rng(&mut rand::rng(), msg)
+            .map_err(|e| -> signature::Error { e.into() })
+    }
+}
+
+#[cfg(feature = "signature-traits")]
+impl<P: MayoParameter> signature::RandomizedSigner<Signature<P>> for SigningKey<P> {
+    fn try_sign_with_rng<R: signature::rand_core::TryCryptoRng + ?Sized>(
+        &self,
+        rng: &mut R,
+        msg: &[u8],
+    ) -> Result<Signature<P>, signature::Error> {
+        // Draw the randomizer up front so an RNG failure surfaces as an error
+        // instead of reaching the signing core.
+        let mut randomizer = Zeroizing::new(vec![0u8; P::SALT_BYTES]);
+        rng.try_fill_bytes(&mut randomizer)
+            .map_err(|_| signature::Error::new())?;
+        self.sign_with_entropy(&randomizer, msg).map_err(Into::into)
+    }
+}
+
+/// Hash-then-sign through a caller-fed XOF such as [`shake::Shake256`].
+///
+/// The first `DIGEST_BYTES` bytes of the XOF output stand in for the MAYO
+/// message digest. With `Shake256` that is exactly the specification's
+/// digest, so the signature equals a plain one over the fed bytes and
+/// verifies with [`VerifyingKey::verify`](crate::VerifyingKey::verify). Any
+/// other XOF must be matched by the same `D` in `signature::DigestVerifier`.
+#[cfg(feature = "signature-traits")]
+impl<P, D> signature::DigestSigner<D, Signature<P>> for SigningKey<P>
+where
+    P: MayoParameter,
+    D: Default + signature::digest::Update + signature::digest::ExtendableOutput,
+{
+    fn try_sign_digest<F: Fn(&mut D) -> Result<(), signature::Error>>(
+        &self,
+        f: F,
+    ) -> Result<Signature<P>, signature::Error> {
+        let mut xof = D::default();
+        f(&mut xof)?;
+        let mut digest = vec![0u8; P::DIGEST_BYTES];
+        xof.finalize_xof_into(&mut digest);
+        self.sign_prehashed(&mut rand::rng(), &digest)
+            .map_err(Into::into)
     }
 }
 
+#[cfg(feature = "signature-traits")]
 impl<P: MayoParameter> signature::Signer<Signature<P>> for ExpandedSigningKey<P> {
     fn try_sign(&self, msg: &[u8]) -> Result<Signature<P>, signature::Error> {
-        let mut sig_bytes = vec![0u8; P::SIG_BYTES];
-        let mut rng = rand::rng();
-        mayo_sign_signature_with_expanded_sk::<P>(
-            &mut sig_bytes,
-            msg,
-            &self.bytes,
-            &self.p,
-            &self.p2,
-            &self.o,
-            &mut rng,
-        )
-        .map_err(|e| -> signature::Error { e.into() })?;
-        Signature::try_from(sig_bytes).map_err(|e| -> signature::Error { e.into() })
+        self.sign_with_rng(&mut rand::rng(), msg)
+            .map_err(|e| -> signature::Error { e.into() })
     }
 }
 
@@ -252,3 +799,106 @@ impl<'de, P: MayoParameter> serde::Deserialize<'de> for SigningKey<P> {
         Self::try_from(bytes).map_err(serde::de::Error::custom)
     }
 }
+
+/// A [`SigningKey`] whose serde form names its parameter set.
+///
+/// The plain `SigningKey` serializes only the seed, so a Mayo1 key
+/// deserializes as a Mayo2 key without complaint (both seeds are 24 bytes).
+/// This wrapper serializes as `{ "algorithm": P::NAME, "key": <seed> }` and
+/// refuses to deserialize a key tagged with a different parameter set.
+#[cfg(feature = "serde")]
+#[derive(Clone)]
+pub struct TaggedSigningKey<P: MayoParameter> {
+    signing_key: SigningKey<P>,
+}
+
+#[cfg(feature = "serde")]
+impl<P: MayoParameter> TaggedSigningKey<P> {
+    /// Get a reference to the wrapped signing key.
+    pub fn signing_key(&self) -> &SigningKey<P> {
+        &self.signing_key
+    }
+
+    /// Unwrap into the signing key.
+    pub fn into_signing_key(self) -> SigningKey<P> {
+        self.signing_key
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<P: MayoParameter> From<SigningKey<P>> for TaggedSigningKey<P> {
+    fn from(signing_key: SigningKey<P>) -> Self {
+        Self { signing_key }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<P: MayoParameter> PartialEq for TaggedSigningKey<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.signing_key == other.signing_key
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<P: MayoParameter> Eq for TaggedSigningKey<P> {}
+
+#[cfg(feature = "serde")]
+impl<P: MayoParameter> ZeroizeOnDrop for TaggedSigningKey<P> {}
+
+#[cfg(feature = "serde")]
+impl<P: MayoParameter> core::fmt::Debug for TaggedSigningKey<P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TaggedSigningKey")
+            .field("signing_key", &self.signing_key)
+            .finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct TaggedRef<'a> {
+    algorithm: &'a str,
+    #[serde(serialize_with = "serdect::slice::serialize_hex_lower_or_bin")]
+    key: &'a [u8],
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct TaggedOwned {
+    algorithm: String,
+    #[serde(deserialize_with = "serdect::slice::deserialize_hex_or_bin_vec")]
+    key: Vec<u8>,
+}
+
+#[cfg(feature = "serde")]
+impl<P: MayoParameter> serde::Serialize for TaggedSigningKey<P> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        TaggedRef {
+            algorithm: P::NAME,
+            key: &self.signing_key.bytes,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, P: MayoParameter> serde::Deserialize<'de> for TaggedSigningKey<P> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let TaggedOwned { algorithm, key } = TaggedOwned::deserialize(deserializer)?;
+        let key = Zeroizing::new(key);
+        if algorithm != P::NAME {
+            return Err(serde::de::Error::custom(format!(
+                "parameter set mismatch: expected {}, got {algorithm}",
+                P::NAME
+            )));
+        }
+        let signing_key = SigningKey::try_from(key.as_slice()).map_err(serde::de::Error::custom)?;
+        Ok(Self { signing_key })
+    }
+}
@@ -0,0 +1,205 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! A conformance test battery for crates built on MAYO.
+//!
+//! Available with the `test-utils` feature, which is meant for
+//! `[dev-dependencies]`. [`mayo_test_suite!`](crate::mayo_test_suite)
+//! generates a module of `#[test]` functions for one parameter set, each
+//! calling one of the checks below. The checks can also be called directly
+//! from hand-written tests. Every check is deterministic: keys come from
+//! fixed seeds and signatures use the zero randomizer, so a failure
+//! reproduces exactly.
+//!
+//! ```
+//! pq_mayo::mayo_test_suite!(mayo1, pq_mayo::Mayo1);
+//! pq_mayo::mayo_test_suite!(mayo2, pq_mayo::Mayo2);
+//! ```
+//!
+//! A failing check panics with the parameter set name in the message.
+
+use crate::{Error, KeyPair, MayoParameter, Signature, SigningKey, VerifyingKey};
+
+fn fixed_keypair<P: MayoParameter>(tag: u8) -> KeyPair<P> {
+    let seed: Vec<u8> = (0u8..).map(|i| i ^ tag).take(P::SK_SEED_BYTES).collect();
+    KeyPair::from_seed(&seed).expect("a SK_SEED_BYTES-long seed is accepted")
+}
+
+/// Sign and verify a few messages, including the empty one, and check that
+/// deterministic signing is repeatable.
+pub fn roundtrip<P: MayoParameter>() {
+    let keypair = fixed_keypair::<P>(0);
+    for msg in [&b""[..], b"a", b"conformance message", &[0xA5; 1000]] {
+        let sig = keypair
+            .signing_key()
+            .sign_deterministic(msg)
+            .expect("signing failed");
+        assert!(
+            keypair.verifying_key().verify(msg, &sig).is_ok(),
+            "{}: signature over a {}-byte message does not verify",
+            P::NAME,
+            msg.len()
+        );
+        let again = keypair
+            .signing_key()
+            .sign_deterministic(msg)
+            .expect("signing failed");
+        assert!(again == sig, "{}: deterministic signing differs", P::NAME);
+    }
+}
+
+/// Check that a signature fails for a changed message, a changed signature
+/// and another key.
+pub fn wrong_message<P: MayoParameter>() {
+    let keypair = fixed_keypair::<P>(0);
+    let other = fixed_keypair::<P>(0xFF);
+    let msg = b"conformance message";
+    let sig = keypair
+        .signing_key()
+        .sign_deterministic(msg)
+        .expect("signing failed");
+    let vk = keypair.verifying_key();
+
+    assert!(
+        vk.verify(b"conformance messagE", &sig)
+            .is_err_and(|e| e.is_verification_failure()),
+        "{}: signature verifies for a different message",
+        P::NAME
+    );
+    assert!(
+        other.verifying_key().verify(msg, &sig).is_err(),
+        "{}: signature verifies under a different key",
+        P::NAME
+    );
+    for i in [0, P::SIG_BYTES / 2, P::SIG_BYTES - 1] {
+        let mut bytes = sig.to_vec();
+        bytes[i] ^= 1;
+        let tampered = Signature::<P>::try_from(bytes).expect("valid length");
+        assert!(
+            vk.verify(msg, &tampered).is_err(),
+            "{}: signature with byte {i} flipped still verifies",
+            P::NAME
+        );
+    }
+}
+
+/// Round-trip the key pair, both keys and a signature through the `serde`
+/// implementations, as JSON.
+pub fn serde_roundtrip<P: MayoParameter>() {
+    fn roundtrip<T>(value: &T, what: &str, name: &str) -> T
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned + PartialEq,
+    {
+        let json = serde_json::to_string(value).expect("serialization failed");
+        let decoded: T = serde_json::from_str(&json).expect("deserialization failed");
+        assert!(
+            decoded == *value,
+            "{name}: {what} changed in a serde round trip"
+        );
+        decoded
+    }
+
+    let keypair = fixed_keypair::<P>(0);
+    let sig = keypair
+        .signing_key()
+        .sign_deterministic(b"serde")
+        .expect("signing failed");
+
+    roundtrip(&keypair, "key pair", P::NAME);
+    roundtrip(keypair.signing_key(), "signing key", P::NAME);
+    let vk = roundtrip(keypair.verifying_key(), "verifying key", P::NAME);
+    let sig = roundtrip(&sig, "signature", P::NAME);
+    assert!(
+        vk.verify(b"serde", &sig).is_ok(),
+        "{}: decoded signature does not verify",
+        P::NAME
+    );
+}
+
+/// Check that inputs of the wrong length are rejected with the matching
+/// length error rather than a panic.
+pub fn length_validation<P: MayoParameter>() {
+    let keypair = fixed_keypair::<P>(0);
+    let sig = keypair
+        .signing_key()
+        .sign_deterministic(b"lengths")
+        .expect("signing failed");
+    let vk = keypair.verifying_key().as_ref();
+    let sk = keypair.signing_key().as_ref();
+
+    for len in [0, P::CPK_BYTES - 1, P::CPK_BYTES + 1] {
+        let bytes = vec![0u8; len];
+        assert!(
+            matches!(
+                VerifyingKey::<P>::try_from(bytes.as_slice()),
+                Err(Error::InvalidKeyLength { got, .. }) if got == len
+            ),
+            "{}: {len}-byte verifying key not rejected",
+            P::NAME
+        );
+    }
+    for len in [0, P::CSK_BYTES - 1, P::CSK_BYTES + 1] {
+        let bytes = vec![0u8; len];
+        assert!(
+            SigningKey::<P>::try_from(bytes.as_slice()).is_err_and(|e| e.is_length_error()),
+            "{}: {len}-byte signing key not rejected",
+            P::NAME
+        );
+    }
+    for len in [0, P::SIG_BYTES - 1, P::SIG_BYTES + 1] {
+        let bytes = vec![0u8; len];
+        assert!(
+            matches!(
+                Signature::<P>::try_from(bytes.as_slice()),
+                Err(Error::InvalidSignatureLength { got, .. }) if got == len
+            ),
+            "{}: {len}-byte signature not rejected",
+            P::NAME
+        );
+    }
+    assert!(
+        KeyPair::<P>::from_seed(&vec![0u8; P::SK_SEED_BYTES + 1])
+            .is_err_and(|e| e.is_length_error()),
+        "{}: overlong seed not rejected",
+        P::NAME
+    );
+
+    assert!(VerifyingKey::<P>::try_from(vk).is_ok());
+    assert!(SigningKey::<P>::try_from(sk).is_ok());
+    assert!(Signature::<P>::try_from(sig.as_ref()).is_ok());
+}
+
+/// Generate a module of conformance tests for a parameter set.
+///
+/// `mayo_test_suite!(name, Param)` expands to `mod name` holding one
+/// `#[test]` per check in [`test_utils`](crate::test_utils): `roundtrip`,
+/// `wrong_message`, `serde_roundtrip` and `length_validation`. `Param` is
+/// resolved from the enclosing module, so an imported name works.
+#[macro_export]
+macro_rules! mayo_test_suite {
+    ($name:ident, $param:ty) => {
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[test]
+            fn roundtrip() {
+                $crate::test_utils::roundtrip::<$param>();
+            }
+
+            #[test]
+            fn wrong_message() {
+                $crate::test_utils::wrong_message::<$param>();
+            }
+
+            #[test]
+            fn serde_roundtrip() {
+                $crate::test_utils::serde_roundtrip::<$param>();
+            }
+
+            #[test]
+            fn length_validation() {
+                $crate::test_utils::length_validation::<$param>();
+            }
+        }
+    };
+}
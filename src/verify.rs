@@ -2,52 +2,89 @@
 
 //! MAYO signature verification.
 
-use crate::codec::{decode, unpack_m_vecs};
-use crate::error::{Error, Result};
-use crate::keygen::expand_p1_p2;
-use crate::matrix_ops::{PsSpsScratch, m_calculate_ps_sps_with_scratch};
+use crate::codec::{decode, has_zero_padding, unpack_m_vecs};
+use crate::digester::{Digester, XofDigester};
+use crate::error::{Error, Result, VerifyDetail};
+use crate::keygen::{expand_p1_p2, expand_p1_p2_into};
+use crate::matrix_ops::{P3Input, compute_rhs, m_calculate_ps_sps};
 use crate::params::{MAX_M, MayoParameter};
-use crate::sign::compute_rhs;
-use shake::Shake256;
-use shake::digest::{ExtendableOutput, Update, XofReader};
-use subtle::ConstantTimeEq;
+use crate::workspace::Carve;
+use crate::xof::Xof;
+use subtle::{Choice, ConstantTimeEq};
 
+/// Owned working memory for verification, carved into [`VerifyBuffers`]
+/// on each use.
 pub(crate) struct VerifyScratch {
-    ps_sps: PsSpsScratch,
-    sps: Vec<u64>,
-    tmp: Vec<u8>,
-    tenc: Vec<u8>,
-    t: Vec<u8>,
-    s: Vec<u8>,
-    y: Vec<u8>,
+    limbs: Vec<u64>,
+    bytes: Vec<u8>,
 }
 
 impl VerifyScratch {
     pub(crate) fn new<P: MayoParameter>() -> Self {
         Self {
-            ps_sps: PsSpsScratch::new::<P>(),
-            sps: vec![0u64; P::K * P::K * P::M_VEC_LIMBS],
-            tmp: vec![0u8; P::DIGEST_BYTES + P::SALT_BYTES],
-            tenc: vec![0u8; P::M_BYTES],
-            t: vec![0u8; P::M],
-            s: vec![0u8; P::K * P::N],
-            y: vec![0u8; P::M],
+            limbs: vec![0u64; VerifyBuffers::limbs::<P>()],
+            bytes: vec![0u8; VerifyBuffers::bytes::<P>()],
         }
     }
+
+    pub(crate) fn buffers<P: MayoParameter>(&mut self) -> VerifyBuffers<'_> {
+        VerifyBuffers::carve::<P>(
+            &mut Carve::new(&mut self.limbs),
+            &mut Carve::new(&mut self.bytes),
+        )
+    }
 }
 
-pub(crate) fn expand_public_key<P: MayoParameter>(cpk: &[u8]) -> (Vec<u64>, Vec<u64>) {
-    let param_m = P::M;
-    let param_pk_seed_bytes = P::PK_SEED_BYTES;
-    let m_vec_limbs = P::M_VEC_LIMBS;
+/// The working buffers of one verification, each sized for `P`.
+pub(crate) struct VerifyBuffers<'a> {
+    ps: &'a mut [u64],
+    sps: &'a mut [u64],
+    tmp: &'a mut [u8],
+    tenc: &'a mut [u8],
+    t: &'a mut [u8],
+    s: &'a mut [u8],
+    y: &'a mut [u8],
+}
 
-    let pk = expand_p1_p2::<P>(&cpk[..param_pk_seed_bytes]);
+impl<'a> VerifyBuffers<'a> {
+    /// Number of `u64` limbs [`carve`](Self::carve) takes.
+    pub(crate) const fn limbs<P: MayoParameter>() -> usize {
+        P::N * P::K * P::M_VEC_LIMBS + P::K * P::K * P::M_VEC_LIMBS
+    }
 
-    let p3_vecs = P::P3_LIMBS / m_vec_limbs;
-    let mut p3 = vec![0u64; P::P3_LIMBS];
-    unpack_m_vecs(&cpk[param_pk_seed_bytes..], &mut p3, p3_vecs, param_m);
+    /// Number of bytes [`carve`](Self::carve) takes.
+    pub(crate) const fn bytes<P: MayoParameter>() -> usize {
+        P::DIGEST_BYTES + P::SALT_BYTES + P::M_BYTES + P::M + P::K * P::N + P::M
+    }
+
+    /// Take the buffers from the front of `limbs` and `bytes`.
+    pub(crate) fn carve<P: MayoParameter>(
+        limbs: &mut Carve<'a, u64>,
+        bytes: &mut Carve<'a, u8>,
+    ) -> Self {
+        Self {
+            ps: limbs.take(P::N * P::K * P::M_VEC_LIMBS),
+            sps: limbs.take(P::K * P::K * P::M_VEC_LIMBS),
+            tmp: bytes.take(P::DIGEST_BYTES + P::SALT_BYTES),
+            tenc: bytes.take(P::M_BYTES),
+            t: bytes.take(P::M),
+            s: bytes.take(P::K * P::N),
+            y: bytes.take(P::M),
+        }
+    }
+}
+
+pub(crate) fn expand_public_key<P: MayoParameter>(cpk: &[u8]) -> Result<(Vec<u64>, Vec<u64>)> {
+    let pk = expand_p1_p2::<P>(&cpk[..P::PK_SEED_BYTES])?;
+    Ok((pk, unpack_p3::<P>(cpk)))
+}
 
-    (pk, p3)
+/// Unpack the upper-triangular P3 stored after the public seed in `cpk`.
+pub(crate) fn unpack_p3<P: MayoParameter>(cpk: &[u8]) -> Vec<u64> {
+    let p3_vecs = P::P3_LIMBS / P::M_VEC_LIMBS;
+    let mut p3 = vec![0u64; P::P3_LIMBS];
+    unpack_m_vecs(&cpk[P::PK_SEED_BYTES..], &mut p3, p3_vecs, P::M);
+    p3
 }
 
 /// Evaluate the public map: compute SPS from s and P1, P2, P3.
@@ -55,10 +92,10 @@ fn eval_public_map<P: MayoParameter>(
     s: &[u8],
     p1: &[u64],
     p2: &[u64],
-    p3: &[u64],
+    p3: P3Input<'_>,
     eval: &mut [u8],
     sps: &mut [u64],
-    scratch: &mut PsSpsScratch,
+    ps: &mut [u64],
 ) {
     let m_vec_limbs = P::M_VEC_LIMBS;
     let param_k = P::K;
@@ -66,7 +103,7 @@ fn eval_public_map<P: MayoParameter>(
     let sps_len = param_k * param_k * m_vec_limbs;
     debug_assert!(sps.len() >= sps_len);
     let sps = &mut sps[..sps_len];
-    m_calculate_ps_sps_with_scratch::<P>(p1, p2, p3, s, sps, scratch);
+    m_calculate_ps_sps::<P>(p1, p2, p3, s, sps, ps);
 
     let zero = [0u8; MAX_M];
     compute_rhs::<P>(sps, &zero, eval);
@@ -75,29 +112,188 @@ fn eval_public_map<P: MayoParameter>(
 /// Verify a MAYO signature.
 ///
 /// Returns `Ok(())` if the signature is valid, `Err(VerificationFailed)` otherwise.
-pub(crate) fn mayo_verify<P: MayoParameter>(msg: &[u8], sig: &[u8], cpk: &[u8]) -> Result<()> {
-    let (pk, p3) = expand_public_key::<P>(cpk);
+///
+/// P3 is read straight from the packed public key rather than unpacked up
+/// front, since a one-shot verify touches each P3 entry only once.
+pub(crate) fn mayo_verify<P: MayoParameter, X: Xof>(
+    msg: &[u8],
+    sig: &[u8],
+    cpk: &[u8],
+) -> Result<()> {
+    mayo_verify_with_digester::<P, X, XofDigester<X>>(msg, sig, cpk)
+}
+
+/// [`mayo_verify`] with the message digest computed by `D`.
+pub(crate) fn mayo_verify_with_digester<P: MayoParameter, X: Xof, D: Digester>(
+    msg: &[u8],
+    sig: &[u8],
+    cpk: &[u8],
+) -> Result<()> {
+    let pk = expand_p1_p2::<P>(&cpk[..P::PK_SEED_BYTES])?;
+    let (p1, p2) = pk.split_at(P::P1_LIMBS);
+    let p3 = P3Input::Packed(&cpk[P::PK_SEED_BYTES..]);
     let mut scratch = VerifyScratch::new::<P>();
-    mayo_verify_with_expanded_pk_and_scratch::<P>(msg, sig, &pk, &p3, &mut scratch)
+    mayo_verify_split_with_scratch::<P, X, D>(msg, sig, p1, p2, p3, &mut scratch.buffers::<P>())
 }
 
-pub(crate) fn mayo_verify_split_with_scratch<P: MayoParameter>(
+/// Limbs [`mayo_verify_in`] works in: P1, P2 and the [`VerifyBuffers`].
+pub(crate) const fn verify_in_limbs<P: MayoParameter>() -> usize {
+    P::P1_LIMBS + P::P2_LIMBS + VerifyBuffers::limbs::<P>()
+}
+
+/// [`mayo_verify`] working only in `limbs` and `bytes`, which must hold at
+/// least [`verify_in_limbs`] limbs and [`VerifyBuffers::bytes`] bytes.
+pub(crate) fn mayo_verify_in<P: MayoParameter, X: Xof>(
+    msg: &[u8],
+    sig: &[u8],
+    cpk: &[u8],
+    limbs: &mut [u64],
+    bytes: &mut [u8],
+) -> Result<()> {
+    let mut limbs = Carve::new(limbs);
+    let mut bytes = Carve::new(bytes);
+    let pk = limbs.take(P::P1_LIMBS + P::P2_LIMBS);
+    expand_p1_p2_into::<P>(&cpk[..P::PK_SEED_BYTES], pk)?;
+    let (p1, p2) = pk.split_at(P::P1_LIMBS);
+    let p3 = P3Input::Packed(&cpk[P::PK_SEED_BYTES..]);
+    let mut buffers = VerifyBuffers::carve::<P>(&mut limbs, &mut bytes);
+    mayo_verify_split_with_scratch::<P, X, XofDigester<X>>(msg, sig, p1, p2, p3, &mut buffers)
+}
+
+/// Verify a MAYO signature, returning the outcome as a [`Choice`].
+///
+/// Errors are reserved for malformed inputs (wrong signature or key length);
+/// a well-formed signature that does not verify is `Ok(Choice(0))`.
+#[cfg(feature = "subtle")]
+pub(crate) fn mayo_verify_choice<P: MayoParameter, X: Xof>(
+    msg: &[u8],
+    sig: &[u8],
+    cpk: &[u8],
+) -> Result<Choice> {
+    let pk = expand_p1_p2::<P>(&cpk[..P::PK_SEED_BYTES])?;
+    let (p1, p2) = pk.split_at(P::P1_LIMBS);
+    let p3 = P3Input::Packed(&cpk[P::PK_SEED_BYTES..]);
+    let mut scratch = VerifyScratch::new::<P>();
+    verify_split_choice::<P, X, XofDigester<X>>(msg, sig, p1, p2, p3, &mut scratch.buffers::<P>())
+}
+
+pub(crate) fn mayo_verify_split_with_scratch<P: MayoParameter, X: Xof, D: Digester>(
     msg: &[u8],
     sig: &[u8],
     p1: &[u64],
     p2: &[u64],
-    p3: &[u64],
-    scratch: &mut VerifyScratch,
+    p3: P3Input<'_>,
+    scratch: &mut VerifyBuffers<'_>,
 ) -> Result<()> {
+    if bool::from(verify_split_choice::<P, X, D>(
+        msg, sig, p1, p2, p3, scratch,
+    )?) {
+        Ok(())
+    } else {
+        Err(Error::VerificationFailed)
+    }
+}
+
+/// Compute the target `t = decode(SHAKE256(D(msg) || salt))` into `t[..M]`,
+/// using `tmp` and `tenc` as working space.
+pub(crate) fn compute_target<P: MayoParameter, X: Xof, D: Digester>(
+    msg: &[u8],
+    salt: &[u8],
+    tmp: &mut [u8],
+    tenc: &mut [u8],
+    t: &mut [u8],
+) {
+    let param_digest_bytes = P::DIGEST_BYTES;
+    let tmp_len = param_digest_bytes + P::SALT_BYTES;
+    debug_assert!(tmp.len() >= tmp_len);
+    debug_assert_eq!(salt.len(), P::SALT_BYTES);
+    let tmp = &mut tmp[..tmp_len];
+
+    // Hash message
+    D::digest(msg, &mut tmp[..param_digest_bytes]);
+
+    // Compute t = SHAKE256(digest || salt)
+    tmp[param_digest_bytes..].copy_from_slice(salt);
+    debug_assert!(tenc.len() >= P::M_BYTES);
+    let tenc = &mut tenc[..P::M_BYTES];
+    X::hash(&[tmp], tenc);
+    decode(tenc, t, P::M);
+}
+
+fn verify_split_choice<P: MayoParameter, X: Xof, D: Digester>(
+    msg: &[u8],
+    sig: &[u8],
+    p1: &[u64],
+    p2: &[u64],
+    p3: P3Input<'_>,
+    scratch: &mut VerifyBuffers<'_>,
+) -> Result<Choice> {
+    if !evaluate_split::<P, X, D>(msg, sig, p1, p2, p3, scratch)? {
+        return Ok(Choice::from(0));
+    }
+    // Constant-time compare y == t
+    Ok(scratch.y[..P::M].ct_eq(&scratch.t[..P::M]))
+}
+
+/// Verify like [`mayo_verify`], but say how a failing signature failed.
+///
+/// The outer `Result` carries malformed inputs, as for `mayo_verify`. On a
+/// well-formed signature that does not verify, the inner one reports the
+/// number of entries where the evaluation `y` differs from the target `t`.
+/// Counting them is not constant time.
+pub(crate) fn mayo_verify_detail<P: MayoParameter, X: Xof>(
+    msg: &[u8],
+    sig: &[u8],
+    cpk: &[u8],
+) -> Result<core::result::Result<(), VerifyDetail>> {
+    let pk = expand_p1_p2::<P>(&cpk[..P::PK_SEED_BYTES])?;
+    let (p1, p2) = pk.split_at(P::P1_LIMBS);
+    let p3 = P3Input::Packed(&cpk[P::PK_SEED_BYTES..]);
+    let mut scratch = VerifyScratch::new::<P>();
+    let mut scratch = scratch.buffers::<P>();
+    if !evaluate_split::<P, X, XofDigester<X>>(msg, sig, p1, p2, p3, &mut scratch)? {
+        return Ok(Err(VerifyDetail::NonCanonical));
+    }
+    let mismatched = scratch.y[..P::M]
+        .iter()
+        .zip(&scratch.t[..P::M])
+        .filter(|(y, t)| y != t)
+        .count();
+    if mismatched == 0 {
+        Ok(Ok(()))
+    } else {
+        Ok(Err(VerifyDetail::Mismatch {
+            mismatched,
+            total: P::M,
+        }))
+    }
+}
+
+/// Compute the target into `scratch.t[..M]` and the public map evaluated at
+/// the signature's `s` into `scratch.y[..M]`.
+///
+/// Returns `Ok(false)`, leaving `y` unset, if `s` is not canonically encoded.
+fn evaluate_split<P: MayoParameter, X: Xof, D: Digester>(
+    msg: &[u8],
+    sig: &[u8],
+    p1: &[u64],
+    p2: &[u64],
+    p3: P3Input<'_>,
+    scratch: &mut VerifyBuffers<'_>,
+) -> Result<bool> {
     let param_m = P::M;
     let param_n = P::N;
     let param_k = P::K;
-    let param_m_bytes = P::M_BYTES;
     let param_sig_bytes = P::SIG_BYTES;
-    let param_digest_bytes = P::DIGEST_BYTES;
     let param_salt_bytes = P::SALT_BYTES;
-    let VerifyScratch {
-        ps_sps,
+    if sig.len() != param_sig_bytes {
+        return Err(Error::InvalidSignatureLength {
+            expected: param_sig_bytes,
+            got: sig.len(),
+        });
+    }
+    let VerifyBuffers {
+        ps,
         sps,
         tmp,
         tenc,
@@ -106,34 +302,20 @@ pub(crate) fn mayo_verify_split_with_scratch<P: MayoParameter>(
         y,
     } = scratch;
 
-    // Hash message
-    let tmp_len = param_digest_bytes + param_salt_bytes;
-    debug_assert!(tmp.len() >= tmp_len);
-    let tmp = &mut tmp[..tmp_len];
-    {
-        let mut hasher = Shake256::default();
-        hasher.update(msg);
-        let mut reader = hasher.finalize_xof();
-        reader.read(&mut tmp[..param_digest_bytes]);
-    }
+    let salt = &sig[param_sig_bytes - param_salt_bytes..param_sig_bytes];
+    compute_target::<P, X, D>(msg, salt, tmp, tenc, t);
 
-    // Compute t = SHAKE256(digest || salt)
-    tmp[param_digest_bytes..param_digest_bytes + param_salt_bytes]
-        .copy_from_slice(&sig[param_sig_bytes - param_salt_bytes..param_sig_bytes]);
-    debug_assert!(tenc.len() >= param_m_bytes);
-    let tenc = &mut tenc[..param_m_bytes];
-    {
-        let mut hasher = Shake256::default();
-        hasher.update(&tmp[..param_digest_bytes + param_salt_bytes]);
-        let mut reader = hasher.finalize_xof();
-        reader.read(tenc);
+    // Reject non-canonical encodings of s. With an odd nibble count the high
+    // half of the last s byte is padding that decoding ignores, so a nonzero
+    // value there would be a second valid encoding of the same signature.
+    // Every current parameter set has an even k*n, so this folds away; it
+    // keeps verification strict if that ever changes.
+    let s_len = param_k * param_n;
+    if !has_zero_padding(sig, s_len) {
+        return Ok(false);
     }
-    debug_assert!(t.len() >= param_m);
-    let t = &mut t[..param_m];
-    decode(tenc, t, param_m);
 
     // Decode s from signature
-    let s_len = param_k * param_n;
     debug_assert!(s.len() >= s_len);
     let s = &mut s[..s_len];
     decode(sig, s, param_k * param_n);
@@ -141,44 +323,30 @@ pub(crate) fn mayo_verify_split_with_scratch<P: MayoParameter>(
     // Evaluate public map
     debug_assert!(y.len() >= param_m);
     let y = &mut y[..param_m];
-    eval_public_map::<P>(s, p1, p2, p3, y, sps, ps_sps);
-
-    // Constant-time compare y == t
-    if bool::from(y[..param_m].ct_eq(&t[..param_m])) {
-        Ok(())
-    } else {
-        Err(Error::VerificationFailed)
-    }
+    eval_public_map::<P>(s, p1, p2, p3, y, sps, ps);
+    Ok(true)
 }
 
-pub(crate) fn mayo_verify_with_expanded_pk_and_scratch<P: MayoParameter>(
+pub(crate) fn mayo_verify_with_expanded_pk_and_scratch<P: MayoParameter, X: Xof>(
     msg: &[u8],
     sig: &[u8],
     pk: &[u64],
     p3: &[u64],
-    scratch: &mut VerifyScratch,
+    scratch: &mut VerifyBuffers<'_>,
 ) -> Result<()> {
     let p1 = &pk[..P::P1_LIMBS];
     let p2 = &pk[P::P1_LIMBS..P::P1_LIMBS + P::P2_LIMBS];
-    mayo_verify_split_with_scratch::<P>(msg, sig, p1, p2, p3, scratch)
+    mayo_verify_split_with_scratch::<P, X, XofDigester<X>>(
+        msg,
+        sig,
+        p1,
+        p2,
+        P3Input::Unpacked(p3),
+        scratch,
+    )
 }
 
-pub(crate) fn mayo_verify_with_expanded_pk<P: MayoParameter>(
-    msg: &[u8],
-    sig: &[u8],
-    pk: &[u64],
-    p3: &[u64],
-) -> Result<()> {
-    let mut scratch = VerifyScratch::new::<P>();
-    mayo_verify_with_expanded_pk_and_scratch::<P>(msg, sig, pk, p3, &mut scratch)
-}
-
-/// Verify a signature with P1, P2, and P3 supplied as separate (non-contiguous)
-/// slices, allocating a fresh verification scratch.
-///
-/// Used by the verify-after-sign fault check, which reuses the public P1/P2 it
-/// already expanded for signing instead of re-expanding them.
-pub(crate) fn mayo_verify_with_split_pk<P: MayoParameter>(
+pub(crate) fn mayo_verify_with_expanded_pk<P: MayoParameter, X: Xof>(
     msg: &[u8],
     sig: &[u8],
     p1: &[u64],
@@ -186,5 +354,89 @@ pub(crate) fn mayo_verify_with_split_pk<P: MayoParameter>(
     p3: &[u64],
 ) -> Result<()> {
     let mut scratch = VerifyScratch::new::<P>();
-    mayo_verify_split_with_scratch::<P>(msg, sig, p1, p2, p3, &mut scratch)
+    mayo_verify_split_with_scratch::<P, X, XofDigester<X>>(
+        msg,
+        sig,
+        p1,
+        p2,
+        P3Input::Unpacked(p3),
+        &mut scratch.buffers::<P>(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xof::Shake256Xof;
+    use crate::{KeyPair, Mayo1, Mayo2};
+
+    fn truncated_signatures_rejected<P: MayoParameter>() {
+        let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+        let msg = b"truncated";
+        let sig = keypair
+            .signing_key()
+            .sign_with_rng(&mut rand::rng(), msg)
+            .expect("signing failed");
+        let cpk = keypair.verifying_key().as_ref();
+
+        let mut extended = sig.to_vec();
+        extended.push(0);
+        let lengths = (0..P::SIG_BYTES).chain(core::iter::once(P::SIG_BYTES + 1));
+        for len in lengths {
+            let got = mayo_verify::<P, Shake256Xof>(msg, &extended[..len], cpk);
+            assert!(
+                matches!(
+                    got,
+                    Err(Error::InvalidSignatureLength { expected, got })
+                        if expected == P::SIG_BYTES && got == len
+                ),
+                "length {len}"
+            );
+        }
+        assert!(mayo_verify::<P, Shake256Xof>(msg, sig.as_ref(), cpk).is_ok());
+    }
+
+    #[test]
+    fn truncated_signatures_rejected_mayo1() {
+        truncated_signatures_rejected::<Mayo1>();
+    }
+
+    #[test]
+    fn truncated_signatures_rejected_mayo2() {
+        truncated_signatures_rejected::<Mayo2>();
+    }
+
+    /// The exposed target is the one verification compares the public map
+    /// against.
+    #[cfg(feature = "internals")]
+    #[test]
+    fn exposed_target_matches_verification() {
+        let keypair = KeyPair::<Mayo1>::generate(&mut rand::rng()).expect("keygen failed");
+        let msg = b"target";
+        let sig = keypair
+            .signing_key()
+            .sign_with_rng(&mut rand::rng(), msg)
+            .expect("signing failed");
+        let cpk = keypair.verifying_key().as_ref();
+
+        let pk = expand_p1_p2::<Mayo1>(&cpk[..Mayo1::PK_SEED_BYTES]).expect("expand");
+        let (p1, p2) = pk.split_at(Mayo1::P1_LIMBS);
+        let p3 = P3Input::Packed(&cpk[Mayo1::PK_SEED_BYTES..]);
+        let mut scratch = VerifyScratch::new::<Mayo1>();
+        let mut scratch = scratch.buffers::<Mayo1>();
+        mayo_verify_split_with_scratch::<Mayo1, Shake256Xof, XofDigester<Shake256Xof>>(
+            msg,
+            sig.as_ref(),
+            p1,
+            p2,
+            p3,
+            &mut scratch,
+        )
+        .expect("verification failed");
+
+        let t = crate::internals::compute_target::<Mayo1>(msg, sig.salt()).expect("target");
+        assert_eq!(t, scratch.t);
+        // A valid signature maps to the target.
+        assert_eq!(scratch.y, scratch.t);
+    }
 }
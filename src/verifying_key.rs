@@ -2,15 +2,30 @@
 
 //! MAYO verifying (public) key.
 
-use crate::error::Error;
+use crate::codec::pack_m_vecs;
+use crate::context::{aad_preimage, context_preimage};
+#[cfg(any(feature = "std", feature = "signature-traits"))]
+use crate::digester::Prehashed;
+#[cfg(feature = "std")]
+use crate::digester::digest_reader;
+use crate::error::{Error, VerifyDetail};
+use crate::keypair::derive_cpk_from_csk;
 use crate::mayo_signature::Signature;
 use crate::params::MayoParameter;
 use crate::signing_key::SigningKey;
+#[cfg(feature = "subtle")]
+use crate::verify::mayo_verify_choice;
+#[cfg(any(feature = "std", feature = "signature-traits"))]
+use crate::verify::mayo_verify_with_digester;
 use crate::verify::{
-    VerifyScratch, expand_public_key, mayo_verify, mayo_verify_with_expanded_pk,
-    mayo_verify_with_expanded_pk_and_scratch,
+    VerifyScratch, expand_public_key, mayo_verify, mayo_verify_detail, mayo_verify_in,
+    mayo_verify_with_expanded_pk, mayo_verify_with_expanded_pk_and_scratch, unpack_p3,
 };
+use crate::workspace::Workspace;
+use crate::xof::{Shake256Xof, Xof};
 use core::marker::PhantomData;
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
 
 /// A MAYO verifying key (compact public key).
 #[derive(Clone)]
@@ -75,6 +90,17 @@ impl<P: MayoParameter> TryFrom<Box<[u8]>> for VerifyingKey<P> {
     }
 }
 
+impl<P: MayoParameter> Zeroize for VerifyingKey<P> {
+    /// Overwrite the key bytes with zeros in place.
+    ///
+    /// A verifying key is public; this exists for frameworks that manage
+    /// every key type through `Zeroize`, not as a security measure. The
+    /// length is kept and dropping a key does not wipe it.
+    fn zeroize(&mut self) {
+        self.bytes.as_mut_slice().zeroize();
+    }
+}
+
 impl<P: MayoParameter> PartialEq for VerifyingKey<P> {
     fn eq(&self, other: &Self) -> bool {
         self.bytes == other.bytes
@@ -83,6 +109,24 @@ impl<P: MayoParameter> PartialEq for VerifyingKey<P> {
 
 impl<P: MayoParameter> Eq for VerifyingKey<P> {}
 
+impl<P: MayoParameter> PartialOrd for VerifyingKey<P> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P: MayoParameter> Ord for VerifyingKey<P> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.bytes.cmp(&other.bytes)
+    }
+}
+
+impl<P: MayoParameter> core::hash::Hash for VerifyingKey<P> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.bytes.hash(state);
+    }
+}
+
 impl<P: MayoParameter> core::fmt::Debug for VerifyingKey<P> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("VerifyingKey")
@@ -92,16 +136,187 @@ impl<P: MayoParameter> core::fmt::Debug for VerifyingKey<P> {
     }
 }
 
+impl<P: MayoParameter> core::fmt::Display for VerifyingKey<P> {
+    /// The compact public key as lowercase hex.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&hex::encode(&self.bytes))
+    }
+}
+
+impl<P: MayoParameter> core::str::FromStr for VerifyingKey<P> {
+    type Err = Error;
+
+    /// Parse the hex form written by [`Display`](core::fmt::Display), in
+    /// either case. Returns [`Error::InvalidHex`] or
+    /// [`Error::InvalidKeyLength`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s).map_err(|_| Error::InvalidHex)?;
+        Self::try_from(bytes)
+    }
+}
+
 impl<P: MayoParameter> From<&SigningKey<P>> for VerifyingKey<P> {
+    /// Copy out the public key a [`SigningKey`] carries.
+    ///
+    /// The costly part, deriving the public key from the seed (a full P3
+    /// computation), happens when the signing key is constructed; this only
+    /// clones the cached bytes. Prefer [`KeyPair::verifying_key`](crate::KeyPair::verifying_key)
+    /// or [`KeyPair::verifying_key_cloned`](crate::KeyPair::verifying_key_cloned)
+    /// when a keypair is at hand.
     fn from(sk: &SigningKey<P>) -> Self {
         Self::from_bytes_unchecked(sk.cpk.clone())
     }
 }
 
+#[cfg(feature = "signature-traits")]
 impl<P: MayoParameter> signature::Verifier<Signature<P>> for VerifyingKey<P> {
     fn verify(&self, msg: &[u8], signature: &Signature<P>) -> Result<(), signature::Error> {
-        mayo_verify::<P>(msg, signature.as_ref(), &self.bytes).map_err(Into::into)
+        VerifyingKey::verify(self, msg, signature).map_err(Into::into)
+    }
+}
+
+/// Verification for [`SigningKey`]'s `signature::DigestSigner` impl: the
+/// first `DIGEST_BYTES` bytes of the fed XOF are the message digest.
+#[cfg(feature = "signature-traits")]
+impl<P, D> signature::DigestVerifier<D, Signature<P>> for VerifyingKey<P>
+where
+    P: MayoParameter,
+    D: Default + signature::digest::Update + signature::digest::ExtendableOutput,
+{
+    fn verify_digest<F: Fn(&mut D) -> Result<(), signature::Error>>(
+        &self,
+        f: F,
+        signature: &Signature<P>,
+    ) -> Result<(), signature::Error> {
+        let mut xof = D::default();
+        f(&mut xof)?;
+        let mut digest = vec![0u8; P::DIGEST_BYTES];
+        xof.finalize_xof_into(&mut digest);
+        self.verify_prehashed(&digest, signature)
+            .map_err(Into::into)
+    }
+}
+
+/// Verify `sig` over `msg` against a compact public key held as raw bytes.
+///
+/// This gives the same result as [`VerifyingKey::verify`], but borrows `cpk`
+/// instead of copying it into a key, which saves an allocation per call when
+/// the key already sits in a buffer.
+///
+/// Returns [`Error::InvalidKeyLength`] or [`Error::InvalidSignatureLength`] if
+/// `cpk` or `sig` has the wrong size for `P`, and
+/// [`Error::VerificationFailed`] if the signature does not verify.
+pub fn verify_raw<P: MayoParameter>(
+    cpk: &[u8],
+    msg: &[u8],
+    sig: &[u8],
+) -> crate::error::Result<()> {
+    if cpk.len() != P::CPK_BYTES {
+        return Err(Error::InvalidKeyLength {
+            expected: P::CPK_BYTES,
+            got: cpk.len(),
+        });
+    }
+    if sig.len() != P::SIG_BYTES {
+        return Err(Error::InvalidSignatureLength {
+            expected: P::SIG_BYTES,
+            got: sig.len(),
+        });
+    }
+    mayo_verify::<P, Shake256Xof>(msg, sig, cpk)
+}
+
+/// Find which of `keys` signed `msg`, for verifiers that hold several valid
+/// keys, e.g. during key rotation.
+///
+/// Returns the index of the first key under which `sig` verifies, or
+/// [`Error::VerificationFailed`] if none does (including for an empty
+/// slice). Each key tried is checked as [`VerifyingKey::verify`] would,
+/// expanding its public matrices, so the running time grows with the
+/// number of keys tried and reveals the position of the signer. Use
+/// [`verify_any_expanded`] when the keys are verified against repeatedly.
+pub fn verify_any<P: MayoParameter>(
+    keys: &[&VerifyingKey<P>],
+    msg: &[u8],
+    sig: &Signature<P>,
+) -> crate::error::Result<usize> {
+    keys.iter()
+        .position(|key| key.verify(msg, sig).is_ok())
+        .ok_or(Error::VerificationFailed)
+}
+
+/// [`verify_any`] over keys whose public matrices are already expanded.
+///
+/// Skips the per-key expansion; otherwise the result and the running time
+/// behave the same way.
+pub fn verify_any_expanded<P: MayoParameter>(
+    keys: &[&ExpandedVerifyingKey<P>],
+    msg: &[u8],
+    sig: &Signature<P>,
+) -> crate::error::Result<usize> {
+    keys.iter()
+        .position(|key| key.verify(msg, sig).is_ok())
+        .ok_or(Error::VerificationFailed)
+}
+
+/// Verify independent `(key, message, signature)` triples in parallel.
+///
+/// Each triple is checked exactly as [`VerifyingKey::verify`] would, on the
+/// rayon thread pool. The returned results are in input order, so
+/// `results[i]` belongs to `items[i]` regardless of scheduling.
+#[cfg(feature = "rayon")]
+pub fn verify_many<P: MayoParameter>(
+    items: &[(&VerifyingKey<P>, &[u8], &Signature<P>)],
+) -> Vec<crate::error::Result<()>> {
+    use rayon::prelude::*;
+
+    items
+        .par_iter()
+        .map(|(vk, msg, sig)| mayo_verify::<P, Shake256Xof>(msg, sig.as_ref(), &vk.bytes))
+        .collect()
+}
+
+/// Verify `sig` over `msg` against already-expanded public matrices.
+///
+/// This is the lowest-level verification entry point. It skips both the
+/// AES-CTR expansion of P1 and P2 and the unpacking of P3, for callers that
+/// persist the expanded key, e.g. from [`ExpandedVerifyingKey::p1`],
+/// [`p2`](ExpandedVerifyingKey::p2) and [`p3`](ExpandedVerifyingKey::p3).
+///
+/// Each matrix is a sequence of bitsliced m-vectors of `P::M_VEC_LIMBS`
+/// little-endian `u64` limbs, with element `i` in the nibble at bits
+/// `4 * (i % 16)` of limb `i / 16`. With `v = n - o`:
+///
+/// - `p1` is the upper triangle of a `v x v` matrix, row by row (`P::P1_LIMBS` limbs).
+/// - `p2` is a full `v x o` matrix, row-major (`P::P2_LIMBS` limbs).
+/// - `p3` is the upper triangle of an `o x o` matrix, row by row (`P::P3_LIMBS` limbs).
+///
+/// | Type | `P1_LIMBS` | `P2_LIMBS` | `P3_LIMBS` |
+/// |------|------------|------------|------------|
+/// | [`Mayo1`](crate::Mayo1) | 15,405 | 3,120 | 180 |
+/// | [`Mayo2`](crate::Mayo2) | 12,960 | 5,120 | 544 |
+/// | [`Mayo3`](crate::Mayo3) | 41,202 | 7,560 | 385 |
+/// | [`Mayo5`](crate::Mayo5) | 91,377 | 15,336 | 702 |
+///
+/// Returns [`Error::InvalidKeyLength`] (with lengths counted in limbs) if a
+/// matrix has the wrong size, [`Error::InvalidSignatureLength`] if `sig` does,
+/// and [`Error::VerificationFailed`] if the signature does not verify.
+pub fn verify_with_expanded<P: MayoParameter>(
+    p1: &[u64],
+    p2: &[u64],
+    p3: &[u64],
+    msg: &[u8],
+    sig: &[u8],
+) -> crate::error::Result<()> {
+    for (limbs, expected) in [(p1, P::P1_LIMBS), (p2, P::P2_LIMBS), (p3, P::P3_LIMBS)] {
+        if limbs.len() != expected {
+            return Err(Error::InvalidKeyLength {
+                expected,
+                got: limbs.len(),
+            });
+        }
     }
+    mayo_verify_with_expanded_pk::<P, Shake256Xof>(msg, sig, p1, p2, p3)
 }
 
 impl<P: MayoParameter> VerifyingKey<P> {
@@ -112,10 +327,247 @@ impl<P: MayoParameter> VerifyingKey<P> {
         }
     }
 
+    /// Decode a verifying key and run [`validate`](Self::validate) on it.
+    ///
+    /// Returns [`Error::InvalidKeyLength`] if `bytes` is not `CPK_BYTES` long
+    /// and [`Error::MalformedKey`] if the contents fail validation, so that a
+    /// damaged key is reported here rather than by every later verification.
+    pub fn try_from_validated(bytes: &[u8]) -> Result<Self, Error> {
+        let key = Self::try_from(bytes)?;
+        key.validate()?;
+        Ok(key)
+    }
+
+    /// Run a deep structural check of this key.
+    ///
+    /// Decoding via `TryFrom` stays cheap and only checks the total length.
+    /// This re-expands P1 and P2 from the public seed, unpacks P3, and checks
+    /// that every component has the size the parameter set requires and that
+    /// P3 re-encodes to the stored bytes. It also rejects a key in which any
+    /// P3 entry is the zero m-vector: an honestly generated key has one with
+    /// probability about `2^-4m` per entry, while a truncated key padded back
+    /// to length with zeros almost always does.
+    ///
+    /// The packed encoding is a bijection for all MAYO parameter sets, so a
+    /// key with a flipped bit in P3 still passes; such a key is simply a
+    /// different public key and will reject signatures from the original.
+    ///
+    /// Returns [`Error::InvalidKeyLength`] for a key of the wrong length and
+    /// [`Error::MalformedKey`] if any other check fails.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.bytes.len() != P::CPK_BYTES || self.bytes.len() - P::PK_SEED_BYTES != P::P3_BYTES {
+            return Err(Error::InvalidKeyLength {
+                expected: P::CPK_BYTES,
+                got: self.bytes.len(),
+            });
+        }
+        let (pk, p3) = expand_public_key::<P>(&self.bytes)?;
+        if pk.len() != P::P1_LIMBS + P::P2_LIMBS || p3.len() != P::P3_LIMBS {
+            return Err(Error::MalformedKey);
+        }
+        let packed = &self.bytes[P::PK_SEED_BYTES..];
+        let mut repacked = vec![0u8; P::P3_BYTES];
+        pack_m_vecs(&p3, &mut repacked, P::P3_LIMBS / P::M_VEC_LIMBS, P::M);
+        if repacked != packed {
+            return Err(Error::MalformedKey);
+        }
+        if packed
+            .chunks_exact(P::M_BYTES)
+            .any(|entry| entry.iter().all(|&b| b == 0))
+        {
+            return Err(Error::MalformedKey);
+        }
+        Ok(())
+    }
+
+    /// Whether `signing_key` is the private half of this key.
+    ///
+    /// Useful when the two halves are loaded from separate places. The public
+    /// key is re-derived from the signing key's seed, which costs about as
+    /// much as key generation, rather than read from the copy the signing key
+    /// caches; the comparison is constant time.
+    pub fn matches_signing_key(&self, signing_key: &SigningKey<P>) -> bool {
+        let mut cpk = vec![0u8; P::CPK_BYTES];
+        derive_cpk_from_csk::<P, Shake256Xof>(signing_key.as_ref(), &mut cpk).is_ok()
+            && bool::from(cpk.ct_eq(&self.bytes))
+    }
+
     /// Expand this compact verifying key for faster repeated verification.
     pub fn expand(&self) -> ExpandedVerifyingKey<P> {
         ExpandedVerifyingKey::from_bytes_unchecked(self.bytes.clone())
     }
+
+    /// Verify a signature made with
+    /// [`SigningKey::sign_with_context`] under the context string `ctx`.
+    ///
+    /// Returns [`Error::InvalidContextLength`] if `ctx` is longer than 255
+    /// bytes, [`Error::ReservedContext`] for a context reserved by the
+    /// associated-data methods, and [`Error::VerificationFailed`] if the
+    /// signature does not verify, including when it was made under a
+    /// different context.
+    pub fn verify_with_context(
+        &self,
+        ctx: &[u8],
+        msg: &[u8],
+        signature: &Signature<P>,
+    ) -> Result<(), Error> {
+        let preimage = context_preimage(ctx, msg)?;
+        mayo_verify::<P, Shake256Xof>(&preimage, signature.as_ref(), &self.bytes)
+    }
+
+    /// Verify a signature made with [`SigningKey::sign_with_aad`] over `msg`
+    /// and the associated data `aad`.
+    ///
+    /// Returns [`Error::VerificationFailed`] if the signature does not
+    /// verify, including when `aad` differs from what was signed.
+    pub fn verify_with_aad(
+        &self,
+        aad: &[u8],
+        msg: &[u8],
+        signature: &Signature<P>,
+    ) -> Result<(), Error> {
+        let preimage = aad_preimage(None, aad, msg)?;
+        mayo_verify::<P, Shake256Xof>(&preimage, signature.as_ref(), &self.bytes)
+    }
+
+    /// Verify a signature made with [`SigningKey::sign_with_context_and_aad`].
+    ///
+    /// Returns [`Error::InvalidContextLength`] if `ctx` is longer than 255
+    /// bytes and [`Error::VerificationFailed`] if the signature does not
+    /// verify under exactly this `ctx`, `aad` and `msg`.
+    pub fn verify_with_context_and_aad(
+        &self,
+        ctx: &[u8],
+        aad: &[u8],
+        msg: &[u8],
+        signature: &Signature<P>,
+    ) -> Result<(), Error> {
+        let preimage = aad_preimage(Some(ctx), aad, msg)?;
+        mayo_verify::<P, Shake256Xof>(&preimage, signature.as_ref(), &self.bytes)
+    }
+
+    /// Verify `signature` over everything `reader` yields, streaming it
+    /// through SHAKE256 in fixed-size chunks instead of holding it in memory.
+    ///
+    /// Accepts exactly the signatures [`verify`](Self::verify) accepts for
+    /// the whole contents. Returns [`Error::Io`] if reading fails and
+    /// [`Error::VerificationFailed`] if the signature does not verify.
+    #[cfg(feature = "std")]
+    pub fn verify_reader(
+        &self,
+        reader: impl std::io::Read,
+        signature: &Signature<P>,
+    ) -> Result<(), Error> {
+        let mut digest = vec![0u8; P::DIGEST_BYTES];
+        digest_reader::<Shake256Xof>(reader, &mut digest)?;
+        self.verify_prehashed(&digest, signature)
+    }
+
+    /// Verify a signature over a message given as its `DIGEST_BYTES`-long
+    /// digest.
+    #[cfg(any(feature = "std", feature = "signature-traits"))]
+    fn verify_prehashed(&self, digest: &[u8], signature: &Signature<P>) -> Result<(), Error> {
+        mayo_verify_with_digester::<P, Shake256Xof, Prehashed>(
+            digest,
+            signature.as_ref(),
+            &self.bytes,
+        )
+    }
+
+    /// A 32-byte identifier for this key: `SHAKE256(cpk)` truncated.
+    ///
+    /// Equal keys have equal commitments and distinct keys, in practice,
+    /// distinct ones, so it serves as a cache key, fingerprint or key id in
+    /// a protocol header. It is derived from public bytes only and is not
+    /// secret.
+    pub fn commitment(&self) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        Shake256Xof::hash(&[&self.bytes], &mut out);
+        out
+    }
+
+    /// A 64-bit identifier for this key: its [`Hash`](core::hash::Hash)
+    /// under [`DefaultHasher`](std::hash::DefaultHasher).
+    ///
+    /// Cheaper to compare and store than [`commitment`](Self::commitment),
+    /// for deduplicating keys within one process. It is not collision
+    /// resistant: distinct keys with the same id can be found with little
+    /// effort, so never use it to identify a key to another party or to
+    /// decide which key to trust. The value may also change between Rust
+    /// releases; do not persist it.
+    pub fn key_id(&self) -> u64 {
+        use core::hash::{Hash, Hasher};
+
+        let mut hasher = std::hash::DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Verify `signature` over `msg`.
+    ///
+    /// The check behind the `signature::Verifier` impl, available without
+    /// the `signature-traits` feature. Returns [`Error::VerificationFailed`]
+    /// if the signature does not verify.
+    pub fn verify(&self, msg: &[u8], signature: &Signature<P>) -> Result<(), Error> {
+        mayo_verify::<P, Shake256Xof>(msg, signature.as_ref(), &self.bytes)
+    }
+
+    /// Verify `signature` over `msg`, explaining a failure.
+    ///
+    /// Accepts exactly what [`verify`](Self::verify) accepts. On
+    /// failure, [`VerifyDetail::Mismatch`] counts how many of the `m` target
+    /// entries the signature misses. A wrong key or message typically misses
+    /// nearly all of them (about 15 in 16), while a signature damaged after
+    /// signing may miss only a few.
+    ///
+    /// Meant for debugging integrations. The count says how close a
+    /// candidate signature is, which must not reach an attacker, and it is
+    /// computed in variable time; production code should use
+    /// [`verify`](Self::verify).
+    pub fn verify_verbose(&self, msg: &[u8], signature: &Signature<P>) -> Result<(), VerifyDetail> {
+        mayo_verify_detail::<P, Shake256Xof>(msg, signature.as_ref(), &self.bytes)
+            .unwrap_or(Err(VerifyDetail::Malformed))
+    }
+
+    /// Verify a signature given as raw bytes, without building a [`Signature`].
+    ///
+    /// Suits signatures that arrive in a network buffer: the bytes are
+    /// verified in place. Returns [`Error::InvalidSignatureLength`] unless
+    /// `sig_bytes` is exactly `P::SIG_BYTES` long, and
+    /// [`Error::VerificationFailed`] if the signature does not verify.
+    pub fn verify_from_slice(&self, msg: &[u8], sig_bytes: &[u8]) -> Result<(), Error> {
+        verify_raw::<P>(&self.bytes, msg, sig_bytes)
+    }
+
+    /// [`verify_from_slice`](Self::verify_from_slice) without allocating.
+    ///
+    /// The public key is expanded into `workspace`, which also holds every
+    /// intermediate value, so verification makes no heap allocation.
+    pub fn verify_from_slice_with_workspace<const LIMBS: usize, const BYTES: usize>(
+        &self,
+        msg: &[u8],
+        sig_bytes: &[u8],
+        workspace: &mut Workspace<P, LIMBS, BYTES>,
+    ) -> Result<(), Error> {
+        let (limbs, bytes) = workspace.buffers();
+        mayo_verify_in::<P, Shake256Xof>(msg, sig_bytes, &self.bytes, limbs, bytes)
+    }
+
+    /// Verify `signature` over `msg`, returning the outcome as a [`subtle::Choice`].
+    ///
+    /// Where [`verify`](signature::Verifier::verify) returns early with an
+    /// error, this hands back the result of the final constant-time
+    /// comparison, so it can be combined with other `Choice`s (`&`, `|`)
+    /// before anything branches on it. Convert to `bool` once, at the point
+    /// where the caller actually acts on the combined result.
+    ///
+    /// A valid signature gives `Choice(1)`; anything else, including a key
+    /// that fails to expand, gives `Choice(0)`.
+    #[cfg(feature = "subtle")]
+    pub fn verify_ct(&self, msg: &[u8], signature: &Signature<P>) -> subtle::Choice {
+        mayo_verify_choice::<P, Shake256Xof>(msg, signature.as_ref(), &self.bytes)
+            .unwrap_or_else(|_| subtle::Choice::from(0))
+    }
 }
 
 /// A MAYO verifying key with cached expanded public material.
@@ -229,16 +681,17 @@ impl<P: MayoParameter> core::fmt::Debug for VerificationContext<P> {
     }
 }
 
+#[cfg(feature = "signature-traits")]
 impl<P: MayoParameter> signature::Verifier<Signature<P>> for ExpandedVerifyingKey<P> {
     fn verify(&self, msg: &[u8], signature: &Signature<P>) -> Result<(), signature::Error> {
-        mayo_verify_with_expanded_pk::<P>(msg, signature.as_ref(), &self.expanded_pk, &self.p3)
-            .map_err(Into::into)
+        ExpandedVerifyingKey::verify(self, msg, signature).map_err(Into::into)
     }
 }
 
 impl<P: MayoParameter> ExpandedVerifyingKey<P> {
     fn from_bytes_unchecked(bytes: Vec<u8>) -> Self {
-        let (expanded_pk, p3) = expand_public_key::<P>(&bytes);
+        let (expanded_pk, p3) =
+            expand_public_key::<P>(&bytes).expect("a CPK_BYTES-long key holds a full public seed");
         Self {
             bytes,
             expanded_pk,
@@ -247,11 +700,48 @@ impl<P: MayoParameter> ExpandedVerifyingKey<P> {
         }
     }
 
+    /// Build from `P1 || P2` that is already expanded, e.g. by an
+    /// [`ExpandedSigningKey`](crate::ExpandedSigningKey) for the same key, so
+    /// only P3 is unpacked from `bytes`.
+    pub(crate) fn from_expanded_parts(bytes: Vec<u8>, expanded_pk: Vec<u64>) -> Self {
+        debug_assert_eq!(expanded_pk.len(), P::P1_LIMBS + P::P2_LIMBS);
+        Self {
+            p3: unpack_p3::<P>(&bytes),
+            bytes,
+            expanded_pk,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Verify `signature` over `msg`.
+    ///
+    /// The check behind the `signature::Verifier` impl, available without
+    /// the `signature-traits` feature. Returns [`Error::VerificationFailed`]
+    /// if the signature does not verify.
+    pub fn verify(&self, msg: &[u8], signature: &Signature<P>) -> Result<(), Error> {
+        verify_with_expanded::<P>(self.p1(), self.p2(), self.p3(), msg, signature.as_ref())
+    }
+
     /// Return the compact verifying key form.
     pub fn compact(&self) -> VerifyingKey<P> {
         VerifyingKey::from_bytes_unchecked(self.bytes.clone())
     }
 
+    /// The expanded P1 limbs, see [`verify_with_expanded`] for the layout.
+    pub fn p1(&self) -> &[u64] {
+        &self.expanded_pk[..P::P1_LIMBS]
+    }
+
+    /// The expanded P2 limbs, see [`verify_with_expanded`] for the layout.
+    pub fn p2(&self) -> &[u64] {
+        &self.expanded_pk[P::P1_LIMBS..]
+    }
+
+    /// The unpacked P3 limbs, see [`verify_with_expanded`] for the layout.
+    pub fn p3(&self) -> &[u64] {
+        &self.p3
+    }
+
     /// Create a reusable verification context for this expanded key.
     pub fn context(&self) -> VerificationContext<P> {
         VerificationContext::from(self)
@@ -260,15 +750,16 @@ impl<P: MayoParameter> ExpandedVerifyingKey<P> {
 
 impl<P: MayoParameter> VerificationContext<P> {
     /// Verify a signature using cached expanded public material and scratch buffers.
-    pub fn verify(&mut self, msg: &[u8], signature: &Signature<P>) -> Result<(), signature::Error> {
-        mayo_verify_with_expanded_pk_and_scratch::<P>(
+    ///
+    /// Returns [`Error::VerificationFailed`] if the signature does not verify.
+    pub fn verify(&mut self, msg: &[u8], signature: &Signature<P>) -> Result<(), Error> {
+        mayo_verify_with_expanded_pk_and_scratch::<P, Shake256Xof>(
             msg,
             signature.as_ref(),
             &self.key.expanded_pk,
             &self.key.p3,
-            &mut self.scratch,
+            &mut self.scratch.buffers::<P>(),
         )
-        .map_err(Into::into)
     }
 
     /// Return the expanded verifying key backing this context.
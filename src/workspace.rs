@@ -0,0 +1,131 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Fixed-size working memory for signing and verifying without a heap.
+
+use crate::params::{Mayo1, Mayo2, Mayo3, Mayo5, MayoParameter};
+use crate::sign::{sign_in_bytes, sign_in_limbs};
+use crate::verify::{VerifyBuffers, verify_in_limbs};
+use core::marker::PhantomData;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Working memory for signing and verifying with parameter set `P`, held
+/// inline so that neither touches the heap.
+///
+/// Pass it to
+/// [`SigningKey::sign_inline_with_workspace`](crate::SigningKey::sign_inline_with_workspace)
+/// and
+/// [`VerifyingKey::verify_from_slice_with_workspace`](crate::VerifyingKey::verify_from_slice_with_workspace).
+/// Together with an [`InlineSignature`](crate::InlineSignature) for the
+/// result, signing and verifying then make no heap allocation at all.
+///
+/// Name the type through the alias for the parameter set, e.g.
+/// [`Mayo1Workspace`]. `LIMBS` and `BYTES` are its size, and
+/// [`new`](Self::new) fails to compile if they are too small for `P`.
+///
+/// The workspace holds the expanded key, so it is large: about 300 KB for
+/// MAYO_1 and MAYO_2, 710 KB for MAYO_3 and 1.5 MB for MAYO_5. Keep it in a
+/// `static` or on a stack with room to spare. Everything in it is zeroized after each signature and on
+/// drop; one workspace serves one operation at a time.
+pub struct Workspace<P: MayoParameter, const LIMBS: usize, const BYTES: usize> {
+    limbs: [u64; LIMBS],
+    bytes: [u8; BYTES],
+    _marker: PhantomData<P>,
+}
+
+/// [`Workspace`] for [`Mayo1`].
+pub type Mayo1Workspace = Workspace<Mayo1, { limbs::<Mayo1>() }, { bytes::<Mayo1>() }>;
+/// [`Workspace`] for [`Mayo2`].
+pub type Mayo2Workspace = Workspace<Mayo2, { limbs::<Mayo2>() }, { bytes::<Mayo2>() }>;
+/// [`Workspace`] for [`Mayo3`].
+pub type Mayo3Workspace = Workspace<Mayo3, { limbs::<Mayo3>() }, { bytes::<Mayo3>() }>;
+/// [`Workspace`] for [`Mayo5`].
+pub type Mayo5Workspace = Workspace<Mayo5, { limbs::<Mayo5>() }, { bytes::<Mayo5>() }>;
+
+/// Limbs a workspace for `P` needs; signing needs more than verifying.
+const fn limbs<P: MayoParameter>() -> usize {
+    let (sign, verify) = (sign_in_limbs::<P>(), verify_in_limbs::<P>());
+    if sign > verify { sign } else { verify }
+}
+
+/// Bytes a workspace for `P` needs.
+const fn bytes<P: MayoParameter>() -> usize {
+    let (sign, verify) = (sign_in_bytes::<P>(), VerifyBuffers::bytes::<P>());
+    if sign > verify { sign } else { verify }
+}
+
+impl<P: MayoParameter, const LIMBS: usize, const BYTES: usize> Workspace<P, LIMBS, BYTES> {
+    /// A zeroed workspace.
+    pub const fn new() -> Self {
+        const {
+            assert!(
+                LIMBS >= limbs::<P>() && BYTES >= bytes::<P>(),
+                "workspace too small for the parameter set"
+            );
+        }
+        Self {
+            limbs: [0u64; LIMBS],
+            bytes: [0u8; BYTES],
+            _marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn buffers(&mut self) -> (&mut [u64], &mut [u8]) {
+        (&mut self.limbs, &mut self.bytes)
+    }
+}
+
+impl<P: MayoParameter, const LIMBS: usize, const BYTES: usize> Default
+    for Workspace<P, LIMBS, BYTES>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: MayoParameter, const LIMBS: usize, const BYTES: usize> Zeroize
+    for Workspace<P, LIMBS, BYTES>
+{
+    fn zeroize(&mut self) {
+        self.limbs.zeroize();
+        self.bytes.zeroize();
+    }
+}
+
+impl<P: MayoParameter, const LIMBS: usize, const BYTES: usize> Drop for Workspace<P, LIMBS, BYTES> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<P: MayoParameter, const LIMBS: usize, const BYTES: usize> ZeroizeOnDrop
+    for Workspace<P, LIMBS, BYTES>
+{
+}
+
+impl<P: MayoParameter, const LIMBS: usize, const BYTES: usize> core::fmt::Debug
+    for Workspace<P, LIMBS, BYTES>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Workspace")
+            .field("variant", &P::NAME)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Hands out consecutive, non-overlapping pieces of a buffer.
+#[derive(Debug)]
+pub(crate) struct Carve<'a, T>(&'a mut [T]);
+
+impl<'a, T> Carve<'a, T> {
+    pub(crate) fn new(buf: &'a mut [T]) -> Self {
+        Self(buf)
+    }
+
+    /// The next `len` entries. Panics if fewer are left, which means the
+    /// buffer was sized for a different parameter set.
+    pub(crate) fn take(&mut self, len: usize) -> &'a mut [T] {
+        let (head, tail) = core::mem::take(&mut self.0).split_at_mut(len);
+        self.0 = tail;
+        head
+    }
+}
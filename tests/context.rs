@@ -0,0 +1,273 @@
+//! Context-bound signing tests.
+//!
+//! Signing under context `ctx` is defined as signing `len(ctx) || ctx || msg`
+//! with the plain scheme, which is how the MAYO reference implementation
+//! would be driven to produce the same signature. The fixed vectors use an
+//! all-zero randomizer, MAYO's deterministic mode, from the seed `00 01 02 ..`.
+
+use core::convert::Infallible;
+use pq_mayo::{Error, KeyPair, Mayo1, Mayo2, Mayo3, Mayo5, MayoParameter};
+use shake::Shake256;
+use shake::digest::{ExtendableOutput, Update, XofReader};
+
+/// An RNG that yields only zeros, selecting the deterministic randomizer.
+struct ZeroRng;
+
+impl rand::TryRng for ZeroRng {
+    type Error = Infallible;
+
+    fn try_next_u32(&mut self) -> Result<u32, Infallible> {
+        Ok(0)
+    }
+
+    fn try_next_u64(&mut self) -> Result<u64, Infallible> {
+        Ok(0)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Infallible> {
+        dest.fill(0);
+        Ok(())
+    }
+}
+
+impl rand::TryCryptoRng for ZeroRng {}
+
+/// SHAKE256 of `data`, truncated to 32 bytes, as lowercase hex.
+fn digest_hex(data: &[u8]) -> String {
+    let mut hasher = Shake256::default();
+    hasher.update(data);
+    let mut out = [0u8; 32];
+    hasher.finalize_xof().read(&mut out);
+    out.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn fixed_keypair<P: MayoParameter>() -> KeyPair<P> {
+    let seed: Vec<u8> = (0u8..).take(P::SK_SEED_BYTES).collect();
+    KeyPair::<P>::from_seed(&seed).expect("from_seed")
+}
+
+/// `(ctx, msg, digest of the deterministic signature)`.
+type Vector = (&'static [u8], &'static [u8], &'static str);
+
+const LONG_CONTEXT: [u8; 255] = [0xAA; 255];
+
+fn fixed_vectors<P: MayoParameter>(vectors: &[Vector]) {
+    let keypair = fixed_keypair::<P>();
+    let vk = keypair.verifying_key();
+    for &(ctx, msg, expected) in vectors {
+        let sig = keypair
+            .signing_key()
+            .sign_with_context(&mut ZeroRng, ctx, msg)
+            .expect("signing failed");
+        assert_eq!(digest_hex(sig.as_ref()), expected);
+        assert!(vk.verify_with_context(ctx, msg, &sig).is_ok());
+
+        // Interoperability: it is the plain signature of len || ctx || msg.
+        let preimage = [
+            &[u8::try_from(ctx.len()).expect("short context")][..],
+            ctx,
+            msg,
+        ]
+        .concat();
+        assert!(vk.verify(&preimage, &sig).is_ok());
+        let plain = keypair
+            .signing_key()
+            .sign_deterministic(&preimage)
+            .expect("signing failed");
+        assert_eq!(plain, sig);
+    }
+}
+
+#[test]
+fn fixed_vectors_mayo1() {
+    fixed_vectors::<Mayo1>(&[
+        (
+            b"",
+            b"message",
+            "7408485849e393593cd71d79329dfa6d3603c2e79d9add6ce894b621561675c6",
+        ),
+        (
+            b"app-v1",
+            b"message",
+            "0a70e47aa43165131da6df5c2b52b7312ef052ff2c53347f7f4e401678a25ad8",
+        ),
+        (
+            &LONG_CONTEXT,
+            b"",
+            "d3082c6296e117014d7e35b0da8b62ddfa9b0f8f52f0dd0c71cddeeb75f72b0d",
+        ),
+    ]);
+}
+
+#[test]
+fn fixed_vectors_mayo2() {
+    fixed_vectors::<Mayo2>(&[
+        (
+            b"",
+            b"message",
+            "1413982263f683c0f6b896b8885c51d65890710103a0cf266fbb9d58e597d38d",
+        ),
+        (
+            b"app-v1",
+            b"message",
+            "5afabaa4dd67786bbe8a9073572f2b742376b50251d82970ab11b019b15565f7",
+        ),
+        (
+            &LONG_CONTEXT,
+            b"",
+            "f7a1d74e45cd36012db9569f29dea699bdee1f7aaa4e3c307f6538091f0867c1",
+        ),
+    ]);
+}
+
+fn context_separation<P: MayoParameter>() {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let vk = keypair.verifying_key();
+    let sig = keypair
+        .signing_key()
+        .sign_with_context(&mut rand::rng(), b"ctx-a", b"message")
+        .expect("signing failed");
+
+    assert!(vk.verify_with_context(b"ctx-a", b"message", &sig).is_ok());
+    assert!(vk.verify_with_context(b"ctx-b", b"message", &sig).is_err());
+    assert!(vk.verify_with_context(b"", b"message", &sig).is_err());
+    assert!(vk.verify(b"message", &sig).is_err());
+    // The length prefix keeps the split between context and message unambiguous.
+    assert!(vk.verify_with_context(b"ctx-", b"amessage", &sig).is_err());
+}
+
+#[test]
+fn context_separation_mayo3() {
+    context_separation::<Mayo3>();
+}
+
+#[test]
+fn context_separation_mayo5() {
+    context_separation::<Mayo5>();
+}
+
+#[test]
+fn overlong_context_rejected() {
+    let keypair = fixed_keypair::<Mayo1>();
+    let ctx = [0u8; 256];
+    let err = keypair
+        .signing_key()
+        .sign_with_context(&mut rand::rng(), &ctx, b"message")
+        .expect_err("256-byte context");
+    assert!(matches!(
+        err,
+        Error::InvalidContextLength { max: 255, got: 256 }
+    ));
+    assert!(err.is_length_error());
+
+    let sig = keypair
+        .signing_key()
+        .sign_with_context(&mut rand::rng(), &ctx[..255], b"message")
+        .expect("signing failed");
+    assert!(matches!(
+        keypair
+            .verifying_key()
+            .verify_with_context(&ctx, b"message", &sig),
+        Err(Error::InvalidContextLength { .. })
+    ));
+}
+
+fn aad_binding<P: MayoParameter>() {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let vk = keypair.verifying_key();
+    let sig = keypair
+        .signing_key()
+        .sign_with_aad(&mut rand::rng(), b"header", b"body")
+        .expect("signing failed");
+
+    assert!(vk.verify_with_aad(b"header", b"body", &sig).is_ok());
+    assert!(vk.verify_with_aad(b"heade!", b"body", &sig).is_err());
+    assert!(vk.verify_with_aad(b"", b"body", &sig).is_err());
+    assert!(vk.verify_with_aad(b"header", b"bodY", &sig).is_err());
+    // The length prefix keeps the split between AAD and message unambiguous.
+    assert!(vk.verify_with_aad(b"head", b"erbody", &sig).is_err());
+
+    // Neither a plain nor a context signature, however the bytes are split.
+    assert!(vk.verify(b"headerbody", &sig).is_err());
+    assert!(vk.verify(b"body", &sig).is_err());
+    let framed = [&6u64.to_le_bytes()[..], b"header", b"body"].concat();
+    assert!(vk.verify(&framed, &sig).is_err());
+    assert!(vk.verify_with_context(b"header", b"body", &sig).is_err());
+    assert!(
+        vk.verify_with_context(&[0u8; 6], b"\0headerbody", &sig)
+            .is_err()
+    );
+    assert!(vk.verify_with_context(b"", &framed, &sig).is_err());
+    assert!(
+        vk.verify_with_context_and_aad(b"", b"header", b"body", &sig)
+            .is_err()
+    );
+}
+
+#[test]
+fn aad_binding_mayo1() {
+    aad_binding::<Mayo1>();
+}
+
+#[test]
+fn aad_binding_mayo2() {
+    aad_binding::<Mayo2>();
+}
+
+#[test]
+fn context_and_aad_binding() {
+    let keypair = fixed_keypair::<Mayo1>();
+    let vk = keypair.verifying_key();
+    let sig = keypair
+        .signing_key()
+        .sign_with_context_and_aad(&mut rand::rng(), b"app-v1", b"header", b"body")
+        .expect("signing failed");
+
+    assert!(
+        vk.verify_with_context_and_aad(b"app-v1", b"header", b"body", &sig)
+            .is_ok()
+    );
+    assert!(
+        vk.verify_with_context_and_aad(b"app-v2", b"header", b"body", &sig)
+            .is_err()
+    );
+    assert!(
+        vk.verify_with_context_and_aad(b"app-v1", b"footer", b"body", &sig)
+            .is_err()
+    );
+    assert!(vk.verify_with_aad(b"header", b"body", &sig).is_err());
+    assert!(vk.verify_with_context(b"app-v1", b"body", &sig).is_err());
+    let inner = [&6u64.to_le_bytes()[..], b"header", b"body"].concat();
+    assert!(vk.verify_with_context(b"app-v1", &inner, &sig).is_err());
+
+    assert!(matches!(
+        keypair
+            .signing_key()
+            .sign_with_context_and_aad(&mut rand::rng(), &[0u8; 256], b"", b""),
+        Err(Error::InvalidContextLength { max: 255, got: 256 })
+    ));
+}
+
+#[test]
+fn aad_tags_reserved() {
+    let keypair = fixed_keypair::<Mayo1>();
+    let sig = keypair
+        .signing_key()
+        .sign_with_aad(&mut rand::rng(), b"header", b"body")
+        .expect("signing failed");
+
+    for tag in [&b"pq-mayo/aad"[..], b"pq-mayo/ctx+aad"] {
+        assert!(matches!(
+            keypair
+                .signing_key()
+                .sign_with_context(&mut rand::rng(), tag, b"body"),
+            Err(Error::ReservedContext)
+        ));
+        assert!(matches!(
+            keypair
+                .verifying_key()
+                .verify_with_context(tag, b"body", &sig),
+            Err(Error::ReservedContext)
+        ));
+    }
+}
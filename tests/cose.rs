@@ -0,0 +1,76 @@
+//! COSE_Key and COSE_Sign1 tests.
+
+#![cfg(feature = "cose")]
+
+use coset::{Algorithm, CborSerializable, CoseKey, CoseSign1, iana};
+use pq_mayo::{KeyPair, Mayo1, Mayo2, Mayo3, Mayo5, MayoParameter, VerifyingKey};
+
+fn cose_roundtrip<P: MayoParameter>() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen failed");
+    let vk = keypair.verifying_key();
+
+    let key_bytes = vk.to_cose_key().to_vec().expect("encode COSE_Key");
+    let key = CoseKey::from_slice(&key_bytes).expect("decode COSE_Key");
+    let restored = VerifyingKey::<P>::from_cose_key(&key).expect("from COSE_Key");
+    assert_eq!(&restored, vk);
+
+    let sign1 = keypair
+        .signing_key()
+        .sign_cose_sign1(b"payload", b"aad")
+        .expect("sign COSE_Sign1");
+    let sign1_bytes = sign1.to_vec().expect("encode COSE_Sign1");
+    let sign1 = CoseSign1::from_slice(&sign1_bytes).expect("decode COSE_Sign1");
+    restored
+        .verify_cose_sign1(&sign1, b"aad")
+        .expect("verify COSE_Sign1");
+    assert!(restored.verify_cose_sign1(&sign1, b"other aad").is_err());
+
+    let mut tampered = sign1.clone();
+    tampered.payload = Some(b"other payload".to_vec());
+    assert!(restored.verify_cose_sign1(&tampered, b"aad").is_err());
+}
+
+#[test]
+fn cose_roundtrip_mayo1() {
+    cose_roundtrip::<Mayo1>();
+}
+
+#[test]
+fn cose_roundtrip_mayo2() {
+    cose_roundtrip::<Mayo2>();
+}
+
+#[test]
+fn cose_roundtrip_mayo3() {
+    cose_roundtrip::<Mayo3>();
+}
+
+#[test]
+fn cose_roundtrip_mayo5() {
+    cose_roundtrip::<Mayo5>();
+}
+
+#[test]
+fn cose_rejects_wrong_algorithm() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen failed");
+
+    // A MAYO-1 key must not decode as MAYO-2, nor with a non-MAYO algorithm
+    let mut key = keypair.verifying_key().to_cose_key();
+    assert!(VerifyingKey::<Mayo2>::from_cose_key(&key).is_err());
+    key.alg = Some(Algorithm::Assigned(iana::Algorithm::EdDSA));
+    assert!(VerifyingKey::<Mayo1>::from_cose_key(&key).is_err());
+
+    let mut sign1 = keypair
+        .signing_key()
+        .sign_cose_sign1(b"payload", b"")
+        .expect("sign COSE_Sign1");
+    sign1.protected.header.alg = Some(Algorithm::Assigned(iana::Algorithm::EdDSA));
+    assert!(
+        keypair
+            .verifying_key()
+            .verify_cose_sign1(&sign1, b"")
+            .is_err()
+    );
+}
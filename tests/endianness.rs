@@ -0,0 +1,70 @@
+//! Fixed known-answer vectors guarding the byte order of the encodings.
+//!
+//! All limb <-> byte conversions are little-endian regardless of the host, so
+//! these digests must come out the same on big-endian targets such as s390x.
+//! A native-endian shortcut in the codec or the bitsliced arithmetic changes
+//! the public key and signature bytes there, and these tests catch it without
+//! the slower NIST KAT suite.
+//!
+//! The vectors were produced by this implementation, which matches the NIST
+//! KAT files, from the seed `00 01 02 ..` and deterministic signing.
+
+use pq_mayo::{KeyPair, Mayo1, Mayo2, Mayo3, Mayo5, MayoParameter};
+use shake::Shake256;
+use shake::digest::{ExtendableOutput, Update, XofReader};
+
+const MSG: &[u8] = b"little-endian limbs";
+
+/// SHAKE256 of `data`, truncated to 32 bytes, as lowercase hex.
+fn digest_hex(data: &[u8]) -> String {
+    let mut hasher = Shake256::default();
+    hasher.update(data);
+    let mut out = [0u8; 32];
+    hasher.finalize_xof().read(&mut out);
+    out.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn fixed_vector<P: MayoParameter>(cpk_digest: &str, sig_digest: &str) {
+    let seed: Vec<u8> = (0u8..).take(P::SK_SEED_BYTES).collect();
+    let keypair = KeyPair::<P>::from_seed(&seed).expect("from_seed");
+    let sig = keypair
+        .signing_key()
+        .sign_deterministic(MSG)
+        .expect("signing failed");
+
+    assert_eq!(digest_hex(keypair.verifying_key().as_ref()), cpk_digest);
+    assert_eq!(digest_hex(sig.as_ref()), sig_digest);
+    assert!(keypair.verifying_key().verify(MSG, &sig).is_ok());
+}
+
+#[test]
+fn fixed_vector_mayo1() {
+    fixed_vector::<Mayo1>(
+        "f6e5d3e6778fb2e5ce3f5e8944edfcb08eac6db4535ab74163b9be2c3b3540ec",
+        "ea11fd3994c6ae4eccb43edb3606b17679012ac7a2bd48cfe8300573a2bb7df7",
+    );
+}
+
+#[test]
+fn fixed_vector_mayo2() {
+    fixed_vector::<Mayo2>(
+        "dda380f4797f64e3f9aa5ff2029e965309da66c092bf6f3c656c6010225d6bc2",
+        "08119331d3bb23be8b062decbfb6277ee308b5a0172f035bfd504558e48db73f",
+    );
+}
+
+#[test]
+fn fixed_vector_mayo3() {
+    fixed_vector::<Mayo3>(
+        "dc69dd1254d357a1cffda691a66406278fd00d34b9a4da808280375ada383d96",
+        "814f6f6957829b1da58d3f29edb4b23d2e607a562110cebf741816e7fb019b9b",
+    );
+}
+
+#[test]
+fn fixed_vector_mayo5() {
+    fixed_vector::<Mayo5>(
+        "19b0f7de57edc26a02222e779220b3a7466411e22ae49a565de1ae2ccc04ec96",
+        "c26999e360ac3037a2feb05ec9acb5372e25780edd18f6294f896b980f3baf88",
+    );
+}
@@ -0,0 +1,196 @@
+//! Signature envelope tests.
+
+use pq_mayo::envelope::{self, HEADER_LEN, MAGIC};
+use pq_mayo::{Error, KeyPair, Mayo1, Mayo2, Mayo3, Mayo5, Signature};
+
+fn seal_open<P: pq_mayo::MayoParameter>(id: u8) {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen failed");
+    let msg = b"envelope roundtrip";
+
+    let sealed = envelope::seal(keypair.signing_key(), &mut rng, msg).expect("seal failed");
+    assert_eq!(sealed.len(), HEADER_LEN + P::SIG_BYTES);
+    assert_eq!(sealed[..4], MAGIC);
+    assert_eq!(sealed[4], id);
+    assert_eq!(envelope::param_id::<P>(), Some(id));
+    assert_eq!(usize::from(sealed[5]), P::SALT_BYTES);
+
+    envelope::open(keypair.verifying_key(), &sealed, msg).expect("open failed");
+    assert!(envelope::open(keypair.verifying_key(), &sealed, b"other message").is_err());
+}
+
+#[test]
+fn seal_open_mayo1() {
+    seal_open::<Mayo1>(1);
+}
+
+#[test]
+fn seal_open_mayo2() {
+    seal_open::<Mayo2>(2);
+}
+
+#[test]
+fn seal_open_mayo3() {
+    seal_open::<Mayo3>(3);
+}
+
+#[test]
+fn seal_open_mayo5() {
+    seal_open::<Mayo5>(5);
+}
+
+#[test]
+fn tampered_magic_rejected() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen failed");
+    let sealed = envelope::seal(keypair.signing_key(), &mut rng, b"msg").expect("seal failed");
+
+    for i in 0..MAGIC.len() {
+        let mut tampered = sealed.clone();
+        tampered[i] ^= 0x20;
+        assert!(matches!(
+            envelope::open(keypair.verifying_key(), &tampered, b"msg"),
+            Err(Error::VerificationFailed)
+        ));
+    }
+}
+
+#[test]
+fn wrong_param_id_rejected() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen failed");
+    let sealed = envelope::seal(keypair.signing_key(), &mut rng, b"msg").expect("seal failed");
+
+    let mut relabeled = sealed.clone();
+    relabeled[4] = 3;
+    assert!(matches!(
+        envelope::open(keypair.verifying_key(), &relabeled, b"msg"),
+        Err(Error::VerificationFailed)
+    ));
+
+    // A Mayo1 envelope fed to a Mayo3 verifier fails on the header, before
+    // the signature length is even looked at.
+    let mayo3 = KeyPair::<Mayo3>::generate(&mut rng).expect("keygen failed");
+    assert!(matches!(
+        envelope::open(mayo3.verifying_key(), &sealed, b"msg"),
+        Err(Error::VerificationFailed)
+    ));
+}
+
+#[test]
+fn truncated_envelope_rejected() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<Mayo2>::generate(&mut rng).expect("keygen failed");
+    let sealed = envelope::seal(keypair.signing_key(), &mut rng, b"msg").expect("seal failed");
+
+    assert!(envelope::open(keypair.verifying_key(), &sealed[..3], b"msg").is_err());
+    assert!(matches!(
+        envelope::open(keypair.verifying_key(), &sealed[..sealed.len() - 1], b"msg"),
+        Err(Error::InvalidSignatureLength { .. })
+    ));
+}
+
+fn tagged_roundtrip<P: pq_mayo::MayoParameter>(id: u8) {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let sig: Signature<P> = keypair
+        .signing_key()
+        .sign_with_rng(&mut rand::rng(), b"tagged")
+        .expect("sign");
+
+    let tagged = sig.to_tagged_bytes();
+    assert_eq!(tagged.len(), 3 + P::SIG_BYTES);
+    assert_eq!(tagged[0], id);
+    assert_eq!(
+        usize::from(u16::from_be_bytes([tagged[1], tagged[2]])),
+        P::SIG_BYTES
+    );
+    assert_eq!(&tagged[3..], sig.as_ref());
+    assert_eq!(
+        Signature::<P>::from_tagged_bytes(&tagged).expect("decode"),
+        sig
+    );
+}
+
+#[test]
+fn tagged_roundtrip_mayo1() {
+    tagged_roundtrip::<Mayo1>(1);
+}
+
+#[test]
+fn tagged_roundtrip_mayo2() {
+    tagged_roundtrip::<Mayo2>(2);
+}
+
+#[test]
+fn tagged_roundtrip_mayo3() {
+    tagged_roundtrip::<Mayo3>(3);
+}
+
+#[test]
+fn tagged_roundtrip_mayo5() {
+    tagged_roundtrip::<Mayo5>(5);
+}
+
+#[test]
+fn tagged_wrong_param_set_rejected() {
+    let keypair = KeyPair::<Mayo2>::generate(&mut rand::rng()).expect("keygen failed");
+    let sig: Signature<Mayo2> = keypair
+        .signing_key()
+        .sign_with_rng(&mut rand::rng(), b"tagged")
+        .expect("sign");
+    let tagged = sig.to_tagged_bytes();
+
+    assert!(matches!(
+        Signature::<Mayo1>::from_tagged_bytes(&tagged),
+        Err(Error::ParameterSetMismatch {
+            expected: 1,
+            got: 2
+        })
+    ));
+
+    let mut untagged = tagged.clone();
+    untagged[0] = 0;
+    assert!(matches!(
+        Signature::<Mayo1>::from_tagged_bytes(&untagged),
+        Err(Error::ParameterSetMismatch {
+            expected: 1,
+            got: 0
+        })
+    ));
+
+    // A retagged signature still fails on its length.
+    let mut retagged = tagged.clone();
+    retagged[0] = 1;
+    assert!(matches!(
+        Signature::<Mayo1>::from_tagged_bytes(&retagged),
+        Err(Error::InvalidSignatureLength { .. })
+    ));
+}
+
+#[test]
+fn tagged_bad_length_rejected() {
+    let keypair = KeyPair::<Mayo1>::generate(&mut rand::rng()).expect("keygen failed");
+    let sig: Signature<Mayo1> = keypair
+        .signing_key()
+        .sign_with_rng(&mut rand::rng(), b"tagged")
+        .expect("sign");
+    let tagged = sig.to_tagged_bytes();
+
+    for len in [0, 2, 3, tagged.len() - 1] {
+        assert!(matches!(
+            Signature::<Mayo1>::from_tagged_bytes(&tagged[..len]),
+            Err(Error::InvalidSignatureLength { .. })
+        ));
+    }
+
+    let mut extended = tagged.clone();
+    extended.push(0);
+    assert!(Signature::<Mayo1>::from_tagged_bytes(&extended).is_err());
+
+    let mut wrong_len = tagged;
+    wrong_len[2] ^= 1;
+    assert!(matches!(
+        Signature::<Mayo1>::from_tagged_bytes(&wrong_len),
+        Err(Error::InvalidSignatureLength { .. })
+    ));
+}
@@ -0,0 +1,68 @@
+//! Error classification tests.
+
+use pq_mayo::Error;
+
+#[test]
+fn predicates() {
+    assert!(Error::VerificationFailed.is_verification_failure());
+    assert!(!Error::VerificationFailed.is_length_error());
+
+    let length_errors = [
+        Error::InvalidKeyLength {
+            expected: 1,
+            got: 2,
+        },
+        Error::InvalidSignatureLength {
+            expected: 1,
+            got: 2,
+        },
+        Error::InvalidSeedLength {
+            expected: 1,
+            got: 2,
+        },
+        Error::InvalidEntropyLength {
+            expected: 1,
+            got: 2,
+        },
+    ];
+    for e in length_errors {
+        assert!(e.is_length_error());
+        assert!(!e.is_verification_failure());
+    }
+
+    for e in [
+        Error::KeyGeneration,
+        Error::Signing,
+        Error::WeakSeed,
+        Error::MalformedKey,
+        Error::ParameterSetMismatch {
+            expected: 1,
+            got: 2,
+        },
+        Error::ReservedContext,
+        Error::InvalidHex,
+    ] {
+        assert!(!e.is_length_error());
+        assert!(!e.is_verification_failure());
+    }
+}
+
+#[test]
+#[cfg(feature = "signature-traits")]
+fn signature_error_keeps_source() {
+    use pq_mayo::{KeyPair, Mayo1, Signature};
+    use std::error::Error as _;
+
+    let keypair = KeyPair::<Mayo1>::generate(&mut rand::rng()).expect("keygen");
+    let sig: Signature<Mayo1> = keypair
+        .signing_key()
+        .sign_with_rng(&mut rand::rng(), b"msg")
+        .expect("sign");
+    let err = signature::Verifier::verify(keypair.verifying_key(), b"other", &sig)
+        .expect_err("wrong message verified");
+    let source = err
+        .source()
+        .and_then(|s| s.downcast_ref::<Error>())
+        .expect("source is pq_mayo::Error");
+    assert!(source.is_verification_failure());
+}
@@ -0,0 +1,65 @@
+//! Tests for the `gf16-internals` public surface.
+
+#![cfg(feature = "gf16-internals")]
+
+use pq_mayo::gf16::{Matrix, add, inverse, mul, row_echelon_form};
+
+#[test]
+fn field_axioms() {
+    for a in 0..16u8 {
+        assert_eq!(add(a, a), 0);
+        assert_eq!(mul(a, 1), a);
+        assert_eq!(mul(a, 0), 0);
+        if a != 0 {
+            assert_eq!(mul(a, inverse(a)), 1);
+        }
+        for b in 0..16u8 {
+            assert_eq!(mul(a, b), mul(b, a));
+        }
+    }
+    assert_eq!(inverse(0), 0);
+    // x * x^3 = x^4 = x + 1
+    assert_eq!(mul(2, 8), 3);
+}
+
+#[test]
+fn matrix_mul_identity_and_add() {
+    let m = Matrix::from_vec(2, 3, vec![1, 2, 3, 4, 5, 6]).expect("valid matrix");
+    let mut id = Matrix::zero(3, 3);
+    for i in 0..3 {
+        assert!(id.set(i, i, 1));
+    }
+    assert_eq!(m.mul(&id).expect("shapes match"), m);
+    assert_eq!(m.add(&m).expect("shapes match"), Matrix::zero(2, 3));
+    assert_eq!(m.get(1, 2), Some(6));
+    assert_eq!(m.get(2, 0), None);
+}
+
+#[test]
+fn matrix_rejects_bad_input() {
+    assert!(Matrix::from_vec(2, 2, vec![0; 3]).is_none());
+    assert!(Matrix::from_vec(1, 1, vec![16]).is_none());
+    let a = Matrix::zero(2, 3);
+    assert!(a.mul(&a).is_none());
+    assert!(a.add(&Matrix::zero(3, 2)).is_none());
+}
+
+#[test]
+fn row_echelon_form_normalizes_and_validates() {
+    // The first column's pivot is found in the last row, and all three rows
+    // are summed into the first rather than swapped.
+    let mut m = vec![0, 2, 4, 0, 1, 3, 1, 1, 1];
+    assert!(row_echelon_form(&mut m, 3, 3));
+    assert_eq!(m, vec![1, 2, 6, 0, 1, 3, 0, 0, 1]);
+
+    let mut zero = vec![0u8; 6];
+    assert!(row_echelon_form(&mut zero, 2, 3));
+    assert_eq!(zero, vec![0; 6]);
+    assert!(row_echelon_form(&mut [], 0, 4));
+
+    let mut bad = vec![1, 2, 16, 3];
+    assert!(!row_echelon_form(&mut bad, 2, 2));
+    assert_eq!(bad, vec![1, 2, 16, 3]);
+    assert!(!row_echelon_form(&mut [1, 2, 3], 2, 2));
+    assert!(!row_echelon_form(&mut [], usize::MAX, 2));
+}
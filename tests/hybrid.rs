@@ -0,0 +1,70 @@
+//! Hybrid MAYO + Ed25519 signature tests.
+
+#![cfg(feature = "hybrid")]
+
+use pq_mayo::{HybridSignature, HybridSigningKey, Mayo1, Mayo2, Mayo3, Mayo5, MayoParameter};
+use signature::{Signer, Verifier};
+
+fn hybrid_roundtrip<P: MayoParameter>() {
+    let mut rng = rand::rng();
+    let sk = HybridSigningKey::<P>::generate(&mut rng).expect("keygen failed");
+    let vk = sk.verifying_key();
+    let msg = b"hybrid signature test";
+
+    let sig = sk.try_sign(msg).expect("signing failed");
+    vk.verify(msg, &sig).expect("verification failed");
+    assert!(vk.verify(b"other message", &sig).is_err());
+
+    let bytes = sig.to_bytes();
+    assert_eq!(bytes.len(), HybridSignature::<P>::BYTES);
+    assert_eq!(bytes.len(), 4 + P::SIG_BYTES + 64);
+    assert_eq!(
+        &bytes[..4],
+        &u32::try_from(P::SIG_BYTES).expect("fits").to_be_bytes()
+    );
+    assert_eq!(&bytes[4..4 + P::SIG_BYTES], sig.mayo().as_ref());
+
+    let decoded = HybridSignature::<P>::try_from(bytes.as_slice()).expect("decode failed");
+    assert_eq!(decoded, sig);
+    vk.verify(msg, &decoded).expect("verify decoded");
+
+    // Tampering with the MAYO half must fail
+    let mut tampered = bytes.clone();
+    tampered[4] ^= 0x01;
+    let tampered = HybridSignature::<P>::try_from(tampered.as_slice()).expect("decode");
+    assert!(vk.verify(msg, &tampered).is_err());
+
+    // Tampering with the Ed25519 half must fail
+    let mut tampered = bytes.clone();
+    let last = tampered.len() - 40;
+    tampered[last] ^= 0x01;
+    if let Ok(tampered) = HybridSignature::<P>::try_from(tampered.as_slice()) {
+        assert!(vk.verify(msg, &tampered).is_err());
+    }
+
+    // A wrong length prefix or total length is rejected
+    let mut bad_prefix = bytes.clone();
+    bad_prefix[3] ^= 0x01;
+    assert!(HybridSignature::<P>::try_from(bad_prefix.as_slice()).is_err());
+    assert!(HybridSignature::<P>::try_from(&bytes[..bytes.len() - 1]).is_err());
+}
+
+#[test]
+fn hybrid_roundtrip_mayo1() {
+    hybrid_roundtrip::<Mayo1>();
+}
+
+#[test]
+fn hybrid_roundtrip_mayo2() {
+    hybrid_roundtrip::<Mayo2>();
+}
+
+#[test]
+fn hybrid_roundtrip_mayo3() {
+    hybrid_roundtrip::<Mayo3>();
+}
+
+#[test]
+fn hybrid_roundtrip_mayo5() {
+    hybrid_roundtrip::<Mayo5>();
+}
@@ -0,0 +1,147 @@
+//! Fixed-capacity signature storage, and signing and verifying without a
+//! heap.
+
+use pq_mayo::{
+    InlineSignature, KeyPair, MAX_SIG_BYTES, Mayo1, Mayo1Workspace, Mayo2, Mayo2Workspace, Mayo3,
+    Mayo3Workspace, Mayo5, Mayo5Workspace, MayoParameter, Workspace,
+};
+use rand::SeedableRng;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Counts allocations made by the current thread, so tests running in
+/// parallel do not disturb each other.
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+// SAFETY: every call is forwarded to `System` unchanged; the counter is only
+// updated alongside it.
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        // SAFETY: forwarded unchanged to the system allocator.
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: `ptr` came from `alloc` above, i.e. from the system allocator.
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn allocations_in<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let out = f();
+    (out, ALLOCATIONS.with(Cell::get) - before)
+}
+
+fn inline_signature<P: MayoParameter>() {
+    assert!(P::SIG_BYTES <= MAX_SIG_BYTES);
+
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen failed");
+    let msg = b"inline signature";
+    let sig = keypair
+        .signing_key()
+        .sign_inline(&mut rng, msg)
+        .expect("signing failed");
+    assert_eq!(sig.as_ref().len(), P::SIG_BYTES);
+    let vk = keypair.verifying_key();
+    assert!(vk.verify_from_slice(msg, sig.as_ref()).is_ok());
+    assert!(vk.verify_from_slice(b"other", sig.as_ref()).is_err());
+    assert!(vk.verify(msg, &sig.to_signature()).is_ok());
+
+    let bytes = sig.as_ref();
+    let parsed = InlineSignature::<P>::try_from(bytes).expect("valid length");
+    assert_eq!(parsed, sig);
+    assert!(InlineSignature::<P>::try_from(&bytes[1..]).is_err());
+}
+
+/// Signing and verifying through a workspace on the stack make no heap
+/// allocation, and give the same signature as the heap-backed path.
+fn heap_free<P: MayoParameter, const LIMBS: usize, const BYTES: usize>(
+    workspace: &mut Workspace<P, LIMBS, BYTES>,
+) {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let (sk, vk) = (keypair.signing_key(), keypair.verifying_key());
+    let msg = b"heap-free";
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+    let (sig, allocations) = allocations_in(|| {
+        sk.sign_inline_with_workspace(&mut rng, msg, workspace)
+            .expect("signing failed")
+    });
+    assert_eq!(allocations, 0, "signing allocated");
+
+    let (valid, allocations) =
+        allocations_in(|| vk.verify_from_slice_with_workspace(msg, sig.as_ref(), workspace));
+    assert_eq!(allocations, 0, "verifying allocated");
+    assert!(valid.is_ok());
+
+    let (forged, allocations) =
+        allocations_in(|| vk.verify_from_slice_with_workspace(b"other", sig.as_ref(), workspace));
+    assert_eq!(allocations, 0, "rejecting allocated");
+    assert!(forged.is_err_and(|e| e.is_verification_failure()));
+
+    let heap_sig = sk
+        .sign_inline(&mut rand::rngs::StdRng::seed_from_u64(7), msg)
+        .expect("signing failed");
+    assert_eq!(sig, heap_sig);
+}
+
+/// Runs `f` on a thread whose stack fits the largest workspace.
+fn on_big_stack(f: fn()) {
+    std::thread::Builder::new()
+        .stack_size(16 << 20)
+        .spawn(f)
+        .expect("spawn")
+        .join()
+        .expect("test thread panicked");
+}
+
+#[test]
+fn inline_signature_mayo1() {
+    inline_signature::<Mayo1>();
+}
+
+#[test]
+fn inline_signature_mayo2() {
+    inline_signature::<Mayo2>();
+}
+
+#[test]
+fn inline_signature_mayo3() {
+    inline_signature::<Mayo3>();
+}
+
+#[test]
+fn inline_signature_mayo5() {
+    inline_signature::<Mayo5>();
+    assert_eq!(Mayo5::SIG_BYTES, MAX_SIG_BYTES);
+}
+
+#[test]
+fn heap_free_mayo1() {
+    heap_free(&mut Mayo1Workspace::new());
+}
+
+#[test]
+fn heap_free_mayo2() {
+    heap_free(&mut Mayo2Workspace::new());
+}
+
+#[test]
+fn heap_free_mayo3() {
+    on_big_stack(|| heap_free(&mut Mayo3Workspace::new()));
+}
+
+#[test]
+fn heap_free_mayo5() {
+    on_big_stack(|| heap_free(&mut Mayo5Workspace::new()));
+}
@@ -0,0 +1,217 @@
+//! Tests for the `internals` public surface.
+
+#![cfg(feature = "internals")]
+
+use pq_mayo::internals::{
+    Digester, SampleOutcome, SignStages, compute_target, expand_public_matrices,
+    sign_with_digester, verify_with_digester,
+};
+use pq_mayo::{Error, KeyPair, Mayo1, Mayo2, Mayo3, Mayo5, MayoParameter};
+
+fn expand_public_matrices_layout<P: MayoParameter>() {
+    let keypair = KeyPair::<P>::from_seed(&vec![7u8; P::SK_SEED_BYTES]).expect("from_seed");
+    let (p1, p2) = expand_public_matrices::<P>(keypair.public_seed()).expect("expand");
+    assert_eq!(p1.len(), P::P1_LIMBS);
+    assert_eq!(p2.len(), P::P2_LIMBS);
+    assert_eq!(
+        expand_public_matrices::<P>(keypair.public_seed()).expect("expand"),
+        (p1.clone(), p2.clone())
+    );
+
+    // Nibbles above m in the last limb of each m-vector are zero.
+    if P::M % 16 != 0 {
+        let mask = u64::MAX << ((P::M % 16) * 4);
+        for v in p1
+            .chunks_exact(P::M_VEC_LIMBS)
+            .chain(p2.chunks_exact(P::M_VEC_LIMBS))
+        {
+            assert_eq!(v[P::M_VEC_LIMBS - 1] & mask, 0);
+        }
+    }
+
+    let other = vec![0u8; P::PK_SEED_BYTES];
+    assert_ne!(expand_public_matrices::<P>(&other).expect("expand").0, p1);
+}
+
+#[test]
+fn expand_public_matrices_layout_mayo1() {
+    expand_public_matrices_layout::<Mayo1>();
+}
+
+#[test]
+fn expand_public_matrices_layout_mayo2() {
+    expand_public_matrices_layout::<Mayo2>();
+}
+
+#[test]
+fn expand_public_matrices_layout_mayo3() {
+    expand_public_matrices_layout::<Mayo3>();
+}
+
+#[test]
+fn expand_public_matrices_layout_mayo5() {
+    expand_public_matrices_layout::<Mayo5>();
+}
+
+#[test]
+fn expand_public_matrices_rejects_short_seed() {
+    assert!(matches!(
+        expand_public_matrices::<Mayo1>(&[0u8; 8]),
+        Err(Error::InvalidKeyLength {
+            expected: 16,
+            got: 8
+        })
+    ));
+}
+
+fn sign_stages<P: MayoParameter>() {
+    let keypair = KeyPair::<P>::from_seed(&vec![9u8; P::SK_SEED_BYTES]).expect("from_seed");
+    let mut stages = SignStages::<P>::new(keypair.signing_key()).expect("new");
+    stages.compute_m_and_vpv();
+    stages.compute_a();
+    stages.echelon_form();
+    let solvable = stages.sample_solution();
+    assert_eq!(
+        stages.sample_solution_detailed() == SampleOutcome::Solved,
+        solvable
+    );
+
+    // Every stage works from copies, so rerunning any of them is repeatable.
+    stages.expand_secret_key().expect("expand");
+    stages.compute_m_and_vpv();
+    stages.compute_a();
+    stages.echelon_form();
+    assert_eq!(stages.sample_solution(), solvable);
+
+    let mut again = SignStages::<P>::new(keypair.signing_key()).expect("new");
+    again.compute_m_and_vpv();
+    again.compute_a();
+    assert_eq!(again.sample_solution(), solvable);
+}
+
+#[test]
+fn sign_stages_mayo1() {
+    sign_stages::<Mayo1>();
+}
+
+#[test]
+fn sign_stages_mayo2() {
+    sign_stages::<Mayo2>();
+}
+
+#[test]
+fn sign_stages_mayo3() {
+    sign_stages::<Mayo3>();
+}
+
+#[test]
+fn sign_stages_mayo5() {
+    sign_stages::<Mayo5>();
+}
+
+fn target<P: MayoParameter>() {
+    use shake::Shake256;
+    use shake::digest::{ExtendableOutput, Update, XofReader};
+
+    let msg = b"target vector";
+    let salt = vec![0x3cu8; P::SALT_BYTES];
+    let t = compute_target::<P>(msg, &salt).expect("target");
+
+    // Recompute from the specification: t = SHAKE256(SHAKE256(msg) || salt),
+    // decoded low nibble first.
+    let mut digest = vec![0u8; P::DIGEST_BYTES];
+    let mut hasher = Shake256::default();
+    hasher.update(msg);
+    hasher.finalize_xof().read(&mut digest);
+    let mut tenc = vec![0u8; P::M_BYTES];
+    let mut hasher = Shake256::default();
+    hasher.update(&digest);
+    hasher.update(&salt);
+    hasher.finalize_xof().read(&mut tenc);
+    let expected: Vec<u8> = tenc.iter().flat_map(|b| [b & 0xf, b >> 4]).collect();
+    assert_eq!(t, expected[..P::M]);
+
+    assert_ne!(
+        compute_target::<P>(msg, &vec![0u8; P::SALT_BYTES]).expect("target"),
+        t
+    );
+    assert!(matches!(
+        compute_target::<P>(msg, &salt[1..]),
+        Err(Error::InvalidSignatureLength { .. })
+    ));
+}
+
+#[test]
+fn target_mayo1() {
+    target::<Mayo1>();
+}
+
+#[test]
+fn target_mayo2() {
+    target::<Mayo2>();
+}
+
+#[test]
+fn target_mayo3() {
+    target::<Mayo3>();
+}
+
+#[test]
+fn target_mayo5() {
+    target::<Mayo5>();
+}
+
+/// SHAKE256 over a domain-separated message, standing in for a digest
+/// computed by other hash-then-sign infrastructure.
+struct DomainDigester;
+
+impl Digester for DomainDigester {
+    fn digest(msg: &[u8], out: &mut [u8]) {
+        use shake::Shake256;
+        use shake::digest::{ExtendableOutput, Update, XofReader};
+
+        let mut hasher = Shake256::default();
+        hasher.update(b"domain");
+        hasher.update(msg);
+        hasher.finalize_xof().read(out);
+    }
+}
+
+fn custom_digester<P: MayoParameter>() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen failed");
+    let (sk, vk) = (keypair.signing_key(), keypair.verifying_key());
+    let msg = b"hash then sign";
+
+    let sig = sign_with_digester::<P, DomainDigester>(sk, &mut rng, msg).expect("sign");
+    verify_with_digester::<P, DomainDigester>(vk, msg, &sig).expect("verify");
+    assert!(matches!(
+        verify_with_digester::<P, DomainDigester>(vk, b"other", &sig),
+        Err(Error::VerificationFailed)
+    ));
+
+    // The digest really changed: the standard API disagrees both ways.
+    assert!(vk.verify(msg, &sig).is_err());
+    let standard = sk.sign_with_rng(&mut rng, msg).expect("sign");
+    assert!(verify_with_digester::<P, DomainDigester>(vk, msg, &standard).is_err());
+}
+
+#[test]
+fn custom_digester_mayo1() {
+    custom_digester::<Mayo1>();
+}
+
+#[test]
+fn custom_digester_mayo2() {
+    custom_digester::<Mayo2>();
+}
+
+#[test]
+fn custom_digester_mayo3() {
+    custom_digester::<Mayo3>();
+}
+
+#[test]
+fn custom_digester_mayo5() {
+    custom_digester::<Mayo5>();
+}
@@ -0,0 +1,68 @@
+//! JWK import/export tests.
+
+#![cfg(feature = "jwk")]
+
+use pq_mayo::{KeyPair, Mayo1, Mayo2, Mayo3, Mayo5, MayoParameter, VerifyingKey};
+
+fn jwk_roundtrip<P: MayoParameter>() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen failed");
+    let vk = keypair.verifying_key();
+
+    let jwk = vk.to_jwk();
+    let value: serde_json::Value = serde_json::from_str(&jwk).expect("valid JSON");
+    assert_eq!(value["kty"], "AKP");
+    assert_eq!(value["alg"], P::NAME.replace('_', "-"));
+
+    let restored = VerifyingKey::<P>::from_jwk(&jwk).expect("from_jwk failed");
+    assert_eq!(&restored, vk);
+}
+
+#[test]
+fn jwk_roundtrip_mayo1() {
+    jwk_roundtrip::<Mayo1>();
+}
+
+#[test]
+fn jwk_roundtrip_mayo2() {
+    jwk_roundtrip::<Mayo2>();
+}
+
+#[test]
+fn jwk_roundtrip_mayo3() {
+    jwk_roundtrip::<Mayo3>();
+}
+
+#[test]
+fn jwk_roundtrip_mayo5() {
+    jwk_roundtrip::<Mayo5>();
+}
+
+#[test]
+fn jwk_rejects_bad_alg_and_length() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen failed");
+    let jwk = keypair.verifying_key().to_jwk();
+    let mut value: serde_json::Value = serde_json::from_str(&jwk).expect("valid JSON");
+
+    // Mismatched parameter set
+    assert!(VerifyingKey::<Mayo2>::from_jwk(&jwk).is_err());
+
+    // Missing alg
+    let mut missing = value.clone();
+    missing
+        .as_object_mut()
+        .expect("object")
+        .remove("alg")
+        .expect("alg present");
+    assert!(VerifyingKey::<Mayo1>::from_jwk(&missing.to_string()).is_err());
+
+    // Wrong kty
+    let mut wrong_kty = value.clone();
+    wrong_kty["kty"] = "OKP".into();
+    assert!(VerifyingKey::<Mayo1>::from_jwk(&wrong_kty.to_string()).is_err());
+
+    // Truncated key
+    value["pub"] = "AAAA".into();
+    assert!(VerifyingKey::<Mayo1>::from_jwk(&value.to_string()).is_err());
+}
@@ -0,0 +1,147 @@
+//! Peak heap usage of keygen, sign and verify for each parameter set.
+//!
+//! This test binary replaces the global allocator with one that counts live
+//! bytes, so the library itself is unaffected. Run with
+//! `cargo test --release --test memory -- --nocapture` to see the report.
+
+use pq_mayo::{ExpandedSigningKey, ExpandedVerifyingKey, KeyPair, Mayo1, Mayo2, Mayo3, Mayo5};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The system allocator, plus a count of live bytes and its high-water mark.
+struct PeakAlloc;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+impl PeakAlloc {
+    fn grew(by: usize) {
+        let now = CURRENT.fetch_add(by, Ordering::Relaxed) + by;
+        PEAK.fetch_max(now, Ordering::Relaxed);
+    }
+
+    fn shrank(by: usize) {
+        CURRENT.fetch_sub(by, Ordering::Relaxed);
+    }
+}
+
+// SAFETY: every call is forwarded to `System` unchanged; the counters are
+// only updated alongside it.
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: the caller upholds `GlobalAlloc::alloc`'s contract.
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::grew(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: the caller upholds `GlobalAlloc::alloc_zeroed`'s contract.
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::grew(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: the caller upholds `GlobalAlloc::dealloc`'s contract.
+        unsafe { System.dealloc(ptr, layout) };
+        Self::shrank(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // SAFETY: the caller upholds `GlobalAlloc::realloc`'s contract.
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            if new_size > layout.size() {
+                Self::grew(new_size - layout.size());
+            } else {
+                Self::shrank(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static ALLOC: PeakAlloc = PeakAlloc;
+
+/// Bytes allocated by `f` at its peak, on top of what was live before it ran.
+///
+/// The counters are global, so this is only meaningful while no other thread
+/// allocates; everything below runs inside a single test for that reason.
+fn peak_during<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let base = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(base, Ordering::Relaxed);
+    let out = f();
+    (out, PEAK.load(Ordering::Relaxed) - base)
+}
+
+struct Peaks {
+    keygen: usize,
+    sign: usize,
+    verify: usize,
+    expand_sk: usize,
+    expand_vk: usize,
+}
+
+fn peaks<P: pq_mayo::MayoParameter>() -> Peaks {
+    let msg = b"peak heap usage";
+    let (keypair, keygen) =
+        peak_during(|| KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed"));
+    let (sig, sign) = peak_during(|| {
+        keypair
+            .signing_key()
+            .sign_with_rng(&mut rand::rng(), msg)
+            .expect("signing failed")
+    });
+    let ((), verify) = peak_during(|| {
+        keypair
+            .verifying_key()
+            .verify(msg, &sig)
+            .expect("verification failed");
+    });
+    let (_, expand_sk) = peak_during(|| ExpandedSigningKey::from(keypair.signing_key()));
+    let (_, expand_vk) = peak_during(|| ExpandedVerifyingKey::from(keypair.verifying_key()));
+
+    // An expanded verifying key holds P1 and P2 as u64 limbs.
+    assert!(expand_vk >= (P::P1_LIMBS + P::P2_LIMBS) * 8);
+
+    let peaks = Peaks {
+        keygen,
+        sign,
+        verify,
+        expand_sk,
+        expand_vk,
+    };
+    println!(
+        "{:<7} keygen {:>5} KiB  sign {:>5} KiB  verify {:>5} KiB  \
+         expand sk {:>5} KiB  expand vk {:>5} KiB",
+        P::NAME,
+        peaks.keygen.div_ceil(1024),
+        peaks.sign.div_ceil(1024),
+        peaks.verify.div_ceil(1024),
+        peaks.expand_sk.div_ceil(1024),
+        peaks.expand_vk.div_ceil(1024),
+    );
+    peaks
+}
+
+#[test]
+fn peak_heap_usage() {
+    let all = [
+        peaks::<Mayo1>(),
+        peaks::<Mayo2>(),
+        peaks::<Mayo3>(),
+        peaks::<Mayo5>(),
+    ];
+    for p in &all {
+        assert!(p.keygen > 0 && p.sign > 0 && p.verify > 0 && p.expand_sk > 0);
+    }
+    // MAYO_5 has the largest P1 and so the largest footprint everywhere.
+    assert!(all[3].sign > all[0].sign);
+    assert!(all[3].verify > all[0].verify);
+}
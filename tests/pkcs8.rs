@@ -6,7 +6,6 @@ use pkcs8::DecodePrivateKey;
 use pkcs8::EncodePrivateKey;
 use pkcs8::spki::{DecodePublicKey, EncodePublicKey};
 use pq_mayo::{KeyPair, Mayo1, Mayo2, Mayo3, Mayo5, MayoParameter, SigningKey, VerifyingKey};
-use signature::{Signer, Verifier};
 
 // ============================================================================
 // Private key (PKCS#8) round-trip
@@ -17,6 +16,9 @@ where
     P: MayoParameter
         + pkcs8::spki::AssociatedAlgorithmIdentifier<Params = pkcs8::der::AnyRef<'static>>,
 {
+    // The trait-level OID and the PKCS#8 algorithm identifier agree
+    assert_eq!(P::OID, Some(P::ALGORITHM_IDENTIFIER.oid));
+
     let mut rng = rand::rng();
     let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen");
 
@@ -179,7 +181,7 @@ where
     // Sign with restored key
     let sig = restored_kp
         .signing_key()
-        .try_sign(msg.as_slice())
+        .sign_with_rng(&mut rand::rng(), msg.as_slice())
         .expect("sign");
 
     // Verify with restored public key
@@ -214,6 +216,121 @@ fn sign_verify_after_pkcs8_roundtrip_mayo5() {
     sign_verify_after_pkcs8_roundtrip::<Mayo5>();
 }
 
+// ============================================================================
+// PKCS#8 v2 with the public key embedded
+// ============================================================================
+
+fn pkcs8_with_public_key<P>()
+where
+    P: MayoParameter
+        + pkcs8::spki::AssociatedAlgorithmIdentifier<Params = pkcs8::der::AnyRef<'static>>,
+{
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen");
+
+    let der = keypair
+        .to_pkcs8_der_with_public_key()
+        .expect("encode pkcs8");
+    let info = pkcs8::PrivateKeyInfoRef::try_from(der.as_bytes()).expect("parse");
+    assert_eq!(info.version(), pkcs8::Version::V2);
+    let public_key = info.public_key.and_then(|pk| pk.as_bytes());
+    assert_eq!(public_key, Some(keypair.verifying_key().as_ref()));
+
+    let restored = KeyPair::<P>::from_pkcs8_der(der.as_bytes()).expect("decode pkcs8");
+    assert_eq!(restored, keypair);
+    let sig = restored
+        .signing_key()
+        .sign_with_rng(&mut rand::rng(), b"v2")
+        .expect("sign");
+    keypair.verifying_key().verify(b"v2", &sig).expect("verify");
+
+    // The plain encoding has no public key and still decodes to the same pair.
+    let v1 = keypair.to_pkcs8_der().expect("encode pkcs8");
+    let info = pkcs8::PrivateKeyInfoRef::try_from(v1.as_bytes()).expect("parse");
+    assert_eq!(info.version(), pkcs8::Version::V1);
+    assert!(info.public_key.is_none());
+    let from_v1 = KeyPair::<P>::from_pkcs8_der(v1.as_bytes()).expect("decode pkcs8");
+    assert_eq!(from_v1, restored);
+}
+
+#[test]
+fn pkcs8_with_public_key_mayo1() {
+    pkcs8_with_public_key::<Mayo1>();
+}
+
+#[test]
+fn pkcs8_with_public_key_mayo2() {
+    pkcs8_with_public_key::<Mayo2>();
+}
+
+#[test]
+fn pkcs8_with_public_key_mayo3() {
+    pkcs8_with_public_key::<Mayo3>();
+}
+
+#[test]
+fn pkcs8_with_public_key_mayo5() {
+    pkcs8_with_public_key::<Mayo5>();
+}
+
+#[test]
+fn pkcs8_with_foreign_public_key_rejected() {
+    use pkcs8::der::Encode;
+    use pkcs8::der::asn1::BitStringRef;
+
+    let keypair = KeyPair::<Mayo1>::generate(&mut rand::rng()).expect("keygen");
+    let other = KeyPair::<Mayo1>::generate(&mut rand::rng()).expect("keygen");
+    let der = keypair
+        .to_pkcs8_der_with_public_key()
+        .expect("encode pkcs8");
+
+    let replace = |cpk: &[u8]| {
+        let mut info = pkcs8::PrivateKeyInfoRef::try_from(der.as_bytes()).expect("parse");
+        info.public_key = Some(BitStringRef::new(0, cpk).expect("bit string"));
+        info.to_der().expect("encode")
+    };
+
+    let foreign = replace(other.verifying_key().as_ref());
+    assert!(KeyPair::<Mayo1>::from_pkcs8_der(&foreign).is_err());
+    assert!(SigningKey::<Mayo1>::from_pkcs8_der(&foreign).is_err());
+
+    let short = replace(&keypair.verifying_key().as_ref()[1..]);
+    assert!(KeyPair::<Mayo1>::from_pkcs8_der(&short).is_err());
+}
+
+fn pkcs8_with_tampered_p3_rejected<P>()
+where
+    P: MayoParameter
+        + pkcs8::spki::AssociatedAlgorithmIdentifier<Params = pkcs8::der::AnyRef<'static>>,
+{
+    use pkcs8::der::Encode;
+    use pkcs8::der::asn1::BitStringRef;
+
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen");
+    let der = keypair
+        .to_pkcs8_der_with_public_key()
+        .expect("encode pkcs8");
+
+    // Keep the public seed, flip one byte of the packed P3 that follows it.
+    let mut cpk = keypair.verifying_key().as_ref().to_vec();
+    cpk[P::PK_SEED_BYTES + P::P3_BYTES / 2] ^= 0x10;
+    let mut info = pkcs8::PrivateKeyInfoRef::try_from(der.as_bytes()).expect("parse");
+    info.public_key = Some(BitStringRef::new(0, &cpk).expect("bit string"));
+    let tampered = info.to_der().expect("encode");
+
+    assert!(KeyPair::<P>::from_pkcs8_der(&tampered).is_err());
+    assert!(SigningKey::<P>::from_pkcs8_der(&tampered).is_err());
+}
+
+#[test]
+fn pkcs8_with_tampered_p3_rejected_mayo1() {
+    pkcs8_with_tampered_p3_rejected::<Mayo1>();
+}
+
+#[test]
+fn pkcs8_with_tampered_p3_rejected_mayo2() {
+    pkcs8_with_tampered_p3_rejected::<Mayo2>();
+}
+
 // ============================================================================
 // Wrong OID rejection
 // ============================================================================
@@ -241,3 +358,30 @@ fn wrong_oid_rejected_spki() {
     let result = VerifyingKey::<Mayo2>::from_public_key_der(der.as_bytes());
     assert!(result.is_err(), "should reject wrong OID");
 }
+
+// ============================================================================
+// Size lookup by OID
+// ============================================================================
+
+fn sizes_for_oid<P: MayoParameter>() {
+    let oid = P::OID.expect("built-in parameter sets have an OID");
+    assert_eq!(pq_mayo::sig_bytes_for_oid(&oid), Some(P::SIG_BYTES));
+    assert_eq!(pq_mayo::cpk_bytes_for_oid(&oid), Some(P::CPK_BYTES));
+    assert_eq!(pq_mayo::csk_bytes_for_oid(&oid), Some(P::CSK_BYTES));
+}
+
+#[test]
+fn sizes_for_oid_all_params() {
+    sizes_for_oid::<Mayo1>();
+    sizes_for_oid::<Mayo2>();
+    sizes_for_oid::<Mayo3>();
+    sizes_for_oid::<Mayo5>();
+}
+
+#[test]
+fn sizes_for_unknown_oid() {
+    let ed25519 = pkcs8::ObjectIdentifier::new_unwrap("1.3.101.112");
+    assert_eq!(pq_mayo::sig_bytes_for_oid(&ed25519), None);
+    assert_eq!(pq_mayo::cpk_bytes_for_oid(&ed25519), None);
+    assert_eq!(pq_mayo::csk_bytes_for_oid(&ed25519), None);
+}
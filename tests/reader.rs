@@ -0,0 +1,103 @@
+//! Streaming sign and verify tests.
+
+#![cfg(feature = "std")]
+
+use pq_mayo::{KeyPair, Mayo1, Mayo2, Signature};
+use std::io::{self, Read};
+
+/// A few megabytes, with a period that is not a power of two so it never
+/// lines up with the read chunks.
+fn large_message() -> Vec<u8> {
+    (0..251u8).cycle().take(3 * 1024 * 1024 + 5).collect()
+}
+
+/// Hands out at most `step` bytes per read, and interrupts every other call.
+struct Trickle<'a> {
+    data: &'a [u8],
+    step: usize,
+    interrupt: bool,
+}
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        if self.interrupt {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+        let n = buf.len().min(self.step).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+struct Failing;
+
+impl Read for Failing {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::other("disk on fire"))
+    }
+}
+
+fn reader_roundtrip<P: pq_mayo::MayoParameter>() {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let msg = large_message();
+
+    let sig = keypair
+        .signing_key()
+        .sign_reader(&mut rand::rng(), msg.as_slice())
+        .expect("signing failed");
+    // The streamed signature is an ordinary signature over the contents.
+    assert!(keypair.verifying_key().verify(&msg, &sig).is_ok());
+    assert!(
+        keypair
+            .verifying_key()
+            .verify_reader(
+                Trickle {
+                    data: &msg,
+                    step: 1000,
+                    interrupt: false,
+                },
+                &sig,
+            )
+            .is_ok()
+    );
+
+    let mut tampered = msg.clone();
+    tampered[msg.len() / 2] ^= 1;
+    assert!(
+        keypair
+            .verifying_key()
+            .verify_reader(tampered.as_slice(), &sig)
+            .expect_err("tampered message verified")
+            .is_verification_failure()
+    );
+}
+
+#[test]
+fn reader_roundtrip_mayo1() {
+    reader_roundtrip::<Mayo1>();
+}
+
+#[test]
+fn reader_roundtrip_mayo2() {
+    reader_roundtrip::<Mayo2>();
+}
+
+#[test]
+fn reader_errors_are_reported() {
+    let keypair = KeyPair::<Mayo1>::generate(&mut rand::rng()).expect("keygen failed");
+    assert!(matches!(
+        keypair.signing_key().sign_reader(&mut rand::rng(), Failing),
+        Err(pq_mayo::Error::Io(_))
+    ));
+
+    let sig: Signature<Mayo1> = keypair
+        .signing_key()
+        .sign_reader(&mut rand::rng(), &b"short"[..])
+        .expect("signing failed");
+    assert!(matches!(
+        keypair.verifying_key().verify_reader(Failing, &sig),
+        Err(pq_mayo::Error::Io(_))
+    ));
+}
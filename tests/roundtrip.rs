@@ -1,10 +1,14 @@
 //! Basic sign/verify roundtrip tests.
 
+#![cfg(feature = "signature-traits")]
+
 use pq_mayo::{
-    ExpandedSigningKey, ExpandedVerifyingKey, KeyPair, Mayo1, Mayo2, Mayo3, Mayo5,
-    VerificationContext, VerifyingKey,
+    ExpandedSigningKey, ExpandedVerifyingKey, KeyPair, Mayo1, Mayo2, Mayo3, Mayo5, Seed,
+    SignScratch, Signature, SigningKey, VerificationContext, VerifyingKey,
 };
-use signature::{Signer, Verifier};
+use shake::Shake256;
+use shake::digest::{ExtendableOutput, Update, XofReader};
+use signature::Signer;
 
 fn roundtrip<P: pq_mayo::MayoParameter>() {
     let mut rng = rand::rng();
@@ -46,6 +50,395 @@ fn roundtrip_mayo1() {
     roundtrip::<Mayo1>();
 }
 
+fn zeroize_wipes_in_place<P: pq_mayo::MayoParameter>() {
+    use zeroize::Zeroize;
+
+    let mut keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let mut signing_key = keypair.signing_key().clone();
+
+    signing_key.zeroize();
+    assert_eq!(signing_key.as_ref().len(), P::CSK_BYTES);
+    assert!(signing_key.as_ref().iter().all(|&b| b == 0));
+
+    keypair.zeroize();
+    assert_eq!(keypair.signing_key().as_ref().len(), P::CSK_BYTES);
+    assert!(keypair.signing_key().as_ref().iter().all(|&b| b == 0));
+    assert_eq!(keypair.verifying_key().as_ref().len(), P::CPK_BYTES);
+    assert!(keypair.verifying_key().as_ref().iter().all(|&b| b == 0));
+}
+
+#[test]
+fn zeroize_wipes_in_place_mayo1() {
+    zeroize_wipes_in_place::<Mayo1>();
+}
+
+#[test]
+fn zeroize_wipes_in_place_mayo2() {
+    zeroize_wipes_in_place::<Mayo2>();
+}
+
+fn empty_message<P: pq_mayo::MayoParameter>() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen failed");
+    let vk = keypair.verifying_key();
+    let expanded_vk = ExpandedVerifyingKey::from(vk);
+    let expanded_sk = ExpandedSigningKey::from(keypair.signing_key());
+
+    let signatures = [
+        keypair.signing_key().try_sign(b"").expect("signing failed"),
+        keypair
+            .signing_key()
+            .sign_deterministic(b"")
+            .expect("signing failed"),
+        expanded_sk.try_sign(b"").expect("signing failed"),
+    ];
+    for sig in &signatures {
+        assert!(vk.verify(b"", sig).is_ok());
+        assert!(vk.verify_from_slice(b"", sig.as_ref()).is_ok());
+        assert!(expanded_vk.verify(b"", sig).is_ok());
+        assert!(vk.verify(b"\0", sig).is_err());
+    }
+
+    let mut out = vec![0u8; P::SIG_BYTES];
+    keypair
+        .signing_key()
+        .sign_into(&mut rng, &[], &mut out)
+        .expect("signing failed");
+    assert!(vk.verify_from_slice(&[], &out).is_ok());
+
+    let other = keypair
+        .signing_key()
+        .try_sign(b"x")
+        .expect("signing failed");
+    assert!(vk.verify(b"", &other).is_err());
+}
+
+#[test]
+fn empty_message_mayo1() {
+    empty_message::<Mayo1>();
+}
+
+#[test]
+fn empty_message_mayo2() {
+    empty_message::<Mayo2>();
+}
+
+#[test]
+fn empty_message_mayo3() {
+    empty_message::<Mayo3>();
+}
+
+#[test]
+fn empty_message_mayo5() {
+    empty_message::<Mayo5>();
+}
+
+fn bit_flip_rejected<P: pq_mayo::MayoParameter>() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen failed");
+    let msg = b"strict encoding test";
+    let sig = keypair
+        .signing_key()
+        .try_sign(msg.as_slice())
+        .expect("signing failed");
+
+    // The packed solution s has no padding nibble for any parameter set, so
+    // flipping any bit of its last byte (where padding would live) must yield
+    // a rejected signature rather than a second valid encoding.
+    let s_bytes = (P::K * P::N).div_ceil(2);
+    assert_eq!(s_bytes + P::SALT_BYTES, P::SIG_BYTES);
+    for bit in 0..8 {
+        let mut bytes = sig.as_ref().to_vec();
+        bytes[s_bytes - 1] ^= 1 << bit;
+        let tampered = Signature::<P>::try_from(bytes).expect("valid length");
+        assert!(
+            keypair
+                .verifying_key()
+                .verify(msg.as_slice(), &tampered)
+                .is_err()
+        );
+    }
+}
+
+fn is_well_formed<P: pq_mayo::MayoParameter>() {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let msg = b"well formed";
+    let sig = keypair.signing_key().try_sign(msg).expect("signing failed");
+    assert!(sig.is_well_formed());
+
+    // k * n is even, so the last byte of s is all data: a flipped high
+    // nibble is still well formed and only verification rejects it.
+    let s_bytes = P::SIG_BYTES - P::SALT_BYTES;
+    let mut bytes = sig.to_vec();
+    bytes[s_bytes - 1] ^= 0x80;
+    let tampered = Signature::<P>::try_from(bytes).expect("valid length");
+    assert!(tampered.is_well_formed());
+    assert!(keypair.verifying_key().verify(msg, &tampered).is_err());
+}
+
+#[test]
+fn is_well_formed_mayo1() {
+    is_well_formed::<Mayo1>();
+}
+
+#[test]
+fn is_well_formed_mayo2() {
+    is_well_formed::<Mayo2>();
+}
+
+#[test]
+fn is_well_formed_mayo3() {
+    is_well_formed::<Mayo3>();
+}
+
+#[test]
+fn is_well_formed_mayo5() {
+    is_well_formed::<Mayo5>();
+}
+
+fn sign_fixed_time<P: pq_mayo::MayoParameter>() {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let msg = b"uniform timing";
+    let sig = keypair
+        .signing_key()
+        .sign_fixed_time(&mut rand::rng(), msg)
+        .expect("signing failed");
+    assert!(keypair.verifying_key().verify(msg, &sig).is_ok());
+    assert!(keypair.verifying_key().verify(b"other", &sig).is_err());
+}
+
+#[test]
+fn sign_fixed_time_mayo1() {
+    sign_fixed_time::<Mayo1>();
+}
+
+#[test]
+fn sign_fixed_time_mayo2() {
+    sign_fixed_time::<Mayo2>();
+}
+
+fn verify_verbose<P: pq_mayo::MayoParameter>() {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let msg = b"verbose";
+    let sig = keypair.signing_key().try_sign(msg).expect("signing failed");
+    assert_eq!(keypair.verifying_key().verify_verbose(msg, &sig), Ok(()));
+
+    let Err(pq_mayo::VerifyDetail::Mismatch { mismatched, total }) =
+        keypair.verifying_key().verify_verbose(b"other", &sig)
+    else {
+        unreachable!("a different message must give a mismatch");
+    };
+    assert_eq!(total, P::M);
+    assert!(mismatched > 0 && mismatched <= total);
+
+    let other = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    assert!(matches!(
+        other.verifying_key().verify_verbose(msg, &sig),
+        Err(pq_mayo::VerifyDetail::Mismatch { .. })
+    ));
+}
+
+#[test]
+fn verify_verbose_mayo1() {
+    verify_verbose::<Mayo1>();
+}
+
+#[test]
+fn verify_verbose_mayo2() {
+    verify_verbose::<Mayo2>();
+}
+
+fn commitments<P: pq_mayo::MayoParameter>() {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let other = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let vk = keypair.verifying_key();
+
+    let copy = VerifyingKey::<P>::try_from(vk.as_ref()).expect("valid key");
+    assert_eq!(copy.commitment(), vk.commitment());
+    assert_ne!(other.verifying_key().commitment(), vk.commitment());
+
+    let mut hasher = Shake256::default();
+    hasher.update(vk.as_ref());
+    let mut expected = [0u8; 32];
+    hasher.finalize_xof().read(&mut expected);
+    assert_eq!(vk.commitment(), expected);
+
+    let sig = keypair
+        .signing_key()
+        .try_sign(b"one")
+        .expect("signing failed");
+    let again = Signature::<P>::try_from(sig.as_ref()).expect("valid signature");
+    let different = keypair
+        .signing_key()
+        .try_sign(b"two")
+        .expect("signing failed");
+    assert_eq!(again.commitment(), sig.commitment());
+    assert_ne!(different.commitment(), sig.commitment());
+}
+
+#[test]
+fn commitments_mayo1() {
+    commitments::<Mayo1>();
+}
+
+#[test]
+fn commitments_mayo2() {
+    commitments::<Mayo2>();
+}
+
+fn key_id<P: pq_mayo::MayoParameter>() {
+    use std::collections::HashSet;
+    use std::hash::{BuildHasher, RandomState};
+
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let other = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let vk = keypair.verifying_key();
+    let copy = VerifyingKey::<P>::try_from(vk.as_ref()).expect("valid key");
+
+    assert_eq!(copy.key_id(), vk.key_id());
+    assert_ne!(other.verifying_key().key_id(), vk.key_id());
+
+    // Hash agrees with PartialEq under any hasher.
+    let state = RandomState::new();
+    assert_eq!(state.hash_one(&copy), state.hash_one(vk));
+    let keys: HashSet<_> = [vk.clone(), copy, other.verifying_key_cloned()].into();
+    assert_eq!(keys.len(), 2);
+}
+
+#[test]
+fn key_id_mayo1() {
+    key_id::<Mayo1>();
+}
+
+#[test]
+fn key_id_mayo2() {
+    key_id::<Mayo2>();
+}
+
+fn digest_signer<P: pq_mayo::MayoParameter>() {
+    use shake::Shake128;
+    use signature::{DigestSigner, DigestVerifier};
+
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let sk = keypair.signing_key();
+    let vk = keypair.verifying_key();
+    let feed = |d: &mut Shake256| {
+        d.update(b"hash then ");
+        d.update(b"sign");
+        Ok(())
+    };
+
+    // SHAKE256 is the scheme's own digest: plain and digest APIs agree.
+    let sig: Signature<P> = sk.try_sign_digest(feed).expect("signing failed");
+    vk.verify(b"hash then sign", &sig).expect("plain verify");
+    vk.verify_digest(feed, &sig).expect("digest verify");
+    let plain = sk.try_sign(b"hash then sign").expect("signing failed");
+    vk.verify_digest(feed, &plain)
+        .expect("digest verify of plain");
+    let other = |d: &mut Shake256| {
+        d.update(b"other");
+        Ok(())
+    };
+    assert!(vk.verify_digest(other, &sig).is_err());
+
+    // Another XOF only verifies through the same XOF.
+    let feed128 = |d: &mut Shake128| {
+        d.update(b"hash then sign");
+        Ok(())
+    };
+    let sig: Signature<P> = sk.try_sign_digest(feed128).expect("signing failed");
+    vk.verify_digest(feed128, &sig).expect("digest verify");
+    assert!(vk.verify(b"hash then sign", &sig).is_err());
+
+    // An error while feeding the digest is returned, not signed over.
+    let failing = |_: &mut Shake256| Err(signature::Error::new());
+    let result: Result<Signature<P>, _> = sk.try_sign_digest(failing);
+    assert!(result.is_err());
+}
+
+#[test]
+fn digest_signer_mayo1() {
+    digest_signer::<Mayo1>();
+}
+
+#[test]
+fn digest_signer_mayo2() {
+    digest_signer::<Mayo2>();
+}
+
+#[test]
+fn digest_signer_mayo5() {
+    digest_signer::<Mayo5>();
+}
+
+#[test]
+fn zeroize_public_types() {
+    use zeroize::Zeroize;
+
+    let keypair = KeyPair::<Mayo1>::generate(&mut rand::rng()).expect("keygen failed");
+    let msg = b"zeroize";
+    let mut sig = keypair.signing_key().try_sign(msg).expect("signing failed");
+    let mut vk = keypair.verifying_key_cloned();
+
+    sig.zeroize();
+    assert_eq!(
+        sig.as_ref().len(),
+        <Mayo1 as pq_mayo::MayoParameter>::SIG_BYTES
+    );
+    assert!(sig.as_ref().iter().all(|&b| b == 0));
+
+    vk.zeroize();
+    assert_eq!(
+        vk.as_ref().len(),
+        <Mayo1 as pq_mayo::MayoParameter>::CPK_BYTES
+    );
+    assert!(vk.as_ref().iter().all(|&b| b == 0));
+    assert!(keypair.verifying_key().verify(msg, &sig).is_err());
+}
+
+#[test]
+fn bit_flip_rejected_mayo1() {
+    bit_flip_rejected::<Mayo1>();
+}
+
+#[test]
+fn bit_flip_rejected_mayo2() {
+    bit_flip_rejected::<Mayo2>();
+}
+
+#[test]
+fn bit_flip_rejected_mayo3() {
+    bit_flip_rejected::<Mayo3>();
+}
+
+#[test]
+fn bit_flip_rejected_mayo5() {
+    bit_flip_rejected::<Mayo5>();
+}
+
+#[test]
+fn signature_to_vec_keeps_original_mayo1() {
+    use signature::SignatureEncoding;
+
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen failed");
+    let msg = b"to_vec test";
+    let sig = keypair
+        .signing_key()
+        .try_sign(msg.as_slice())
+        .expect("signing failed");
+
+    let bytes = sig.to_vec();
+    assert_eq!(bytes.len(), sig.len());
+    assert_eq!(sig.len(), <Mayo1 as pq_mayo::MayoParameter>::SIG_BYTES);
+    assert_eq!(bytes, sig.to_bytes());
+    assert_eq!(
+        Signature::<Mayo1>::try_from(bytes).expect("valid length"),
+        sig
+    );
+    assert!(keypair.verifying_key().verify(msg.as_slice(), &sig).is_ok());
+}
+
 #[test]
 fn wrong_message_mayo1() {
     wrong_message::<Mayo1>();
@@ -75,6 +468,7 @@ fn vk_from_sk<P: pq_mayo::MayoParameter>() {
 
     // Must match the original verifying key
     assert_eq!(derived_vk.as_ref(), keypair.verifying_key().as_ref());
+    derived_vk.validate().expect("derived vk failed validation");
 
     // Sign with the signing key, verify with the derived verifying key
     let msg = b"verifying key derivation test";
@@ -87,75 +481,1237 @@ fn vk_from_sk<P: pq_mayo::MayoParameter>() {
         .expect("verification with derived vk failed");
 }
 
-#[test]
-fn vk_from_sk_mayo1() {
-    vk_from_sk::<Mayo1>();
+fn seeds<P: pq_mayo::MayoParameter>() {
+    use shake::Shake256;
+    use shake::digest::{ExtendableOutput, Update, XofReader};
+
+    let seed = vec![0x5au8; P::SK_SEED_BYTES];
+    let keypair = KeyPair::<P>::from_seed(&seed).expect("from_seed failed");
+    assert_eq!(keypair.secret_seed(), seed.as_slice());
+    assert_eq!(keypair.signing_key().secret_seed(), seed.as_slice());
+
+    let mut expected = vec![0u8; P::PK_SEED_BYTES];
+    let mut hasher = Shake256::default();
+    hasher.update(&seed);
+    hasher.finalize_xof().read(&mut expected);
+    assert_eq!(keypair.public_seed(), expected.as_slice());
+    assert_eq!(
+        keypair.public_seed(),
+        &keypair.verifying_key().as_ref()[..P::PK_SEED_BYTES]
+    );
 }
 
 #[test]
-fn vk_from_sk_mayo2() {
-    vk_from_sk::<Mayo2>();
+fn seeds_mayo1() {
+    seeds::<Mayo1>();
 }
 
 #[test]
-fn vk_from_sk_mayo3() {
-    vk_from_sk::<Mayo3>();
+fn seeds_mayo2() {
+    seeds::<Mayo2>();
 }
 
 #[test]
-fn vk_from_sk_mayo5() {
-    vk_from_sk::<Mayo5>();
+fn seeds_mayo3() {
+    seeds::<Mayo3>();
 }
 
 #[test]
-fn expanded_verifying_key_mayo1() {
+fn seeds_mayo5() {
+    seeds::<Mayo5>();
+}
+
+/// 10 MiB, hashed five times; one parameter set is enough since only the
+/// message digest depends on the length.
+fn large_message<P: pq_mayo::MayoParameter>() {
     let mut rng = rand::rng();
-    let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen failed");
-    let expanded = keypair.verifying_key().expand();
-    let expanded_from_bytes =
-        ExpandedVerifyingKey::<Mayo1>::try_from(keypair.verifying_key().as_ref())
-            .expect("expanded vk");
+    let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen failed");
+    let mut msg = vec![0xa5u8; 10 * 1024 * 1024];
 
-    assert_eq!(expanded.as_ref(), keypair.verifying_key().as_ref());
-    assert_eq!(expanded, expanded_from_bytes);
+    let sig = keypair
+        .signing_key()
+        .sign_with_rng(&mut rng, &msg)
+        .expect("signing failed");
+    keypair
+        .verifying_key()
+        .verify(&msg, &sig)
+        .expect("verification failed");
 
-    let msg = b"expanded verifying key test";
+    let ctx_sig = keypair
+        .signing_key()
+        .sign_with_context(&mut rng, b"ctx", &msg)
+        .expect("context signing failed");
+    keypair
+        .verifying_key()
+        .verify_with_context(b"ctx", &msg, &ctx_sig)
+        .expect("context verification failed");
+
+    *msg.last_mut().expect("non-empty") ^= 1;
+    assert!(keypair.verifying_key().verify(&msg, &sig).is_err());
+}
+
+#[test]
+fn large_message_mayo1() {
+    large_message::<Mayo1>();
+}
+
+fn derive<P: pq_mayo::MayoParameter>() {
+    let master = [0x42u8; 32];
+    let a = KeyPair::<P>::derive(&master, b"signing").expect("derive failed");
+    let again = KeyPair::<P>::derive(&master, b"signing").expect("derive failed");
+    assert_eq!(a, again);
+
+    let mut seed = vec![0u8; P::SK_SEED_BYTES];
+    let mut hasher = Shake256::default();
+    hasher.update(&master);
+    hasher.update(b"signing");
+    hasher.update(&7u64.to_le_bytes());
+    hasher.finalize_xof().read(&mut seed);
+    assert_eq!(a.secret_seed(), seed.as_slice());
+
+    let b = KeyPair::<P>::derive(&master, b"attestation").expect("derive failed");
+    assert_ne!(a.verifying_key(), b.verifying_key());
+    let other_master = KeyPair::<P>::derive(&[0x43u8; 32], b"signing").expect("derive failed");
+    assert_ne!(a.verifying_key(), other_master.verifying_key());
+
+    // Shifting a byte from the master secret into the label changes the key.
+    let shifted = KeyPair::<P>::derive(&master[..31], b"\x42signing").expect("derive failed");
+    assert_ne!(a.verifying_key(), shifted.verifying_key());
+}
+
+#[test]
+fn derive_mayo1() {
+    derive::<Mayo1>();
+}
+
+#[test]
+fn derive_mayo2() {
+    derive::<Mayo2>();
+}
+
+fn verify_raw<P: pq_mayo::MayoParameter>() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen failed");
+    let msg = b"raw verify";
     let sig = keypair
         .signing_key()
         .try_sign(msg.as_slice())
         .expect("signing failed");
-    expanded
-        .verify(msg.as_slice(), &sig)
-        .expect("expanded verification failed");
+    let cpk = keypair.verifying_key().as_ref();
+
+    assert!(pq_mayo::verify_raw::<P>(cpk, msg, sig.as_ref()).is_ok());
+    assert!(matches!(
+        pq_mayo::verify_raw::<P>(cpk, b"other", sig.as_ref()),
+        Err(pq_mayo::Error::VerificationFailed)
+    ));
+    assert!(matches!(
+        pq_mayo::verify_raw::<P>(&cpk[1..], msg, sig.as_ref()),
+        Err(pq_mayo::Error::InvalidKeyLength { .. })
+    ));
+    assert!(matches!(
+        pq_mayo::verify_raw::<P>(cpk, msg, &sig.as_ref()[1..]),
+        Err(pq_mayo::Error::InvalidSignatureLength { .. })
+    ));
 }
 
 #[test]
-fn expanded_signing_key_mayo1() {
-    let mut rng = rand::rng();
-    let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen failed");
-    let expanded = ExpandedSigningKey::<Mayo1>::from(keypair.signing_key());
-    let msg = b"expanded signing key test";
+fn verify_raw_mayo1() {
+    verify_raw::<Mayo1>();
+}
 
-    let sig = expanded.try_sign(msg.as_slice()).expect("signing failed");
-    keypair
-        .verifying_key()
-        .verify(msg.as_slice(), &sig)
-        .expect("verification failed");
+#[test]
+fn verify_raw_mayo2() {
+    verify_raw::<Mayo2>();
 }
 
 #[test]
-fn verification_context_mayo1() {
+fn verify_raw_mayo3() {
+    verify_raw::<Mayo3>();
+}
+
+#[test]
+fn verify_raw_mayo5() {
+    verify_raw::<Mayo5>();
+}
+
+fn verify_any<P: pq_mayo::MayoParameter>() {
     let mut rng = rand::rng();
-    let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen failed");
-    let expanded = ExpandedVerifyingKey::<Mayo1>::from(keypair.verifying_key());
-    let mut context = VerificationContext::<Mayo1>::from(&expanded);
-    let msg = b"verification context test";
+    let keypairs: Vec<KeyPair<P>> = (0..4)
+        .map(|_| KeyPair::<P>::generate(&mut rng).expect("keygen failed"))
+        .collect();
+    let keys: Vec<&VerifyingKey<P>> = keypairs.iter().map(|kp| kp.verifying_key()).collect();
+    let expanded: Vec<ExpandedVerifyingKey<P>> = keys.iter().map(|vk| vk.expand()).collect();
+    let expanded_refs: Vec<&ExpandedVerifyingKey<P>> = expanded.iter().collect();
+    let msg = b"which key signed this";
+
+    for (signer, keypair) in keypairs.iter().enumerate() {
+        let sig = keypair.signing_key().try_sign(msg).expect("signing failed");
+        assert_eq!(pq_mayo::verify_any(&keys, msg, &sig).ok(), Some(signer));
+        assert_eq!(
+            pq_mayo::verify_any_expanded(&expanded_refs, msg, &sig).ok(),
+            Some(signer)
+        );
+        assert!(
+            pq_mayo::verify_any(&keys, b"other", &sig).is_err_and(|e| e.is_verification_failure())
+        );
+    }
+
+    let sig = keypairs[0]
+        .signing_key()
+        .try_sign(msg)
+        .expect("signing failed");
+    assert!(pq_mayo::verify_any(&keys[1..], msg, &sig).is_err_and(|e| e.is_verification_failure()));
+    assert!(pq_mayo::verify_any::<P>(&[], msg, &sig).is_err_and(|e| e.is_verification_failure()));
+}
 
+#[test]
+fn verify_any_mayo1() {
+    verify_any::<Mayo1>();
+}
+
+#[test]
+fn verify_any_mayo2() {
+    verify_any::<Mayo2>();
+}
+
+fn verify_with_expanded<P: pq_mayo::MayoParameter>() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen failed");
+    let msg = b"expanded matrices";
     let sig = keypair
         .signing_key()
         .try_sign(msg.as_slice())
         .expect("signing failed");
-    context
-        .verify(msg.as_slice(), &sig)
-        .expect("context verification failed");
+    let vk = keypair.verifying_key();
+    let evk = vk.expand();
+    let (p1, p2, p3) = (evk.p1(), evk.p2(), evk.p3());
+    assert_eq!(p1.len(), P::P1_LIMBS);
+    assert_eq!(p2.len(), P::P2_LIMBS);
+    assert_eq!(p3.len(), P::P3_LIMBS);
+
+    assert!(vk.verify(msg, &sig).is_ok());
+    assert!(pq_mayo::verify_with_expanded::<P>(p1, p2, p3, msg, sig.as_ref()).is_ok());
+    assert!(matches!(
+        pq_mayo::verify_with_expanded::<P>(p1, p2, p3, b"other", sig.as_ref()),
+        Err(pq_mayo::Error::VerificationFailed)
+    ));
+    assert!(matches!(
+        pq_mayo::verify_with_expanded::<P>(p1, p2, &p3[1..], msg, sig.as_ref()),
+        Err(pq_mayo::Error::InvalidKeyLength { .. })
+    ));
+}
+
+#[test]
+fn verify_with_expanded_mayo1() {
+    verify_with_expanded::<Mayo1>();
+}
+
+#[test]
+fn verify_with_expanded_mayo2() {
+    verify_with_expanded::<Mayo2>();
+}
+
+#[test]
+fn verify_with_expanded_mayo3() {
+    verify_with_expanded::<Mayo3>();
+}
+
+#[test]
+fn verify_with_expanded_mayo5() {
+    verify_with_expanded::<Mayo5>();
+}
+
+#[test]
+fn vk_from_sk_mayo1() {
+    vk_from_sk::<Mayo1>();
+}
+
+#[test]
+fn vk_from_sk_mayo2() {
+    vk_from_sk::<Mayo2>();
+}
+
+#[test]
+fn vk_from_sk_mayo3() {
+    vk_from_sk::<Mayo3>();
+}
+
+#[test]
+fn vk_from_sk_mayo5() {
+    vk_from_sk::<Mayo5>();
+}
+
+fn matches_signing_key<P: pq_mayo::MayoParameter>() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen failed");
+    let other = KeyPair::<P>::generate(&mut rng).expect("keygen failed");
+
+    let vk = VerifyingKey::<P>::try_from(keypair.verifying_key().as_ref()).expect("valid key");
+    let sk = SigningKey::<P>::try_from(keypair.signing_key().as_ref()).expect("valid key");
+    assert!(vk.matches_signing_key(&sk));
+    assert!(!vk.matches_signing_key(other.signing_key()));
+    assert!(!other.verifying_key().matches_signing_key(&sk));
+}
+
+#[test]
+fn matches_signing_key_mayo1() {
+    matches_signing_key::<Mayo1>();
+}
+
+#[test]
+fn matches_signing_key_mayo2() {
+    matches_signing_key::<Mayo2>();
+}
+
+fn try_from_validated<P: pq_mayo::MayoParameter>() {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let bytes = keypair.verifying_key().as_ref();
+    let vk = VerifyingKey::<P>::try_from_validated(bytes).expect("valid key rejected");
+    assert_eq!(&vk, keypair.verifying_key());
+
+    // Truncated: a length error, as with `try_from`.
+    let truncated = &bytes[..P::CPK_BYTES / 2];
+    assert!(matches!(
+        VerifyingKey::<P>::try_from_validated(truncated),
+        Err(pq_mayo::Error::InvalidKeyLength { .. })
+    ));
+
+    // Truncated and zero-padded back to length: plain decoding accepts it,
+    // validation does not.
+    for keep in [P::CPK_BYTES / 2, P::CPK_BYTES - P::M_BYTES] {
+        let mut padded = bytes[..keep].to_vec();
+        padded.resize(P::CPK_BYTES, 0);
+        assert!(VerifyingKey::<P>::try_from(padded.as_slice()).is_ok());
+        assert!(matches!(
+            VerifyingKey::<P>::try_from_validated(&padded),
+            Err(pq_mayo::Error::MalformedKey)
+        ));
+    }
+}
+
+#[test]
+fn try_from_validated_mayo1() {
+    try_from_validated::<Mayo1>();
+}
+
+#[test]
+fn try_from_validated_mayo2() {
+    try_from_validated::<Mayo2>();
+}
+
+#[test]
+fn try_from_validated_mayo3() {
+    try_from_validated::<Mayo3>();
+}
+
+#[test]
+fn try_from_validated_mayo5() {
+    try_from_validated::<Mayo5>();
+}
+
+#[test]
+fn expanded_verifying_key_mayo1() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen failed");
+    let expanded = keypair.verifying_key().expand();
+    let expanded_from_bytes =
+        ExpandedVerifyingKey::<Mayo1>::try_from(keypair.verifying_key().as_ref())
+            .expect("expanded vk");
+
+    assert_eq!(expanded.as_ref(), keypair.verifying_key().as_ref());
+    assert_eq!(expanded, expanded_from_bytes);
+
+    let msg = b"expanded verifying key test";
+    let sig = keypair
+        .signing_key()
+        .try_sign(msg.as_slice())
+        .expect("signing failed");
+    expanded
+        .verify(msg.as_slice(), &sig)
+        .expect("expanded verification failed");
+}
+
+#[test]
+fn expanded_signing_key_mayo1() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen failed");
+    let expanded = ExpandedSigningKey::<Mayo1>::from(keypair.signing_key());
+    let msg = b"expanded signing key test";
+
+    let sig = expanded.try_sign(msg.as_slice()).expect("signing failed");
+    keypair
+        .verifying_key()
+        .verify(msg.as_slice(), &sig)
+        .expect("verification failed");
+}
+
+#[test]
+fn sign_with_scratch_matches_allocating_path_mayo1() {
+    use rand::SeedableRng;
+
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen failed");
+    let expanded = keypair.signing_key().expand();
+    let mut scratch = SignScratch::<Mayo1>::new();
+
+    for msg in [b"first".as_slice(), b"second".as_slice()] {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let expected = keypair
+            .signing_key()
+            .sign_with_rng(&mut rng, msg)
+            .expect("signing failed");
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let sig = keypair
+            .signing_key()
+            .sign_with_scratch(&mut scratch, &mut rng, msg)
+            .expect("scratch signing failed");
+        assert_eq!(sig, expected);
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let sig = expanded
+            .sign_with_scratch(&mut scratch, &mut rng, msg)
+            .expect("expanded scratch signing failed");
+        assert_eq!(sig, expected);
+        keypair
+            .verifying_key()
+            .verify(msg, &sig)
+            .expect("verification failed");
+    }
+}
+
+#[test]
+fn randomized_signer_mayo1() {
+    use rand::SeedableRng;
+    use signature::RandomizedSigner;
+
+    let keypair = KeyPair::<Mayo1>::generate(&mut rand::rng()).expect("keygen failed");
+    let sk = keypair.signing_key();
+    let msg = b"randomized signer";
+
+    let via_trait = RandomizedSigner::try_sign_with_rng(
+        sk,
+        &mut rand::rngs::StdRng::seed_from_u64(7),
+        msg.as_slice(),
+    )
+    .expect("signing failed");
+    let via_inherent = sk
+        .sign_with_rng(&mut rand::rngs::StdRng::seed_from_u64(7), msg)
+        .expect("signing failed");
+    assert_eq!(via_trait, via_inherent);
+    assert!(keypair.verifying_key().verify(msg, &via_trait).is_ok());
+}
+
+fn keypair_into_parts<P: pq_mayo::MayoParameter>() {
+    let seed = vec![0x11u8; P::SK_SEED_BYTES];
+    let msg = b"moved out";
+
+    let (sk, vk): (SigningKey<P>, VerifyingKey<P>) =
+        KeyPair::<P>::from_seed(&seed).expect("from_seed").into();
+    let sig = sk.try_sign(msg.as_slice()).expect("signing failed");
+    assert!(vk.verify(msg, &sig).is_ok());
+
+    let sk = KeyPair::<P>::from_seed(&seed)
+        .expect("from_seed")
+        .into_signing_key();
+    let vk = KeyPair::<P>::from_seed(&seed)
+        .expect("from_seed")
+        .into_verifying_key();
+    assert_eq!(VerifyingKey::from(&sk), vk);
+    let sig = sk.try_sign(msg.as_slice()).expect("signing failed");
+    assert!(vk.verify(msg, &sig).is_ok());
+}
+
+#[test]
+fn keypair_into_parts_mayo1() {
+    keypair_into_parts::<Mayo1>();
+}
+
+#[test]
+fn keypair_into_parts_mayo2() {
+    keypair_into_parts::<Mayo2>();
+}
+
+#[test]
+fn keypair_into_parts_mayo3() {
+    keypair_into_parts::<Mayo3>();
+}
+
+#[test]
+fn keypair_into_parts_mayo5() {
+    keypair_into_parts::<Mayo5>();
+}
+
+fn typed_seed<P: pq_mayo::MayoParameter>() {
+    assert_eq!(KeyPair::<P>::SEED_LEN, P::SK_SEED_BYTES);
+
+    let seed = Seed::<P>::generate(&mut rand::rng());
+    assert_eq!(seed.as_ref().len(), KeyPair::<P>::SEED_LEN);
+    assert_eq!(
+        KeyPair::from(&seed),
+        KeyPair::<P>::from_seed(seed.as_ref()).expect("from_seed")
+    );
+    assert_eq!(
+        Seed::<P>::try_from(seed.as_ref()).expect("right length"),
+        seed
+    );
+    assert!(matches!(
+        Seed::<P>::try_from(&seed.as_ref()[1..]),
+        Err(pq_mayo::Error::InvalidSeedLength { .. })
+    ));
+}
+
+#[test]
+fn typed_seed_mayo1() {
+    typed_seed::<Mayo1>();
+}
+
+#[test]
+fn typed_seed_mayo2() {
+    typed_seed::<Mayo2>();
+}
+
+#[test]
+fn typed_seed_mayo3() {
+    typed_seed::<Mayo3>();
+}
+
+#[test]
+fn typed_seed_mayo5() {
+    typed_seed::<Mayo5>();
+}
+
+#[test]
+fn keys_and_signatures_in_sets_mayo1() {
+    use std::collections::{BTreeSet, HashSet};
+
+    let keypairs: Vec<_> = (0u8..4)
+        .map(|i| KeyPair::<Mayo1>::from_seed(&[i + 1; 24]).expect("from_seed"))
+        .collect();
+
+    let mut vks = BTreeSet::new();
+    let mut sigs = HashSet::new();
+    for kp in keypairs.iter().chain(keypairs.iter()) {
+        vks.insert(kp.verifying_key().clone());
+        sigs.insert(kp.signing_key().sign_deterministic(b"set").expect("sign"));
+    }
+    assert_eq!(vks.len(), 4);
+    assert_eq!(sigs.len(), 4);
+    assert!(vks.contains(keypairs[2].verifying_key()));
+
+    let ordered: Vec<_> = vks.iter().map(|vk| vk.as_ref().to_vec()).collect();
+    let mut sorted = ordered.clone();
+    sorted.sort();
+    assert_eq!(ordered, sorted);
+}
+
+#[test]
+fn from_seed_checked_mayo1() {
+    const LEN: usize = <Mayo1 as pq_mayo::MayoParameter>::SK_SEED_BYTES;
+
+    for weak in [[0x00u8; LEN], [0xFFu8; LEN]] {
+        assert!(matches!(
+            KeyPair::<Mayo1>::from_seed_checked(&weak),
+            Err(pq_mayo::Error::WeakSeed)
+        ));
+        assert!(KeyPair::<Mayo1>::from_seed(&weak).is_ok());
+    }
+
+    let mut seed = [0u8; LEN];
+    seed[LEN - 1] = 1;
+    assert_eq!(
+        KeyPair::<Mayo1>::from_seed_checked(&seed).expect("accepted"),
+        KeyPair::<Mayo1>::from_seed(&seed).expect("accepted")
+    );
+    assert!(matches!(
+        KeyPair::<Mayo1>::from_seed_checked(&[0u8; 3]),
+        Err(pq_mayo::Error::InvalidSeedLength { .. })
+    ));
+}
+
+fn sign_with_entropy<P: pq_mayo::MayoParameter>() {
+    use rand::{Rng, SeedableRng};
+
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let msg = b"caller entropy";
+    let mut entropy = vec![0u8; P::SALT_BYTES];
+    rand::rngs::StdRng::seed_from_u64(99).fill_bytes(&mut entropy);
+
+    let sig = keypair
+        .signing_key()
+        .sign_with_entropy(&entropy, msg)
+        .expect("signing failed");
+    let via_rng = keypair
+        .signing_key()
+        .sign_with_rng(&mut rand::rngs::StdRng::seed_from_u64(99), msg)
+        .expect("signing failed");
+    assert_eq!(sig, via_rng);
+    assert_eq!(
+        keypair
+            .signing_key()
+            .expand()
+            .sign_with_entropy(&entropy, msg)
+            .expect("signing failed"),
+        sig
+    );
+    assert!(keypair.verifying_key().verify(msg, &sig).is_ok());
+
+    assert!(matches!(
+        keypair.signing_key().sign_with_entropy(&entropy[1..], msg),
+        Err(pq_mayo::Error::InvalidEntropyLength { .. })
+    ));
+}
+
+#[test]
+fn sign_with_entropy_mayo1() {
+    sign_with_entropy::<Mayo1>();
+}
+
+#[test]
+fn sign_with_entropy_mayo2() {
+    sign_with_entropy::<Mayo2>();
+}
+
+#[test]
+fn sign_with_entropy_mayo3() {
+    sign_with_entropy::<Mayo3>();
+}
+
+#[test]
+fn sign_with_entropy_mayo5() {
+    sign_with_entropy::<Mayo5>();
+}
+
+fn verify_from_slice<P: pq_mayo::MayoParameter>() {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let vk = keypair.verifying_key();
+    let msg = b"verify from a buffer";
+    let sig = keypair.signing_key().try_sign(msg).expect("signing failed");
+
+    let mut tampered = sig.to_vec();
+    tampered[P::SIG_BYTES / 2] ^= 0x10;
+    for (m, bytes) in [
+        (&msg[..], sig.as_ref()),
+        (&b"other message"[..], sig.as_ref()),
+        (&msg[..], tampered.as_slice()),
+    ] {
+        let via_trait = vk.verify(m, &Signature::<P>::try_from(bytes).expect("valid length"));
+        assert_eq!(vk.verify_from_slice(m, bytes).is_ok(), via_trait.is_ok());
+    }
+    assert!(vk.verify_from_slice(msg, sig.as_ref()).is_ok());
+
+    // Extra trailing bytes are not ignored.
+    let mut long = sig.to_vec();
+    long.push(0);
+    assert!(matches!(
+        vk.verify_from_slice(msg, &long),
+        Err(pq_mayo::Error::InvalidSignatureLength { .. })
+    ));
+    assert!(matches!(
+        vk.verify_from_slice(msg, &long[..P::SIG_BYTES - 1]),
+        Err(pq_mayo::Error::InvalidSignatureLength { .. })
+    ));
+}
+
+#[test]
+fn verify_from_slice_mayo1() {
+    verify_from_slice::<Mayo1>();
+}
+
+#[test]
+fn verify_from_slice_mayo2() {
+    verify_from_slice::<Mayo2>();
+}
+
+fn sign_into<P: pq_mayo::MayoParameter>() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen failed");
+    let msg = b"sign into a buffer";
+
+    let mut out = vec![0xAAu8; P::SIG_BYTES + 7];
+    let written = keypair
+        .signing_key()
+        .sign_into(&mut rng, msg, &mut out)
+        .expect("signing failed");
+    assert_eq!(written, P::SIG_BYTES);
+    assert!(out[written..].iter().all(|&b| b == 0xAA));
+
+    let sig = Signature::<P>::try_from(&out[..written]).expect("valid length");
+    assert!(keypair.verifying_key().verify(msg, &sig).is_ok());
+
+    let mut short = vec![0u8; P::SIG_BYTES - 1];
+    assert!(matches!(
+        keypair.signing_key().sign_into(&mut rng, msg, &mut short),
+        Err(pq_mayo::Error::InvalidSignatureLength { expected, got })
+            if expected == P::SIG_BYTES && got == P::SIG_BYTES - 1
+    ));
+}
+
+#[test]
+fn sign_into_mayo1() {
+    sign_into::<Mayo1>();
+}
+
+#[test]
+fn sign_into_mayo3() {
+    sign_into::<Mayo3>();
+}
+
+fn verifying_key_cloned<P: pq_mayo::MayoParameter>() {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let cloned = keypair.verifying_key_cloned();
+    assert_eq!(&cloned, keypair.verifying_key());
+    assert_eq!(cloned, VerifyingKey::from(keypair.signing_key()));
+
+    let rebuilt =
+        KeyPair::from_signing_key(keypair.signing_key().clone()).expect("from_signing_key");
+    assert_eq!(rebuilt.verifying_key(), &cloned);
+
+    let sig = keypair
+        .signing_key()
+        .try_sign(b"cloned")
+        .expect("signing failed");
+    assert!(cloned.verify(b"cloned", &sig).is_ok());
+}
+
+#[test]
+fn verifying_key_cloned_mayo1() {
+    verifying_key_cloned::<Mayo1>();
+}
+
+#[test]
+fn verifying_key_cloned_mayo5() {
+    verifying_key_cloned::<Mayo5>();
+}
+
+fn keypair_expand<P: pq_mayo::MayoParameter>() {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let (esk, evk) = keypair.expand();
+
+    let separate = keypair.verifying_key().expand();
+    assert_eq!(evk, separate);
+    assert_eq!(evk.p1(), separate.p1());
+    assert_eq!(evk.p2(), separate.p2());
+    assert_eq!(evk.p3(), separate.p3());
+    assert_eq!(
+        esk.to_expanded_bytes(),
+        keypair.signing_key().expand().to_expanded_bytes()
+    );
+
+    let sig = esk
+        .sign_with_rng(&mut rand::rng(), b"shared expansion")
+        .expect("signing failed");
+    assert!(evk.verify(b"shared expansion", &sig).is_ok());
+    assert!(evk.verify(b"other", &sig).is_err());
+}
+
+#[test]
+fn keypair_expand_mayo1() {
+    keypair_expand::<Mayo1>();
+}
+
+#[test]
+fn keypair_expand_mayo2() {
+    keypair_expand::<Mayo2>();
+}
+
+fn keypair_from_signing_key<P: pq_mayo::MayoParameter>() {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let converted: KeyPair<P> = keypair.signing_key().clone().into();
+    assert_eq!(converted, keypair);
+
+    let sig = converted
+        .signing_key()
+        .try_sign(b"converted")
+        .expect("signing failed");
+    assert!(converted.verifying_key().verify(b"converted", &sig).is_ok());
+    assert!(keypair.verifying_key().verify(b"converted", &sig).is_ok());
+}
+
+#[test]
+fn keypair_from_signing_key_mayo1() {
+    keypair_from_signing_key::<Mayo1>();
+}
+
+#[test]
+fn keypair_from_signing_key_mayo2() {
+    keypair_from_signing_key::<Mayo2>();
+}
+
+fn keypair_eq_components<P: pq_mayo::MayoParameter>() {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let other = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+
+    assert!(keypair == *keypair.signing_key());
+    assert!(keypair == *keypair.verifying_key());
+    assert!(keypair != *other.signing_key());
+    assert!(keypair != *other.verifying_key());
+}
+
+#[test]
+fn keypair_eq_components_mayo1() {
+    keypair_eq_components::<Mayo1>();
+}
+
+#[test]
+fn keypair_eq_components_mayo2() {
+    keypair_eq_components::<Mayo2>();
+}
+
+fn hex_string_roundtrip<P: pq_mayo::MayoParameter>() {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let sig = keypair
+        .signing_key()
+        .try_sign(b"hex")
+        .expect("signing failed");
+
+    let vk_hex = keypair.verifying_key().to_string();
+    let sig_hex = sig.to_string();
+    assert_eq!(vk_hex.len(), 2 * P::CPK_BYTES);
+    assert_eq!(sig_hex, hex::encode(&sig));
+
+    let vk: VerifyingKey<P> = vk_hex.parse().expect("valid hex key");
+    let parsed: Signature<P> = sig_hex.to_uppercase().parse().expect("valid hex signature");
+    assert_eq!(vk, *keypair.verifying_key());
+    assert_eq!(parsed, sig);
+    assert!(vk.verify(b"hex", &parsed).is_ok());
+
+    assert!(matches!(
+        "zz".parse::<Signature<P>>(),
+        Err(pq_mayo::Error::InvalidHex)
+    ));
+    assert!(
+        sig_hex[2..]
+            .parse::<Signature<P>>()
+            .is_err_and(|e| e.is_length_error())
+    );
+    assert!(
+        "00".parse::<VerifyingKey<P>>()
+            .is_err_and(|e| e.is_length_error())
+    );
+}
+
+#[test]
+fn hex_string_roundtrip_mayo1() {
+    hex_string_roundtrip::<Mayo1>();
+}
+
+#[test]
+fn hex_string_roundtrip_mayo2() {
+    hex_string_roundtrip::<Mayo2>();
+}
+
+fn sign_with_precomputed_digest<P: pq_mayo::MayoParameter>() {
+    use rand::SeedableRng;
+
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let other = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let msg = b"one message, many keys";
+    let digest = pq_mayo::message_digest::<P>(msg);
+    assert_eq!(digest.as_ref().len(), P::DIGEST_BYTES);
+    assert_eq!(digest, pq_mayo::message_digest::<P>(msg));
+    assert_ne!(digest, pq_mayo::message_digest::<P>(b"another message"));
+
+    // Same RNG output, same signature as hashing the message in place.
+    let expected = keypair
+        .signing_key()
+        .sign_with_rng(&mut rand::rngs::StdRng::seed_from_u64(7), msg)
+        .expect("signing failed");
+    let sig = keypair
+        .signing_key()
+        .sign_with_precomputed_digest(&mut rand::rngs::StdRng::seed_from_u64(7), &digest)
+        .expect("signing failed");
+    assert_eq!(sig, expected);
+
+    let sig = other
+        .signing_key()
+        .sign_with_precomputed_digest(&mut rand::rng(), &digest)
+        .expect("signing failed");
+    assert!(other.verifying_key().verify(msg, &sig).is_ok());
+}
+
+#[test]
+fn sign_with_precomputed_digest_mayo1() {
+    sign_with_precomputed_digest::<Mayo1>();
+}
+
+#[test]
+fn sign_with_precomputed_digest_mayo2() {
+    sign_with_precomputed_digest::<Mayo2>();
+}
+
+fn sign_batch_with_stats<P: pq_mayo::MayoParameter>() {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let msgs: Vec<Vec<u8>> = (0u8..20).map(|i| vec![i; usize::from(i)]).collect();
+    let msgs: Vec<&[u8]> = msgs.iter().map(Vec::as_slice).collect();
+
+    let (signatures, stats) = keypair
+        .signing_key()
+        .sign_batch_with_stats(&mut rand::rng(), &msgs)
+        .expect("signing failed");
+
+    assert_eq!(signatures.len(), msgs.len());
+    for (msg, sig) in msgs.iter().zip(&signatures) {
+        assert!(keypair.verifying_key().verify(msg, sig).is_ok());
+    }
+
+    let histogram = stats.histogram();
+    assert_eq!(histogram.iter().sum::<u64>(), 20);
+    let from_histogram: u64 = (1u64..)
+        .zip(histogram)
+        .map(|(attempts, n)| attempts * n)
+        .sum();
+    assert_eq!(stats.total_attempts(), from_histogram);
+    assert!(stats.total_attempts() >= 20);
+    let max = stats.max_attempts();
+    assert!(max >= 1);
+    let last_used = histogram.iter().rposition(|&n| n > 0).expect("non-empty");
+    assert_eq!(usize::try_from(max).expect("fits"), last_used + 1);
+}
+
+#[test]
+fn sign_batch_with_stats_mayo1() {
+    sign_batch_with_stats::<Mayo1>();
+}
+
+#[test]
+fn sign_batch_with_stats_mayo2() {
+    sign_batch_with_stats::<Mayo2>();
+}
+
+fn expected_solve_attempts<P: pq_mayo::MayoParameter>() {
+    let expected = P::expected_solve_attempts();
+    assert!((1.0..1.08).contains(&expected), "{expected}");
+
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let msgs: Vec<[u8; 2]> = (0u16..200).map(u16::to_le_bytes).collect();
+    let msgs: Vec<&[u8]> = msgs.iter().map(<[u8; 2]>::as_slice).collect();
+    let (_, stats) = keypair
+        .signing_key()
+        .sign_batch_with_stats(&mut rand::rng(), &msgs)
+        .expect("signing failed");
+    // The standard error of the mean is at most 0.02 (MAYO_2), so this
+    // tolerance is five of them.
+    let total = u32::try_from(stats.total_attempts()).expect("fits");
+    let average = f64::from(total) / 200.0;
+    assert!((average - expected).abs() < 0.1, "{average} vs {expected}");
+}
+
+#[test]
+fn expected_solve_attempts_mayo1() {
+    expected_solve_attempts::<Mayo1>();
+}
+
+#[test]
+fn expected_solve_attempts_mayo2() {
+    expected_solve_attempts::<Mayo2>();
+}
+
+#[test]
+fn expected_solve_attempts_mayo3() {
+    expected_solve_attempts::<Mayo3>();
+}
+
+#[test]
+fn expected_solve_attempts_mayo5() {
+    expected_solve_attempts::<Mayo5>();
+}
+
+fn sign_iter<P: pq_mayo::MayoParameter>() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen failed");
+    let msgs: Vec<Vec<u8>> = (0..100u32).map(|i| i.to_le_bytes().to_vec()).collect();
+
+    let mut signing_rng = rand::rng();
+    let mut signatures = keypair
+        .signing_key()
+        .sign_iter(&mut signing_rng, msgs.iter().map(Vec::as_slice));
+    let first = signatures
+        .next()
+        .expect("one per message")
+        .expect("signing failed");
+    let rest = signatures
+        .collect::<Result<Vec<_>, _>>()
+        .expect("signing failed");
+    assert_eq!(rest.len(), msgs.len() - 1);
+
+    for (msg, sig) in msgs.iter().zip(core::iter::once(&first).chain(&rest)) {
+        assert!(keypair.verifying_key().verify(msg, sig).is_ok());
+    }
+    assert!(keypair.verifying_key().verify(&msgs[1], &first).is_err());
+}
+
+#[test]
+fn sign_iter_mayo1() {
+    sign_iter::<Mayo1>();
+}
+
+#[test]
+fn sign_iter_mayo2() {
+    sign_iter::<Mayo2>();
+}
+
+fn sign_with_verify<P: pq_mayo::MayoParameter>() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen failed");
+    let msg = b"verify after sign";
+
+    let sig = keypair
+        .signing_key()
+        .sign_with_verify(&mut rng, msg)
+        .expect("signing failed");
+    assert_eq!(sig.as_ref().len(), P::SIG_BYTES);
+    assert!(keypair.verifying_key().verify(msg, &sig).is_ok());
+    assert!(keypair.verifying_key().verify(b"other", &sig).is_err());
+}
+
+#[test]
+fn sign_with_verify_mayo1() {
+    sign_with_verify::<Mayo1>();
+}
+
+#[test]
+fn sign_with_verify_mayo2() {
+    sign_with_verify::<Mayo2>();
+}
+
+#[test]
+fn sign_with_verify_mayo3() {
+    sign_with_verify::<Mayo3>();
+}
+
+#[test]
+fn sign_with_verify_mayo5() {
+    sign_with_verify::<Mayo5>();
+}
+
+fn signature_layout<P: pq_mayo::MayoParameter>() {
+    use shake::Shake256;
+    use shake::digest::{ExtendableOutput, Update, XofReader};
+
+    let keypair = KeyPair::<P>::from_seed(&vec![3u8; P::SK_SEED_BYTES]).expect("from_seed");
+    let msg = b"salt layout";
+    let sig = keypair
+        .signing_key()
+        .sign_deterministic(msg)
+        .expect("signing failed");
+
+    assert_eq!(sig.salt().len(), P::SALT_BYTES);
+    assert_eq!(sig.encoded_solution().len(), (P::K * P::N).div_ceil(2));
+    assert_eq!([sig.encoded_solution(), sig.salt()].concat(), sig.to_vec());
+    let (solution, salt) = sig.split();
+    assert_eq!((solution, salt), (sig.encoded_solution(), sig.salt()));
+    assert_eq!(solution.len(), P::SIG_BYTES - P::SALT_BYTES);
+    assert_eq!([solution, salt].concat(), sig.to_vec());
+
+    // With a zero randomizer, salt = SHAKE256(SHAKE256(msg) || 0^salt || seed_sk).
+    let mut digest = vec![0u8; P::DIGEST_BYTES];
+    let mut hasher = Shake256::default();
+    hasher.update(msg);
+    hasher.finalize_xof().read(&mut digest);
+    let mut salt = vec![0u8; P::SALT_BYTES];
+    let mut hasher = Shake256::default();
+    hasher.update(&digest);
+    hasher.update(&vec![0u8; P::SALT_BYTES]);
+    hasher.update(keypair.secret_seed());
+    hasher.finalize_xof().read(&mut salt);
+    assert_eq!(sig.salt(), salt.as_slice());
+
+    // Verification reads the salt from the same region.
+    let mut tampered = sig.to_vec();
+    tampered[P::SIG_BYTES - 1] ^= 1;
+    let tampered = Signature::<P>::try_from(tampered).expect("valid length");
+    assert_eq!(tampered.encoded_solution(), sig.encoded_solution());
+    assert!(keypair.verifying_key().verify(msg, &tampered).is_err());
+}
+
+#[test]
+fn signature_layout_mayo1() {
+    signature_layout::<Mayo1>();
+}
+
+#[test]
+fn signature_layout_mayo2() {
+    signature_layout::<Mayo2>();
+}
+
+#[test]
+fn signature_layout_mayo3() {
+    signature_layout::<Mayo3>();
+}
+
+#[test]
+fn signature_layout_mayo5() {
+    signature_layout::<Mayo5>();
+}
+
+fn sign_deterministic<P: pq_mayo::MayoParameter>() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen failed");
+    let msg = b"deterministic signing test";
+
+    let sig1 = keypair
+        .signing_key()
+        .sign_deterministic(msg)
+        .expect("signing failed");
+    let sig2 = keypair
+        .signing_key()
+        .sign_deterministic(msg)
+        .expect("signing failed");
+    assert_eq!(sig1, sig2);
+
+    let expanded = keypair.signing_key().expand();
+    assert_eq!(
+        expanded.sign_deterministic(msg).expect("signing failed"),
+        sig1
+    );
+    keypair
+        .verifying_key()
+        .verify(msg, &sig1)
+        .expect("verification failed");
+
+    let other = keypair
+        .signing_key()
+        .sign_deterministic(b"another message")
+        .expect("signing failed");
+    assert_ne!(sig1, other);
+}
+
+#[test]
+fn sign_deterministic_mayo1() {
+    sign_deterministic::<Mayo1>();
+}
+
+#[test]
+fn sign_deterministic_mayo2() {
+    sign_deterministic::<Mayo2>();
+}
+
+#[test]
+fn sign_deterministic_mayo3() {
+    sign_deterministic::<Mayo3>();
+}
+
+#[test]
+fn sign_deterministic_mayo5() {
+    sign_deterministic::<Mayo5>();
+}
+
+#[test]
+fn verification_context_mayo1() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<Mayo1>::generate(&mut rng).expect("keygen failed");
+    let expanded = ExpandedVerifyingKey::<Mayo1>::from(keypair.verifying_key());
+    let mut context = VerificationContext::<Mayo1>::from(&expanded);
+    let msg = b"verification context test";
+
+    let sig = keypair
+        .signing_key()
+        .try_sign(msg.as_slice())
+        .expect("signing failed");
+    context
+        .verify(msg.as_slice(), &sig)
+        .expect("context verification failed");
+}
+
+fn expanded_secret_key_roundtrip<P: pq_mayo::MayoParameter>() {
+    let mut rng = rand::rng();
+    let keypair = KeyPair::<P>::generate(&mut rng).expect("keygen failed");
+    let esk = keypair.signing_key().expand().to_expanded_bytes();
+    assert_eq!(esk.len(), P::ESK_BYTES);
+
+    let restored = KeyPair::<P>::from_expanded_secret_key(&esk).expect("expanded secret key");
+    assert_eq!(keypair, restored);
+
+    assert!(KeyPair::<P>::from_expanded_secret_key(&esk[..P::ESK_BYTES - 1]).is_err());
+
+    // Corrupt the last byte of L; the seed still parses but expansion no longer matches.
+    let mut corrupted = esk.to_vec();
+    corrupted[P::ESK_BYTES - 1] ^= 0x10;
+    assert!(KeyPair::<P>::from_expanded_secret_key(&corrupted).is_err());
+}
+
+#[test]
+fn expanded_secret_key_roundtrip_mayo1() {
+    expanded_secret_key_roundtrip::<Mayo1>();
+}
+
+#[test]
+fn expanded_secret_key_roundtrip_mayo2() {
+    expanded_secret_key_roundtrip::<Mayo2>();
+}
+
+#[test]
+fn expanded_secret_key_roundtrip_mayo3() {
+    expanded_secret_key_roundtrip::<Mayo3>();
+}
+
+#[test]
+fn expanded_secret_key_roundtrip_mayo5() {
+    expanded_secret_key_roundtrip::<Mayo5>();
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn verify_many_matches_sequential_mayo1() {
+    let keypairs: Vec<_> = (0u8..6)
+        .map(|i| KeyPair::<Mayo1>::from_seed(&[i + 1; 24]).expect("from_seed"))
+        .collect();
+    let msgs: Vec<Vec<u8>> = (0u8..6).map(|i| vec![i; 32]).collect();
+    let sigs: Vec<_> = keypairs
+        .iter()
+        .zip(&msgs)
+        .map(|(kp, msg)| kp.signing_key().try_sign(msg).expect("signing failed"))
+        .collect();
+
+    // Odd items pair a signature with the next party's key, so they fail.
+    let items: Vec<_> = (0..6)
+        .map(|i| {
+            let key = if i % 2 == 0 { i } else { (i + 1) % 6 };
+            (keypairs[key].verifying_key(), msgs[i].as_slice(), &sigs[i])
+        })
+        .collect();
+
+    let parallel = pq_mayo::verify_many(&items);
+    let sequential: Vec<_> = items
+        .iter()
+        .map(|(vk, msg, sig)| vk.verify(msg, sig).is_ok())
+        .collect();
+    assert_eq!(
+        parallel.iter().map(Result::is_ok).collect::<Vec<_>>(),
+        sequential
+    );
+    assert_eq!(sequential, [true, false, true, false, true, false]);
+    assert!(
+        parallel[1]
+            .as_ref()
+            .is_err_and(pq_mayo::Error::is_verification_failure)
+    );
+}
+
+#[cfg(feature = "rayon")]
+fn generate_batch_matches_sequential<P: pq_mayo::MayoParameter>() {
+    use rand::SeedableRng;
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+    let batch = KeyPair::<P>::generate_batch(8, &mut rng).expect("batch keygen failed");
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+    let sequential = (0..8)
+        .map(|_| KeyPair::<P>::generate(&mut rng).expect("keygen failed"))
+        .collect::<Vec<_>>();
+
+    assert_eq!(batch, sequential);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn generate_batch_matches_sequential_mayo1() {
+    generate_batch_matches_sequential::<Mayo1>();
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn generate_batch_matches_sequential_mayo2() {
+    generate_batch_matches_sequential::<Mayo2>();
 }
@@ -1,7 +1,6 @@
 //! Public key, signing key, and signature size checks.
 
-use pq_mayo::{KeyPair, Mayo1, Mayo2, Mayo3, Mayo5, MayoParameter, Signature};
-use signature::Signer;
+use pq_mayo::{KeyPair, Mayo1, Mayo2, Mayo3, Mayo5, MayoParameter, ParamInfo, Signature};
 
 fn sizes<P: MayoParameter>(sk_bytes: usize, vk_bytes: usize, sig_bytes: usize) {
     assert_eq!(P::CSK_BYTES, sk_bytes);
@@ -13,7 +12,10 @@ fn sizes<P: MayoParameter>(sk_bytes: usize, vk_bytes: usize, sig_bytes: usize) {
     assert_eq!(keypair.signing_key().as_ref().len(), sk_bytes);
     assert_eq!(keypair.verifying_key().as_ref().len(), vk_bytes);
 
-    let sig = keypair.signing_key().try_sign(b"size check").expect("sign");
+    let sig = keypair
+        .signing_key()
+        .sign_with_rng(&mut rand::rng(), b"size check")
+        .expect("sign");
     assert_eq!(sig.as_ref().len(), sig_bytes);
     assert_eq!(
         Signature::<P>::try_from(sig.as_ref())
@@ -43,3 +45,21 @@ fn mayo3_sizes() {
 fn mayo5_sizes() {
     sizes::<Mayo5>(40, 5554, 964);
 }
+
+fn describe<P: MayoParameter>(name: &str, security_level: u8, sizes: (usize, usize, usize)) {
+    let info: ParamInfo = P::describe();
+    assert_eq!(info.name, name);
+    assert_eq!(info.name, P::NAME);
+    assert_eq!(info.security_level, security_level);
+    assert_eq!(P::SECURITY_LEVEL, security_level);
+    assert_eq!((info.sig_bytes, info.pk_bytes, info.sk_bytes), sizes);
+}
+
+/// The "Supported Parameter Sets" table in the crate docs.
+#[test]
+fn describe_matches_documented_table() {
+    describe::<Mayo1>("MAYO_1", 1, (454, 1420, 24));
+    describe::<Mayo2>("MAYO_2", 1, (216, 4368, 24));
+    describe::<Mayo3>("MAYO_3", 3, (681, 2986, 32));
+    describe::<Mayo5>("MAYO_5", 5, (964, 5554, 40));
+}
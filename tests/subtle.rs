@@ -0,0 +1,53 @@
+//! Tests for `VerifyingKey::verify_ct`.
+
+#![cfg(feature = "subtle")]
+
+use pq_mayo::{KeyPair, Mayo1, Mayo2, Mayo3, Mayo5, MayoParameter, Signature};
+
+fn verify_ct<P: MayoParameter>() {
+    let keypair = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    let vk = keypair.verifying_key();
+    let msg = b"constant-time verify";
+    let sig = keypair
+        .signing_key()
+        .sign_with_rng(&mut rand::rng(), msg)
+        .expect("signing failed");
+
+    assert!(bool::from(vk.verify_ct(msg, &sig)));
+    assert!(!bool::from(vk.verify_ct(b"other message", &sig)));
+
+    let mut tampered = sig.to_vec();
+    tampered[0] ^= 1;
+    let tampered = Signature::<P>::try_from(tampered).expect("valid length");
+    assert!(!bool::from(vk.verify_ct(msg, &tampered)));
+    assert!(vk.verify(msg, &tampered).is_err());
+
+    let other = KeyPair::<P>::generate(&mut rand::rng()).expect("keygen failed");
+    assert!(!bool::from(other.verifying_key().verify_ct(msg, &sig)));
+
+    // Results combine without branching until the end.
+    let both = vk.verify_ct(msg, &sig) & vk.verify_ct(msg, &tampered);
+    assert!(!bool::from(both));
+    let either = vk.verify_ct(msg, &sig) | vk.verify_ct(msg, &tampered);
+    assert!(bool::from(either));
+}
+
+#[test]
+fn verify_ct_mayo1() {
+    verify_ct::<Mayo1>();
+}
+
+#[test]
+fn verify_ct_mayo2() {
+    verify_ct::<Mayo2>();
+}
+
+#[test]
+fn verify_ct_mayo3() {
+    verify_ct::<Mayo3>();
+}
+
+#[test]
+fn verify_ct_mayo5() {
+    verify_ct::<Mayo5>();
+}
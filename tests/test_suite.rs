@@ -0,0 +1,10 @@
+//! The exported conformance suite, invoked the way a downstream crate would.
+
+#![cfg(feature = "test-utils")]
+
+use pq_mayo::{Mayo1, Mayo2};
+
+pq_mayo::mayo_test_suite!(mayo1, Mayo1);
+pq_mayo::mayo_test_suite!(mayo2, Mayo2);
+pq_mayo::mayo_test_suite!(mayo3, pq_mayo::Mayo3);
+pq_mayo::mayo_test_suite!(mayo5, pq_mayo::Mayo5);